@@ -0,0 +1,146 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Response body decoding.
+//!
+//! Textual bodies are decoded to UTF-8 using the charset from the
+//! `Content-Type` header (or a byte order mark), defaulting to UTF-8. Anything
+//! else is returned base64-encoded so it can travel inside JSON unchanged.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use encoding_rs::{Encoding, UTF_8};
+
+/// A response body in a form that can be embedded in JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodedBody {
+    /// Textual body decoded to UTF-8
+    Text {
+        /// The decoded text
+        text: String,
+        /// Name of the charset the body was decoded from
+        charset: &'static str,
+    },
+
+    /// Binary body
+    Binary {
+        /// Base64-encoded body
+        base64: String,
+    },
+}
+
+impl DecodedBody {
+    /// Decodes `body` according to the given `Content-Type` header value.
+    pub fn decode(body: &[u8], content_type: Option<&str>) -> Self {
+        let (essence, charset) = match content_type {
+            Some(content_type) => parse_content_type(content_type),
+            None => (String::new(), None),
+        };
+
+        let encoding = charset.and_then(|label| Encoding::for_label(label.as_bytes()));
+        if let Some(encoding) = encoding {
+            return Self::text(body, encoding);
+        }
+
+        if is_textual(&essence) || (essence.is_empty() && std::str::from_utf8(body).is_ok()) {
+            return Self::text(body, UTF_8);
+        }
+
+        Self::Binary {
+            base64: STANDARD.encode(body),
+        }
+    }
+
+    /// Returns whether the body was decoded as text.
+    pub fn is_text(&self) -> bool {
+        matches!(self, Self::Text { .. })
+    }
+
+    fn text(body: &[u8], encoding: &'static Encoding) -> Self {
+        // A byte order mark takes precedence over the declared charset
+        let (text, used, _) = encoding.decode(body);
+        Self::Text {
+            text: text.into_owned(),
+            charset: used.name(),
+        }
+    }
+}
+
+/// Splits a `Content-Type` value into its lowercased essence and charset parameter.
+fn parse_content_type(content_type: &str) -> (String, Option<&str>) {
+    let mut parts = content_type.split(';');
+    let essence = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+    let charset = parts.find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| value.trim().trim_matches('"'))
+    });
+    (essence, charset)
+}
+
+/// Returns whether a media type carries text.
+fn is_textual(essence: &str) -> bool {
+    essence.starts_with("text/")
+        || essence.ends_with("+json")
+        || essence.ends_with("+xml")
+        || matches!(
+            essence,
+            "application/json"
+                | "application/xml"
+                | "application/javascript"
+                | "application/ecmascript"
+                | "application/x-www-form-urlencoded"
+                | "image/svg+xml"
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decodes_declared_charset() {
+        let body = [0x63, 0x61, 0x66, 0xe9]; // "café" in ISO-8859-1
+        let decoded = DecodedBody::decode(&body, Some("text/plain; charset=\"ISO-8859-1\""));
+        assert_eq!(
+            decoded,
+            DecodedBody::Text {
+                text: "café".to_string(),
+                charset: "windows-1252",
+            }
+        );
+    }
+
+    #[test]
+    fn test_textual_types_default_to_utf8() {
+        let decoded = DecodedBody::decode("{\"a\":\"ü\"}".as_bytes(), Some("application/json"));
+        assert_eq!(
+            decoded,
+            DecodedBody::Text {
+                text: "{\"a\":\"ü\"}".to_string(),
+                charset: "UTF-8",
+            }
+        );
+    }
+
+    #[test]
+    fn test_binary_is_base64_encoded() {
+        let decoded = DecodedBody::decode(&[0x89, 0x50, 0x4e, 0x47], Some("image/png"));
+        assert_eq!(
+            decoded,
+            DecodedBody::Binary {
+                base64: "iVBORw==".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_untyped_body_is_sniffed() {
+        assert!(DecodedBody::decode(b"plain", None).is_text());
+        assert!(!DecodedBody::decode(&[0xff, 0xfe, 0x00, 0xd8], None).is_text());
+    }
+}
@@ -0,0 +1,309 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Connection establishment for the outbound HTTP client.
+//!
+//! The [`Connector`] resolves the target host through the [`Resolver`], opens
+//! a TCP connection to the first vetted address that accepts it and, for
+//! `https` URLs, performs the TLS
+//! handshake, configured from
+//! [`TlsConfig`](crate::config::security::TlsConfig) by [`tls::client_config`]. Each
+//! phase reports its own [`HttpError`] variant. When HTTP/2 and ALPN are
+//! enabled the TLS handshake offers `h2`, and the negotiated protocol is
+//! exposed on the resulting [`MaybeTlsStream`].
+
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use rustls::pki_types::ServerName;
+use rustls::ClientConfig;
+use socket2::{SockRef, TcpKeepalive};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::TlsConnector;
+use tracing::debug;
+use url::{Host, Url};
+
+use super::resolver::Resolver;
+use super::tls;
+use super::validator::UrlValidator;
+use crate::config::http::HttpConfig;
+use crate::config::security::SecurityConfig;
+use crate::error::http::HttpError;
+
+/// A connection to an origin server, with or without TLS.
+#[derive(Debug)]
+pub enum MaybeTlsStream {
+    /// Plaintext TCP connection
+    Plain(TcpStream),
+
+    /// TLS connection over TCP
+    Tls(Box<TlsStream<TcpStream>>),
+}
+
+impl MaybeTlsStream {
+    /// Returns whether HTTP/2 was negotiated during the TLS handshake.
+    pub fn negotiated_http2(&self) -> bool {
+        match self {
+            Self::Plain(_) => false,
+            Self::Tls(stream) => stream.get_ref().1.alpn_protocol() == Some(b"h2".as_slice()),
+        }
+    }
+}
+
+impl AsyncRead for MaybeTlsStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Plain(stream) => Pin::new(stream).poll_read(cx, buf),
+            Self::Tls(stream) => Pin::new(stream.as_mut()).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for MaybeTlsStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            Self::Plain(stream) => Pin::new(stream).poll_write(cx, buf),
+            Self::Tls(stream) => Pin::new(stream.as_mut()).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Plain(stream) => Pin::new(stream).poll_flush(cx),
+            Self::Tls(stream) => Pin::new(stream.as_mut()).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Plain(stream) => Pin::new(stream).poll_shutdown(cx),
+            Self::Tls(stream) => Pin::new(stream.as_mut()).poll_shutdown(cx),
+        }
+    }
+}
+
+/// Opens connections to origin servers.
+#[derive(Clone)]
+pub struct Connector {
+    /// TLS settings for `https` origins
+    tls: Arc<ClientConfig>,
+
+    /// Resolves hosts to the addresses that may be connected to
+    resolver: Resolver,
+
+    /// Time allowed for resolving, connecting and the TLS handshake
+    connect_timeout: Duration,
+
+    /// Idle time before TCP keep-alive probes are sent
+    keep_alive: Duration,
+}
+
+impl Connector {
+    /// Creates a connector from the HTTP client and security configuration.
+    pub fn new(config: &HttpConfig, security: &SecurityConfig) -> Result<Self, HttpError> {
+        let alpn = if security.tls.enable_alpn {
+            alpn_protocols(config.client.http2_enabled)
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            tls: Arc::new(tls::client_config(&security.tls, alpn)?),
+            resolver: Resolver::new(UrlValidator::new(&security.url_validation)),
+            connect_timeout: Duration::from_millis(config.connection_pool.connect_timeout_ms),
+            keep_alive: Duration::from_secs(config.connection_pool.keep_alive_sec),
+        })
+    }
+
+    /// Connects to the origin of `url`.
+    pub async fn connect(&self, url: &Url) -> Result<MaybeTlsStream, HttpError> {
+        tokio::time::timeout(self.connect_timeout, self.connect_inner(url))
+            .await
+            .map_err(|_| HttpError::ConnectTimeout(self.connect_timeout))?
+    }
+
+    async fn connect_inner(&self, url: &Url) -> Result<MaybeTlsStream, HttpError> {
+        let host = url
+            .host()
+            .ok_or_else(|| HttpError::InvalidUrl(format!("{url} has no host")))?;
+        let port = url
+            .port_or_known_default()
+            .ok_or_else(|| HttpError::InvalidUrl(format!("{url} has no port")))?;
+
+        let addrs = self.resolver.resolve(&host, port).await?;
+        let tcp = connect_any(&addrs).await?;
+        let keep_alive = TcpKeepalive::new().with_time(self.keep_alive);
+        if let Err(e) = SockRef::from(&tcp).set_tcp_keepalive(&keep_alive) {
+            debug!(error = %e, "Failed to enable TCP keep-alive");
+        }
+
+        match url.scheme() {
+            "http" => Ok(MaybeTlsStream::Plain(tcp)),
+            "https" => {
+                let server_name = match host {
+                    Host::Domain(domain) => ServerName::try_from(domain.to_string())
+                        .map_err(|e| HttpError::InvalidUrl(format!("{domain}: {e}")))?,
+                    Host::Ipv4(ip) => ServerName::from(std::net::IpAddr::V4(ip)),
+                    Host::Ipv6(ip) => ServerName::from(std::net::IpAddr::V6(ip)),
+                };
+                let tls = TlsConnector::from(self.tls.clone())
+                    .connect(server_name, tcp)
+                    .await
+                    .map_err(|e| tls::handshake_error(&host, e))?;
+                Ok(MaybeTlsStream::Tls(Box::new(tls)))
+            }
+            scheme => Err(HttpError::InvalidUrl(format!("unsupported scheme: {scheme}"))),
+        }
+    }
+}
+
+impl std::fmt::Debug for Connector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Connector")
+            .field("connect_timeout", &self.connect_timeout)
+            .field("keep_alive", &self.keep_alive)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns the ALPN protocols offered during the TLS handshake.
+fn alpn_protocols(http2_enabled: bool) -> Vec<Vec<u8>> {
+    if http2_enabled {
+        vec![b"h2".to_vec(), b"http/1.1".to_vec()]
+    } else {
+        vec![b"http/1.1".to_vec()]
+    }
+}
+
+/// Connects to the first address that accepts a connection.
+async fn connect_any(addrs: &[SocketAddr]) -> Result<TcpStream, HttpError> {
+    let mut last_error = None;
+    for addr in addrs {
+        match TcpStream::connect(addr).await {
+            Ok(stream) => {
+                let _ = stream.set_nodelay(true);
+                return Ok(stream);
+            }
+            Err(e) => last_error = Some(format!("{addr}: {e}")),
+        }
+    }
+    Err(HttpError::ConnectionCreationError(last_error.unwrap_or_default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::security::TlsConfig;
+    use crate::tests::http_server::loopback_security;
+    use crate::tests::tls_server::{fixture, spawn_https_server};
+    use bytes::Bytes;
+    use http_body_util::Full;
+    use hyper::body::Incoming;
+    use hyper::{Request, Response};
+
+    fn tls_config(configure: impl FnOnce(&mut TlsConfig)) -> TlsConfig {
+        let mut config = TlsConfig {
+            ca_file: Some(fixture("ca.pem")),
+            ..Default::default()
+        };
+        configure(&mut config);
+        config
+    }
+
+    async fn connect(tls: &TlsConfig, require_client_cert: bool) -> Result<MaybeTlsStream, HttpError> {
+        let addr = spawn_https_server(
+            |_request: Request<Incoming>| async move { Response::new(Full::new(Bytes::from_static(b"ok"))) },
+            require_client_cert,
+        )
+        .await;
+        let url = Url::parse(&format!("https://localhost:{}/", addr.port())).unwrap();
+        let mut security = loopback_security();
+        security.tls = tls.clone();
+        Connector::new(&HttpConfig::default(), &security).unwrap().connect(&url).await
+    }
+
+    #[test]
+    fn test_alpn_protocols_follow_config() {
+        let mut config = HttpConfig::default();
+        let mut security = SecurityConfig::default();
+        let connector = Connector::new(&config, &security).unwrap();
+        assert_eq!(connector.tls.alpn_protocols, vec![b"h2".to_vec(), b"http/1.1".to_vec()]);
+
+        config.client.http2_enabled = false;
+        let connector = Connector::new(&config, &security).unwrap();
+        assert_eq!(connector.tls.alpn_protocols, vec![b"http/1.1".to_vec()]);
+
+        security.tls.enable_alpn = false;
+        let connector = Connector::new(&config, &security).unwrap();
+        assert!(connector.tls.alpn_protocols.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_private_ca_is_trusted() {
+        let stream = connect(&tls_config(|_| {}), false).await.unwrap();
+        assert!(stream.negotiated_http2());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_issuer_reports_chain() {
+        let error = connect(&TlsConfig::default(), false).await.unwrap_err();
+
+        let HttpError::TlsError(message) = error else {
+            panic!("expected a TLS error, got {error}");
+        };
+        assert!(message.contains("UnknownIssuer"), "{message}");
+        assert!(
+            message.contains("subject \"CN=localhost\" issued by \"CN=Mauka Test CA, O=Mauka MCP\""),
+            "{message}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verification_can_be_disabled() {
+        let tls = TlsConfig {
+            verify_certificates: false,
+            ..Default::default()
+        };
+        assert!(connect(&tls, false).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_client_certificates() {
+        let tls = tls_config(|config| {
+            config.client_cert_file = Some(fixture("client.pem"));
+            config.client_key_file = Some(fixture("client.key"));
+        });
+        let mut stream = connect(&tls, true).await.unwrap();
+        // The server checks the client certificate after the client finishes
+        // its side of the handshake, so exchange data to observe the outcome
+        let (mut sender, connection) = hyper::client::conn::http1::handshake(hyper_util::rt::TokioIo::new(stream))
+            .await
+            .unwrap();
+        tokio::spawn(connection);
+        let request = Request::builder().uri("/").header("host", "localhost").body(Full::<Bytes>::default()).unwrap();
+        assert!(sender.send_request(request).await.unwrap().status().is_success());
+
+        stream = connect(&tls_config(|_| {}), true).await.unwrap();
+        let mut buf = [0; 1];
+        let read = tokio::io::AsyncReadExt::read(&mut stream, &mut buf).await;
+        assert!(read.is_err(), "server must reject clients without a certificate");
+    }
+
+    #[tokio::test]
+    async fn test_minimum_version() {
+        let tls = tls_config(|config| config.min_tls_version = "1.3".to_string());
+        let MaybeTlsStream::Tls(stream) = connect(&tls, false).await.unwrap() else {
+            panic!("expected a TLS stream");
+        };
+        assert_eq!(stream.get_ref().1.protocol_version(), Some(rustls::ProtocolVersion::TLSv1_3));
+    }
+}
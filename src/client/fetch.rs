@@ -0,0 +1,518 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! The outbound HTTP client.
+//!
+//! [`HttpClient::fetch`] sends a [`FetchRequest`], follows redirects as
+//! configured and reads the whole response body, bounded by
+//! [`HttpClientConfig::max_response_bytes`]. The timeout applies to the fetch
+//! as a whole, redirects included. Connections are leased from a
+//! [`MolokaiConnectionPool`] and returned to it once a body has been read;
+//! HTTP/2 connections carry several fetches at once, one stream each.
+
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use hyper::body::Incoming;
+use hyper::header::{
+    HeaderValue, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, COOKIE, HOST, LOCATION, PROXY_AUTHORIZATION,
+    USER_AGENT,
+};
+use hyper::{Method, Request, Response, StatusCode};
+use tracing::debug;
+use url::{Position, Url};
+
+use super::connector::Connector;
+use super::pool::{ConnectionLease, MolokaiConnectionPool, PoolStats};
+use super::types::{FetchRequest, FetchResponse, FetchTiming};
+use super::validator::{UrlRejection, UrlValidator};
+use crate::config::http::{HttpClientConfig, HttpConfig};
+use crate::config::security::SecurityConfig;
+use crate::error::http::HttpError;
+
+/// Client for outbound HTTP requests.
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    /// Client settings
+    config: HttpClientConfig,
+
+    /// Pool of connections to origin servers
+    pool: MolokaiConnectionPool,
+
+    /// Checks every URL before it is requested
+    validator: UrlValidator,
+}
+
+impl HttpClient {
+    /// Creates a client from the HTTP and security configuration.
+    pub fn new(config: &HttpConfig, security: &SecurityConfig) -> Result<Self, HttpError> {
+        Ok(Self {
+            config: config.client.clone(),
+            pool: MolokaiConnectionPool::new(config, Connector::new(config, security)?),
+            validator: UrlValidator::new(&security.url_validation),
+        })
+    }
+
+    /// Returns the client settings.
+    pub fn config(&self) -> &HttpClientConfig {
+        &self.config
+    }
+
+    /// Returns the validator URLs are checked with.
+    pub fn validator(&self) -> &UrlValidator {
+        &self.validator
+    }
+
+    /// Performs a request and reads the full response.
+    ///
+    /// Non-success statuses are returned as responses; only failures to obtain a
+    /// response at all are errors. The URL and every redirect target must pass
+    /// the [`UrlValidator`].
+    pub async fn fetch(&self, request: FetchRequest) -> Result<FetchResponse, HttpError> {
+        let timeout = request
+            .timeout
+            .unwrap_or_else(|| Duration::from_millis(self.config.request_timeout_ms));
+
+        tokio::time::timeout(timeout, self.execute(request))
+            .await
+            .map_err(|_| HttpError::RequestTimeout(timeout))?
+    }
+
+    async fn execute(&self, mut request: FetchRequest) -> Result<FetchResponse, HttpError> {
+        let started = Instant::now();
+        let follow_redirects = request.follow_redirects.unwrap_or(self.config.follow_redirects);
+        let max_redirects = request.max_redirects.unwrap_or(self.config.max_redirects);
+        let mut connect = Duration::ZERO;
+        let mut redirects = 0;
+
+        loop {
+            self.validator.validate(&request.url)?;
+            let (lease, response, latency) = self.send(&request, &mut connect).await?;
+            let first_byte = started.elapsed();
+
+            if follow_redirects {
+                if let Some(location) = redirect_location(&request.url, &response)? {
+                    if redirects == max_redirects {
+                        return Err(HttpError::HttpStatus {
+                            status: response.status().as_u16(),
+                            message: format!("exceeded the maximum of {max_redirects} redirects"),
+                        });
+                    }
+                    // Drain the redirect body so the connection can be reused
+                    let status = response.status();
+                    read_body(response.into_body(), self.config.max_response_bytes).await?;
+                    lease.release(latency);
+
+                    debug!(from = %request.url, to = %location, "Following redirect");
+                    redirects += 1;
+                    request = redirect_request(request, status, location);
+                    continue;
+                }
+            }
+
+            let (parts, body) = response.into_parts();
+            let body = read_body(body, self.config.max_response_bytes).await?;
+            lease.release(latency);
+
+            return Ok(FetchResponse {
+                url: request.url,
+                status: parts.status,
+                version: parts.version,
+                headers: parts.headers,
+                body,
+                redirects,
+                timing: FetchTiming {
+                    connect,
+                    first_byte,
+                    total: started.elapsed(),
+                },
+            });
+        }
+    }
+
+    /// Sends one hop of a fetch on a pooled connection.
+    ///
+    /// A reused connection may have been closed by the server while idle; the
+    /// request is then retried once on a fresh connection. Returns the lease,
+    /// the response head and the time it took to arrive.
+    async fn send(
+        &self,
+        request: &FetchRequest,
+        connect: &mut Duration,
+    ) -> Result<(ConnectionLease, Response<Incoming>, Duration), HttpError> {
+        loop {
+            let acquire_started = Instant::now();
+            let mut lease = self.pool.acquire(&request.url).await?;
+            if !lease.is_reused() {
+                *connect += acquire_started.elapsed();
+            }
+
+            let sent = Instant::now();
+            let http_request = self.build_request(request, lease.is_http2())?;
+            match lease.send_request(http_request).await {
+                Ok(response) => return Ok((lease, response, sent.elapsed())),
+                Err(e) if lease.is_reused() && (e.is_canceled() || e.is_closed() || e.is_incomplete_message()) => {
+                    debug!(url = %request.url, error = %e, "Pooled connection went stale, retrying");
+                }
+                Err(e) => return Err(request_error(e)),
+            }
+        }
+    }
+
+    /// Returns a snapshot of the connection pool statistics.
+    pub fn pool_stats(&self) -> PoolStats {
+        self.pool.stats()
+    }
+
+    /// Builds the HTTP request for one hop.
+    ///
+    /// HTTP/1.1 requests carry the origin-form target and a `Host` header;
+    /// HTTP/2 requests carry the absolute URI, from which the `:authority`
+    /// pseudo-header is derived.
+    fn build_request(&self, request: &FetchRequest, http2: bool) -> Result<Request<Full<Bytes>>, HttpError> {
+        let url = &request.url;
+        let mut headers = request.headers.clone();
+        let target = if http2 {
+            headers.remove(HOST);
+            &url[..Position::AfterQuery]
+        } else {
+            let host = match url.port() {
+                Some(port) => format!("{}:{port}", url.host_str().unwrap_or_default()),
+                None => url.host_str().unwrap_or_default().to_string(),
+            };
+            headers.insert(
+                HOST,
+                HeaderValue::from_str(&host).map_err(|e| HttpError::InvalidUrl(format!("{host}: {e}")))?,
+            );
+            &url[Position::BeforePath..Position::AfterQuery]
+        };
+        if !headers.contains_key(USER_AGENT) {
+            let user_agent = HeaderValue::from_str(&self.config.user_agent)
+                .map_err(|e| HttpError::InvalidRequest(format!("invalid user agent: {e}")))?;
+            headers.insert(USER_AGENT, user_agent);
+        }
+
+        let mut http_request = Request::builder()
+            .method(request.method.clone())
+            .uri(target)
+            .body(Full::new(request.body.clone().unwrap_or_default()))
+            .map_err(|e| HttpError::InvalidRequest(e.to_string()))?;
+        *http_request.headers_mut() = headers;
+        Ok(http_request)
+    }
+}
+
+/// Maps a failure to obtain a response.
+fn request_error(error: hyper::Error) -> HttpError {
+    if error.is_parse() {
+        HttpError::ResponseDecodeError(format!("malformed response: {error}"))
+    } else {
+        HttpError::InvalidConnection(error.to_string())
+    }
+}
+
+/// Returns the target of a redirect response, resolved against the request URL.
+fn redirect_location(url: &Url, response: &Response<Incoming>) -> Result<Option<Url>, HttpError> {
+    if !matches!(
+        response.status(),
+        StatusCode::MOVED_PERMANENTLY
+            | StatusCode::FOUND
+            | StatusCode::SEE_OTHER
+            | StatusCode::TEMPORARY_REDIRECT
+            | StatusCode::PERMANENT_REDIRECT
+    ) {
+        return Ok(None);
+    }
+
+    let Some(location) = response.headers().get(LOCATION) else {
+        return Ok(None);
+    };
+    let location = location
+        .to_str()
+        .map_err(|_| UrlRejection::Malformed.error("redirect location is not valid ASCII"))?;
+    url.join(location)
+        .map(Some)
+        .map_err(|e| UrlRejection::Malformed.error(format!("invalid redirect location {location}: {e}")))
+}
+
+/// Rewrites a request to follow a redirect.
+///
+/// `303 See Other` and a `POST` answered with `301`/`302` continue as a
+/// bodyless `GET`, as browsers do. Credentials are not forwarded to a
+/// different origin.
+fn redirect_request(mut request: FetchRequest, status: StatusCode, location: Url) -> FetchRequest {
+    let switch_to_get = (status == StatusCode::SEE_OTHER && request.method != Method::HEAD)
+        || (matches!(status, StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND) && request.method == Method::POST);
+    if switch_to_get {
+        request.method = Method::GET;
+        request.body = None;
+        request.headers.remove(CONTENT_TYPE);
+        request.headers.remove(CONTENT_LENGTH);
+    }
+
+    if location.origin() != request.url.origin() {
+        request.headers.remove(AUTHORIZATION);
+        request.headers.remove(PROXY_AUTHORIZATION);
+        request.headers.remove(COOKIE);
+    }
+
+    request.url = location;
+    request
+}
+
+/// Reads a response body of at most `limit` bytes.
+async fn read_body(body: Incoming, limit: usize) -> Result<Bytes, HttpError> {
+    match Limited::new(body, limit).collect().await {
+        Ok(collected) => Ok(collected.to_bytes()),
+        Err(e) if e.is::<LengthLimitError>() => Err(HttpError::ContentValidationError(format!(
+            "response body exceeds {limit} bytes"
+        ))),
+        Err(e) => Err(HttpError::ResponseDecodeError(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::http_server::{loopback_security, spawn_http_server};
+    use hyper::header::HeaderMap;
+
+    fn client() -> HttpClient {
+        HttpClient::new(&HttpConfig::default(), &loopback_security()).unwrap()
+    }
+
+    fn url(addr: std::net::SocketAddr, path: &str) -> Url {
+        Url::parse(&format!("http://{addr}{path}")).unwrap()
+    }
+
+    async fn echo_server() -> std::net::SocketAddr {
+        spawn_http_server(|request: Request<Incoming>| async move {
+            let (parts, body) = request.into_parts();
+            match parts.uri.path() {
+                "/redirect" => Response::builder()
+                    .status(StatusCode::FOUND)
+                    .header(LOCATION, "/echo?redirected=1")
+                    .body(Full::default())
+                    .unwrap(),
+                "/private" => Response::builder()
+                    .status(StatusCode::FOUND)
+                    .header(LOCATION, "http://10.0.0.1/admin")
+                    .body(Full::default())
+                    .unwrap(),
+                "/loop" => Response::builder()
+                    .status(StatusCode::TEMPORARY_REDIRECT)
+                    .header(LOCATION, "/loop")
+                    .body(Full::default())
+                    .unwrap(),
+                "/slow" => {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    Response::new(Full::default())
+                }
+                _ => {
+                    let body = body.collect().await.unwrap().to_bytes();
+                    let echo = serde_json::json!({
+                        "method": parts.method.as_str(),
+                        "uri": parts.uri.to_string(),
+                        "userAgent": parts.headers.get(USER_AGENT).map(|v| v.to_str().unwrap()),
+                        "custom": parts.headers.get("x-custom").map(|v| v.to_str().unwrap()),
+                        "body": String::from_utf8_lossy(&body),
+                    });
+                    Response::builder()
+                        .header(CONTENT_TYPE, "application/json")
+                        .body(Full::new(Bytes::from(echo.to_string())))
+                        .unwrap()
+                }
+            }
+        })
+        .await
+    }
+
+    fn json(response: &FetchResponse) -> serde_json::Value {
+        serde_json::from_slice(&response.body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_fetch_sends_method_headers_and_body() {
+        let addr = echo_server().await;
+        let mut headers = HeaderMap::new();
+        headers.insert("x-custom", HeaderValue::from_static("yes"));
+
+        let response = client()
+            .fetch(
+                FetchRequest::new(Method::POST, url(addr, "/echo?q=1"))
+                    .with_headers(headers)
+                    .with_body("payload"),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(response.content_type(), Some("application/json"));
+        let echo = json(&response);
+        assert_eq!(echo["method"], "POST");
+        assert_eq!(echo["uri"], "/echo?q=1");
+        assert_eq!(echo["userAgent"], "Mauka-MCP/1.0");
+        assert_eq!(echo["custom"], "yes");
+        assert_eq!(echo["body"], "payload");
+        assert!(response.timing.total >= response.timing.first_byte);
+    }
+
+    #[tokio::test]
+    async fn test_post_redirect_becomes_get() {
+        let addr = echo_server().await;
+        let response = client()
+            .fetch(FetchRequest::new(Method::POST, url(addr, "/redirect")).with_body("payload"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.redirects, 1);
+        assert_eq!(response.url, url(addr, "/echo?redirected=1"));
+        let echo = json(&response);
+        assert_eq!(echo["method"], "GET");
+        assert_eq!(echo["body"], "");
+    }
+
+    #[tokio::test]
+    async fn test_connections_are_pooled() {
+        let addr = echo_server().await;
+        let client = client();
+
+        // The redirect and both fetches share one keep-alive connection
+        client.fetch(FetchRequest::get(url(addr, "/redirect"))).await.unwrap();
+        let response = client.fetch(FetchRequest::get(url(addr, "/echo"))).await.unwrap();
+
+        assert_eq!(response.timing.connect, Duration::ZERO);
+        let stats = client.pool_stats();
+        assert_eq!(stats.created, 1);
+        assert_eq!(stats.reused, 2);
+    }
+
+    #[tokio::test]
+    async fn test_http2_prior_knowledge_multiplexes_fetches() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        // Track the most requests the server sees at once
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let addr = spawn_http_server({
+            let (in_flight, peak) = (in_flight.clone(), peak.clone());
+            move |request: Request<Incoming>| {
+                let (in_flight, peak) = (in_flight.clone(), peak.clone());
+                async move {
+                    let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(current, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    Response::new(Full::new(Bytes::from(format!("{:?} {}", request.version(), request.uri()))))
+                }
+            }
+        })
+        .await;
+
+        let mut config = HttpConfig::default();
+        config.connection_pool.max_connections_per_host = 1;
+        config.client.http2_max_concurrent_streams = 2;
+        config.client.http2_prior_knowledge_hosts = vec!["127.0.0.1".to_string()];
+        let client = HttpClient::new(&config, &loopback_security()).unwrap();
+
+        let fetches = (0..4).map(|i| client.fetch(FetchRequest::get(url(addr, &format!("/{i}")))));
+        for (i, response) in futures::future::join_all(fetches).await.into_iter().enumerate() {
+            let response = response.unwrap();
+            assert_eq!(response.version, hyper::Version::HTTP_2);
+            assert_eq!(response.body, format!("HTTP/2.0 http://{addr}/{i}"));
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        let stats = client.pool_stats();
+        assert_eq!(stats.created, 1);
+        assert_eq!(stats.http2_connections, 1);
+    }
+
+    #[tokio::test]
+    async fn test_redirects_can_be_disabled() {
+        let addr = echo_server().await;
+        let response = client()
+            .fetch(FetchRequest::get(url(addr, "/redirect")).with_redirects(false, None))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status, StatusCode::FOUND);
+        assert_eq!(response.redirects, 0);
+    }
+
+    #[tokio::test]
+    async fn test_redirect_limit() {
+        let addr = echo_server().await;
+        let error = client()
+            .fetch(FetchRequest::get(url(addr, "/loop")).with_redirects(true, Some(3)))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, HttpError::HttpStatus { status: 307, .. }), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_urls_are_validated() {
+        let addr = echo_server().await;
+
+        let error = HttpClient::new(&HttpConfig::default(), &SecurityConfig::default())
+            .unwrap()
+            .fetch(FetchRequest::get(url(addr, "/echo")))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("loopback_address"), "{error}");
+
+        // Redirect targets are validated before they are followed
+        let error = client().fetch(FetchRequest::get(url(addr, "/private"))).await.unwrap_err();
+        assert!(matches!(&error, HttpError::InvalidUrl(message) if message.starts_with("private_address")), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let addr = echo_server().await;
+        let error = client()
+            .fetch(FetchRequest::get(url(addr, "/slow")).with_timeout(Duration::from_millis(100)))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, HttpError::RequestTimeout(_)), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_response_size_limit() {
+        let addr = spawn_http_server(|_request: Request<Incoming>| async move {
+            Response::new(Full::new(Bytes::from(vec![b'a'; 64])))
+        })
+        .await;
+        let mut config = HttpConfig::default();
+        config.client.max_response_bytes = 16;
+
+        let error = HttpClient::new(&config, &loopback_security())
+            .unwrap()
+            .fetch(FetchRequest::get(url(addr, "/")))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, HttpError::ContentValidationError(_)), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_connection_errors() {
+        // Nothing listens on a port that was just released
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let error = client().fetch(FetchRequest::get(url(addr, "/"))).await.unwrap_err();
+        assert!(matches!(error, HttpError::ConnectionCreationError(_)), "{error}");
+
+        let unresolvable = Url::parse("http://does-not-exist.invalid/").unwrap();
+        let error = client().fetch(FetchRequest::get(unresolvable)).await.unwrap_err();
+        assert!(matches!(error, HttpError::DnsResolutionFailed(_)), "{error}");
+    }
+}
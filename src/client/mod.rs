@@ -0,0 +1,29 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Outbound HTTP client core.
+//!
+//! The [`HttpClient`] performs the fetches behind the web tools. It is
+//! configured from [`HttpConfig`](crate::config::http::HttpConfig), follows
+//! redirects itself, checks every URL it requests with the [`UrlValidator`],
+//! reuses connections through the [`MolokaiConnectionPool`] and reports every
+//! failure as an [`HttpError`](crate::error::http::HttpError).
+
+pub mod body;
+pub mod connector;
+pub mod fetch;
+pub mod pool;
+pub mod resolver;
+pub mod tls;
+pub mod types;
+pub mod validator;
+
+// Re-exports
+pub use body::DecodedBody;
+pub use fetch::HttpClient;
+pub use pool::{MolokaiConnectionPool, PoolStats};
+pub use types::{FetchRequest, FetchResponse, FetchTiming};
+pub use validator::{UrlRejection, UrlValidator};
@@ -0,0 +1,1148 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Molokai adaptive connection pool.
+//!
+//! Connections are pooled per origin ([`PoolKey`]: scheme, host and port) and
+//! handed out as [`ConnectionLease`]s. The pool enforces the settings of
+//! [`ConnectionPoolConfig`]:
+//!
+//! - At most `max_connections_per_host` connections per origin are open or
+//!   being opened at once, whether leased or idle. Callers beyond that wait
+//!   in FIFO order and fail with [`HttpError::ConnectionPoolExhausted`] once
+//!   they have waited `connect_timeout_ms`.
+//! - Idle connections are kept for up to `max_idle_time_sec`, and at most
+//!   `max_idle_connections` idle HTTP/1.1 connections across all origins.
+//!   An idle connection keeps its connection slot, so a caller waiting on a
+//!   saturated origin is handed the next connection returned to the pool.
+//! - Every `health_check_interval_sec` a background task drops idle
+//!   connections that have expired or been closed by the peer.
+//!
+//! HTTP/2 connections, negotiated through ALPN or opened with prior knowledge
+//! for the hosts listed in `http2_prior_knowledge_hosts`, are shared: each
+//! lease on them is one stream, and up to `http2_max_concurrent_streams`
+//! leases use a connection at the same time. Once an origin is known to speak
+//! HTTP/2, new connections to it are opened one at a time so concurrent
+//! callers multiplex over the first one instead of each opening their own.
+//!
+//! The per-origin limit adapts to the latency observed on that origin: while
+//! latency stays near its baseline and callers are queueing the limit grows
+//! back towards the configured maximum, and when latency climbs well above the
+//! baseline the limit shrinks so the origin is not pushed harder.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use dashmap::DashMap;
+use http_body_util::Full;
+use hyper::body::Incoming;
+use hyper::client::conn::{http1, http2};
+use hyper::{Request, Response};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use parking_lot::Mutex;
+use serde::Serialize;
+use tokio::sync::{Notify, OwnedSemaphorePermit, Semaphore};
+use tracing::debug;
+use url::Url;
+
+use super::connector::{Connector, MaybeTlsStream};
+use crate::config::http::{ConnectionPoolConfig, HttpConfig};
+use crate::error::http::HttpError;
+
+/// Request sender of a pooled HTTP/1.1 connection.
+pub type Http1Sender = http1::SendRequest<Full<Bytes>>;
+
+/// Request sender of a pooled HTTP/2 connection.
+pub type Http2Sender = http2::SendRequest<Full<Bytes>>;
+
+/// Weight of the newest sample in the latency moving average.
+const LATENCY_EWMA_WEIGHT: f64 = 0.2;
+
+/// Latency above this multiple of the baseline shrinks the limit.
+const SHRINK_LATENCY_RATIO: f64 = 2.0;
+
+/// Latency below this multiple of the baseline allows the limit to grow.
+const GROW_LATENCY_RATIO: f64 = 1.25;
+
+/// Rate at which the baseline drifts up, so it can recover from an outlier.
+const BASELINE_DRIFT: f64 = 1.01;
+
+/// Origin a connection is pooled under.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PoolKey {
+    /// URL scheme
+    pub scheme: String,
+
+    /// Host name or address
+    pub host: String,
+
+    /// Port
+    pub port: u16,
+}
+
+impl PoolKey {
+    /// Returns the pool key of a URL.
+    pub fn from_url(url: &Url) -> Result<Self, HttpError> {
+        let host = url
+            .host_str()
+            .ok_or_else(|| HttpError::InvalidUrl(format!("{url} has no host")))?;
+        let port = url
+            .port_or_known_default()
+            .ok_or_else(|| HttpError::InvalidUrl(format!("{url} has no port")))?;
+        Ok(Self {
+            scheme: url.scheme().to_string(),
+            host: host.to_ascii_lowercase(),
+            port,
+        })
+    }
+}
+
+impl fmt::Display for PoolKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}://{}:{}", self.scheme, self.host, self.port)
+    }
+}
+
+/// Statistics of one origin.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostStats {
+    /// Origin, as `scheme://host:port`
+    pub origin: String,
+
+    /// Leases currently held, one per HTTP/1.1 connection or HTTP/2 stream
+    pub active: usize,
+
+    /// Idle HTTP/1.1 connections
+    pub idle: usize,
+
+    /// Open HTTP/2 connections
+    pub http2_connections: usize,
+
+    /// Callers waiting for a connection
+    pub waiting: usize,
+
+    /// Current adaptive connection limit
+    pub limit: usize,
+
+    /// Moving average of request latency in milliseconds
+    pub latency_ms: Option<f64>,
+}
+
+/// Statistics of the whole pool.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolStats {
+    /// Leases currently held, one per HTTP/1.1 connection or HTTP/2 stream
+    pub active: usize,
+
+    /// Idle HTTP/1.1 connections
+    pub idle: usize,
+
+    /// Open HTTP/2 connections
+    pub http2_connections: usize,
+
+    /// Callers waiting for a connection
+    pub waiting: usize,
+
+    /// Connections opened since the pool was created
+    pub created: u64,
+
+    /// Leases served by an existing connection
+    pub reused: u64,
+
+    /// Idle connections dropped because they expired or were closed
+    pub reaped: u64,
+
+    /// Callers that gave up waiting for a connection
+    pub exhausted: u64,
+
+    /// Per-origin statistics
+    pub hosts: Vec<HostStats>,
+}
+
+/// HTTP/2 settings of the pool.
+#[derive(Debug, Clone)]
+struct Http2Settings {
+    /// Whether HTTP/2 may be used at all
+    enabled: bool,
+
+    /// Streams a connection carries at once
+    max_concurrent_streams: usize,
+
+    /// Locally reset streams tracked per connection
+    max_idle_streams: usize,
+
+    /// Plaintext hosts that speak HTTP/2 without negotiation
+    prior_knowledge_hosts: Vec<String>,
+}
+
+/// An idle HTTP/1.1 connection.
+struct IdleConnection {
+    /// Request sender
+    sender: Http1Sender,
+
+    /// When the connection was returned to the pool
+    idle_since: Instant,
+
+    /// Connection slot of the origin, held while the connection is open
+    slot: OwnedSemaphorePermit,
+}
+
+/// An HTTP/2 connection shared by concurrent leases.
+struct Http2Connection {
+    /// Request sender, cloned into each lease
+    sender: Http2Sender,
+
+    /// One permit per stream that may be open at once
+    streams: Arc<Semaphore>,
+
+    /// Number of streams the connection was created with
+    max_streams: usize,
+
+    /// When the last stream finished
+    last_active: Mutex<Instant>,
+
+    /// Connection slot of the origin, taken when the connection is dropped
+    /// from the pool
+    slot: Mutex<Option<OwnedSemaphorePermit>>,
+}
+
+impl Http2Connection {
+    /// Returns whether no streams are open.
+    fn is_idle(&self) -> bool {
+        self.streams.available_permits() == self.max_streams
+    }
+}
+
+/// Latency observations of one origin.
+#[derive(Debug, Default)]
+struct LatencyTracker {
+    /// Exponentially weighted moving average, in seconds
+    average: Option<f64>,
+
+    /// Lowest recent average, in seconds
+    baseline: Option<f64>,
+}
+
+impl LatencyTracker {
+    /// Adds a sample and returns the new average and baseline.
+    fn record(&mut self, sample: Duration) -> (f64, f64) {
+        let sample = sample.as_secs_f64();
+        let average = match self.average {
+            Some(average) => average * (1.0 - LATENCY_EWMA_WEIGHT) + sample * LATENCY_EWMA_WEIGHT,
+            None => sample,
+        };
+        let baseline = match self.baseline {
+            Some(baseline) => (baseline * BASELINE_DRIFT).min(average),
+            None => average,
+        };
+        self.average = Some(average);
+        self.baseline = Some(baseline);
+        (average, baseline)
+    }
+}
+
+/// Connections of one origin.
+struct HostPool {
+    /// Origin of the connections
+    key: PoolKey,
+
+    /// One permit per connection that may be open
+    permits: Arc<Semaphore>,
+
+    /// Idle HTTP/1.1 connections, most recently used last
+    idle: Mutex<VecDeque<IdleConnection>>,
+
+    /// Open HTTP/2 connections
+    multiplexed: Mutex<Vec<Arc<Http2Connection>>>,
+
+    /// Whether the origin has negotiated HTTP/2 before
+    speaks_http2: AtomicBool,
+
+    /// Connections currently being opened
+    connecting: AtomicUsize,
+
+    /// Signalled when HTTP/2 stream capacity or an idle connection becomes
+    /// available
+    stream_available: Notify,
+
+    /// Current connection limit
+    limit: AtomicUsize,
+
+    /// Upper bound of the limit
+    max_limit: usize,
+
+    /// Lower bound of the limit
+    min_limit: usize,
+
+    /// Permits to retire as they are released, after the limit shrank
+    shrink_debt: AtomicUsize,
+
+    /// Leases currently held
+    active: AtomicUsize,
+
+    /// Callers waiting for a connection
+    waiting: AtomicUsize,
+
+    /// Latency observations
+    latency: Mutex<LatencyTracker>,
+}
+
+impl HostPool {
+    fn new(key: PoolKey, max_limit: usize) -> Self {
+        Self {
+            key,
+            permits: Arc::new(Semaphore::new(max_limit)),
+            idle: Mutex::new(VecDeque::new()),
+            multiplexed: Mutex::new(Vec::new()),
+            speaks_http2: AtomicBool::new(false),
+            connecting: AtomicUsize::new(0),
+            stream_available: Notify::new(),
+            limit: AtomicUsize::new(max_limit),
+            max_limit,
+            min_limit: (max_limit / 4).max(1),
+            shrink_debt: AtomicUsize::new(0),
+            active: AtomicUsize::new(0),
+            waiting: AtomicUsize::new(0),
+            latency: Mutex::new(LatencyTracker::default()),
+        }
+    }
+
+    /// Opens a stream on an HTTP/2 connection with spare capacity.
+    fn try_stream(&self) -> Option<(Arc<Http2Connection>, OwnedSemaphorePermit)> {
+        let mut multiplexed = self.multiplexed.lock();
+        self.retain_http2(&mut multiplexed, |connection| !connection.sender.is_closed());
+        multiplexed.iter().find_map(|connection| {
+            let stream = connection.streams.clone().try_acquire_owned().ok()?;
+            Some((connection.clone(), stream))
+        })
+    }
+
+    /// Drops the HTTP/2 connections `keep` rejects and returns their connection
+    /// slots. Returns how many were dropped.
+    fn retain_http2(
+        &self,
+        multiplexed: &mut Vec<Arc<Http2Connection>>,
+        mut keep: impl FnMut(&Http2Connection) -> bool,
+    ) -> usize {
+        let before = multiplexed.len();
+        multiplexed.retain(|connection| {
+            let kept = keep(connection);
+            if !kept {
+                if let Some(slot) = connection.slot.lock().take() {
+                    self.release_permit(slot);
+                }
+            }
+            kept
+        });
+        before - multiplexed.len()
+    }
+
+    /// Records a request latency and adapts the connection limit to it.
+    fn record_latency(&self, sample: Duration) {
+        let (average, baseline) = self.latency.lock().record(sample);
+        let limit = self.limit.load(Ordering::Acquire);
+        let saturated = self.waiting.load(Ordering::Acquire) > 0 || self.permits.available_permits() == 0;
+
+        if average > baseline * SHRINK_LATENCY_RATIO && limit > self.min_limit {
+            self.shrink();
+        } else if average <= baseline * GROW_LATENCY_RATIO && saturated && limit < self.max_limit {
+            self.grow();
+        }
+    }
+
+    fn shrink(&self) {
+        let limit = self.limit.fetch_sub(1, Ordering::AcqRel) - 1;
+        if self.permits.forget_permits(1) == 0 {
+            // Every permit is leased; retire the next one to come back
+            self.shrink_debt.fetch_add(1, Ordering::AcqRel);
+        }
+        debug!(origin = %self.key, limit, "Shrinking connection limit");
+    }
+
+    fn grow(&self) {
+        let limit = self.limit.fetch_add(1, Ordering::AcqRel) + 1;
+        let cancelled_debt = self
+            .shrink_debt
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |debt| debt.checked_sub(1))
+            .is_ok();
+        if !cancelled_debt {
+            self.permits.add_permits(1);
+        }
+        debug!(origin = %self.key, limit, "Growing connection limit");
+    }
+
+    /// Returns a permit, retiring it instead if the limit shrank.
+    fn release_permit(&self, permit: OwnedSemaphorePermit) {
+        let retire = self
+            .shrink_debt
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |debt| debt.checked_sub(1))
+            .is_ok();
+        if retire {
+            permit.forget();
+        }
+    }
+
+    fn stats(&self) -> HostStats {
+        HostStats {
+            origin: self.key.to_string(),
+            active: self.active.load(Ordering::Acquire),
+            idle: self.idle.lock().len(),
+            http2_connections: self.multiplexed.lock().len(),
+            waiting: self.waiting.load(Ordering::Acquire),
+            limit: self.limit.load(Ordering::Acquire),
+            latency_ms: self.latency.lock().average.map(|average| average * 1000.0),
+        }
+    }
+}
+
+/// Shared state of the pool.
+struct PoolInner {
+    /// Pool settings
+    config: ConnectionPoolConfig,
+
+    /// HTTP/2 settings
+    http2: Http2Settings,
+
+    /// Opens new connections
+    connector: Connector,
+
+    /// Per-origin pools
+    hosts: DashMap<PoolKey, Arc<HostPool>>,
+
+    /// Idle HTTP/1.1 connections across all origins
+    idle_total: AtomicUsize,
+
+    /// Whether the maintenance task has been started
+    maintenance_started: AtomicBool,
+
+    /// Connections opened
+    created: AtomicU64,
+
+    /// Leases served by an existing connection
+    reused: AtomicU64,
+
+    /// Idle connections dropped by maintenance
+    reaped: AtomicU64,
+
+    /// Callers that gave up waiting
+    exhausted: AtomicU64,
+}
+
+impl PoolInner {
+    /// Drops idle connections that expired before `now` or were closed.
+    fn reap(&self, now: Instant) {
+        let max_idle_time = Duration::from_secs(self.config.max_idle_time_sec);
+        for host in self.hosts.iter() {
+            let mut reaped = 0;
+
+            let mut idle = host.idle.lock();
+            let (kept, expired): (VecDeque<_>, VecDeque<_>) = idle.drain(..).partition(|connection| {
+                !connection.sender.is_closed() && now.saturating_duration_since(connection.idle_since) < max_idle_time
+            });
+            *idle = kept;
+            drop(idle);
+            self.idle_total.fetch_sub(expired.len(), Ordering::AcqRel);
+            reaped += expired.len();
+            for connection in expired {
+                host.release_permit(connection.slot);
+            }
+
+            let mut multiplexed = host.multiplexed.lock();
+            reaped += host.retain_http2(&mut multiplexed, |connection| {
+                let expired = connection.is_idle()
+                    && now.saturating_duration_since(*connection.last_active.lock()) >= max_idle_time;
+                !connection.sender.is_closed() && !expired
+            });
+            drop(multiplexed);
+
+            if reaped > 0 {
+                self.reaped.fetch_add(reaped as u64, Ordering::Relaxed);
+                debug!(origin = %host.key, reaped, "Reaped idle connections");
+            }
+        }
+
+        // Forget origins nobody holds a reference to and that have nothing pooled
+        self.hosts.retain(|_, host| {
+            Arc::strong_count(host) > 1 || !host.idle.lock().is_empty() || !host.multiplexed.lock().is_empty()
+        });
+    }
+}
+
+/// Adaptive pool of outbound HTTP/1.1 and HTTP/2 connections.
+#[derive(Clone)]
+pub struct MolokaiConnectionPool {
+    inner: Arc<PoolInner>,
+}
+
+impl MolokaiConnectionPool {
+    /// Creates a pool that opens connections with `connector`.
+    pub fn new(config: &HttpConfig, connector: Connector) -> Self {
+        let client = &config.client;
+        Self {
+            inner: Arc::new(PoolInner {
+                config: config.connection_pool.clone(),
+                http2: Http2Settings {
+                    enabled: client.http2_enabled,
+                    max_concurrent_streams: client.http2_max_concurrent_streams.max(1) as usize,
+                    max_idle_streams: client.http2_max_idle_streams as usize,
+                    prior_knowledge_hosts: client
+                        .http2_prior_knowledge_hosts
+                        .iter()
+                        .map(|host| host.to_ascii_lowercase())
+                        .collect(),
+                },
+                connector,
+                hosts: DashMap::new(),
+                idle_total: AtomicUsize::new(0),
+                maintenance_started: AtomicBool::new(false),
+                created: AtomicU64::new(0),
+                reused: AtomicU64::new(0),
+                reaped: AtomicU64::new(0),
+                exhausted: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Leases a connection to the origin of `url`.
+    ///
+    /// A stream on an open HTTP/2 connection or an idle HTTP/1.1 connection is
+    /// used when one is available; otherwise a new connection is opened. Fails
+    /// with [`HttpError::ConnectionPoolExhausted`] if nothing becomes available
+    /// within `connect_timeout_ms`.
+    pub async fn acquire(&self, url: &Url) -> Result<ConnectionLease, HttpError> {
+        self.start_maintenance();
+
+        let key = PoolKey::from_url(url)?;
+        let host = self
+            .inner
+            .hosts
+            .entry(key.clone())
+            .or_insert_with(|| Arc::new(HostPool::new(key, self.inner.config.max_connections_per_host)))
+            .clone();
+
+        host.waiting.fetch_add(1, Ordering::AcqRel);
+        let slot = self.wait_for_slot(&host).await;
+        host.waiting.fetch_sub(1, Ordering::AcqRel);
+
+        let slot = match slot {
+            Ok(Slot::Stream(connection, stream)) => {
+                self.inner.reused.fetch_add(1, Ordering::Relaxed);
+                return Ok(self.lease(host, Leased::Http2(connection), stream, true));
+            }
+            Ok(Slot::Idle(connection)) => {
+                let IdleConnection { mut sender, slot, .. } = connection;
+                if sender.ready().await.is_ok() {
+                    self.inner.reused.fetch_add(1, Ordering::Relaxed);
+                    return Ok(self.lease(host, Leased::Http1(sender), slot, true));
+                }
+                // Open a new connection in place of the one the peer closed
+                self.inner.reaped.fetch_add(1, Ordering::Relaxed);
+                slot
+            }
+            Ok(Slot::Connection(slot)) => slot,
+            Err(e) => {
+                self.inner.exhausted.fetch_add(1, Ordering::Relaxed);
+                return Err(e);
+            }
+        };
+
+        host.connecting.fetch_add(1, Ordering::AcqRel);
+        let connected = self.connect(&host, url, slot).await;
+        host.connecting.fetch_sub(1, Ordering::AcqRel);
+        // Let callers waiting on a connection that is being opened re-check
+        host.stream_available.notify_waiters();
+
+        let (leased, permit) = connected?;
+        self.inner.created.fetch_add(1, Ordering::Relaxed);
+        Ok(self.lease(host, leased, permit, false))
+    }
+
+    /// Waits until a stream, an idle connection or a connection slot of `host`
+    /// is available.
+    async fn wait_for_slot(&self, host: &HostPool) -> Result<Slot, HttpError> {
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(self.inner.config.connect_timeout_ms);
+        // Kept across wake-ups so the caller keeps its place in the queue
+        let acquire = host.permits.clone().acquire_owned();
+        tokio::pin!(acquire);
+
+        loop {
+            // Register for wake-ups before checking, so none are missed
+            let notified = host.stream_available.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            if let Some((connection, stream)) = host.try_stream() {
+                return Ok(Slot::Stream(connection, stream));
+            }
+            if let Some(connection) = self.take_idle(host) {
+                return Ok(Slot::Idle(connection));
+            }
+
+            // Once an origin speaks HTTP/2, wait for a connection being opened
+            // instead of opening another one alongside it
+            let opening_http2 =
+                host.speaks_http2.load(Ordering::Acquire) && host.connecting.load(Ordering::Acquire) > 0;
+
+            tokio::select! {
+                permit = &mut acquire, if !opening_http2 => {
+                    let permit = permit.map_err(|_| HttpError::ConnectionPoolExhausted)?;
+                    return Ok(Slot::Connection(permit));
+                }
+                _ = &mut notified => {}
+                _ = tokio::time::sleep_until(deadline) => return Err(HttpError::ConnectionPoolExhausted),
+            }
+        }
+    }
+
+    /// Opens a connection to `url` using the connection slot `slot`.
+    async fn connect(
+        &self,
+        host: &HostPool,
+        url: &Url,
+        slot: OwnedSemaphorePermit,
+    ) -> Result<(Leased, OwnedSemaphorePermit), HttpError> {
+        let stream = self.inner.connector.connect(url).await?;
+        if !self.use_http2(&host.key, &stream) {
+            let (sender, connection) = http1::handshake(TokioIo::new(stream))
+                .await
+                .map_err(|e| HttpError::ConnectionCreationError(e.to_string()))?;
+            tokio::spawn(async move {
+                if let Err(e) = connection.await {
+                    debug!(error = %e, "Outbound connection failed");
+                }
+            });
+            return Ok((Leased::Http1(sender), slot));
+        }
+
+        let settings = &self.inner.http2;
+        let (sender, connection) = http2::Builder::new(TokioExecutor::new())
+            .max_concurrent_reset_streams(settings.max_idle_streams)
+            .handshake(TokioIo::new(stream))
+            .await
+            .map_err(|e| HttpError::ConnectionCreationError(format!("HTTP/2 handshake failed: {e}")))?;
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                debug!(error = %e, "Outbound HTTP/2 connection failed");
+            }
+        });
+        debug!(origin = %host.key, "Opened HTTP/2 connection");
+
+        let streams = Arc::new(Semaphore::new(settings.max_concurrent_streams));
+        let stream = streams
+            .clone()
+            .try_acquire_owned()
+            .map_err(|_| HttpError::ConnectionCreationError("HTTP/2 connection has no streams".to_string()))?;
+        let connection = Arc::new(Http2Connection {
+            sender,
+            streams,
+            max_streams: settings.max_concurrent_streams,
+            last_active: Mutex::new(Instant::now()),
+            slot: Mutex::new(Some(slot)),
+        });
+        host.multiplexed.lock().push(connection.clone());
+        host.speaks_http2.store(true, Ordering::Release);
+
+        Ok((Leased::Http2(connection), stream))
+    }
+
+    /// Returns whether a new connection should speak HTTP/2.
+    fn use_http2(&self, key: &PoolKey, stream: &MaybeTlsStream) -> bool {
+        let settings = &self.inner.http2;
+        match stream {
+            MaybeTlsStream::Tls(_) => stream.negotiated_http2(),
+            MaybeTlsStream::Plain(_) => settings.enabled && settings.prior_knowledge_hosts.contains(&key.host),
+        }
+    }
+
+    /// Takes the most recently used idle connection of `host`.
+    fn take_idle(&self, host: &HostPool) -> Option<IdleConnection> {
+        let connection = host.idle.lock().pop_back()?;
+        self.inner.idle_total.fetch_sub(1, Ordering::AcqRel);
+        Some(connection)
+    }
+
+    fn lease(&self, host: Arc<HostPool>, leased: Leased, permit: OwnedSemaphorePermit, reused: bool) -> ConnectionLease {
+        host.active.fetch_add(1, Ordering::AcqRel);
+        ConnectionLease {
+            connection: Some(leased),
+            reused,
+            host,
+            pool: Arc::downgrade(&self.inner),
+            permit: Some(permit),
+        }
+    }
+
+    /// Returns a snapshot of the pool statistics.
+    pub fn stats(&self) -> PoolStats {
+        let inner = &self.inner;
+        let hosts: Vec<HostStats> = inner.hosts.iter().map(|host| host.stats()).collect();
+        PoolStats {
+            active: hosts.iter().map(|host| host.active).sum(),
+            idle: hosts.iter().map(|host| host.idle).sum(),
+            http2_connections: hosts.iter().map(|host| host.http2_connections).sum(),
+            waiting: hosts.iter().map(|host| host.waiting).sum(),
+            created: inner.created.load(Ordering::Relaxed),
+            reused: inner.reused.load(Ordering::Relaxed),
+            reaped: inner.reaped.load(Ordering::Relaxed),
+            exhausted: inner.exhausted.load(Ordering::Relaxed),
+            hosts,
+        }
+    }
+
+    /// Starts the background task that reaps idle connections, once.
+    fn start_maintenance(&self) {
+        if self.inner.maintenance_started.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let interval = Duration::from_secs(self.inner.config.health_check_interval_sec.max(1));
+        let pool = Arc::downgrade(&self.inner);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                // Stop once the pool has been dropped
+                let Some(pool) = pool.upgrade() else { break };
+                pool.reap(Instant::now());
+            }
+        });
+    }
+}
+
+impl fmt::Debug for MolokaiConnectionPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MolokaiConnectionPool")
+            .field("config", &self.inner.config)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+/// What a caller waiting on an origin was granted.
+enum Slot {
+    /// A stream on an open HTTP/2 connection
+    Stream(Arc<Http2Connection>, OwnedSemaphorePermit),
+
+    /// An idle HTTP/1.1 connection, with the slot it holds
+    Idle(IdleConnection),
+
+    /// Room to open a new connection
+    Connection(OwnedSemaphorePermit),
+}
+
+/// The connection behind a lease.
+enum Leased {
+    /// Exclusive HTTP/1.1 connection
+    Http1(Http1Sender),
+
+    /// Stream on a shared HTTP/2 connection
+    Http2(Arc<Http2Connection>),
+}
+
+/// Use of a pooled connection.
+///
+/// A lease is either an exclusive HTTP/1.1 connection or one stream on a
+/// shared HTTP/2 connection. Call [`release`](Self::release) once the
+/// response body has been read in full so the connection can be reused. An
+/// HTTP/1.1 lease that is dropped instead closes its connection.
+pub struct ConnectionLease {
+    /// Connection in use, taken on release
+    connection: Option<Leased>,
+
+    /// Whether an existing connection was reused
+    reused: bool,
+
+    /// Origin pool the connection belongs to
+    host: Arc<HostPool>,
+
+    /// Pool to return the connection to
+    pool: Weak<PoolInner>,
+
+    /// Connection slot (HTTP/1.1) or stream (HTTP/2) held by the lease
+    permit: Option<OwnedSemaphorePermit>,
+}
+
+impl ConnectionLease {
+    /// Sends a request on the leased connection.
+    ///
+    /// HTTP/2 requests must carry an absolute URI.
+    pub async fn send_request(&mut self, request: Request<Full<Bytes>>) -> hyper::Result<Response<Incoming>> {
+        match self.connection.as_mut().expect("connection lease used after release") {
+            Leased::Http1(sender) => sender.send_request(request).await,
+            Leased::Http2(connection) => connection.sender.clone().send_request(request).await,
+        }
+    }
+
+    /// Returns whether the lease is a stream on an HTTP/2 connection.
+    pub fn is_http2(&self) -> bool {
+        matches!(self.connection, Some(Leased::Http2(_)))
+    }
+
+    /// Returns whether an existing connection was reused.
+    pub fn is_reused(&self) -> bool {
+        self.reused
+    }
+
+    /// Returns the connection to the pool after a completed exchange.
+    ///
+    /// `latency` is the time the exchange took to produce a response, which
+    /// feeds the adaptive limit.
+    pub fn release(mut self, latency: Duration) {
+        self.host.record_latency(latency);
+
+        // HTTP/2 streams are returned when the lease drops
+        if self.is_http2() {
+            return;
+        }
+        let (Some(Leased::Http1(sender)), Some(pool)) = (self.connection.take(), self.pool.upgrade()) else {
+            return;
+        };
+        if sender.is_closed() {
+            return;
+        }
+
+        let max_idle = pool.config.max_idle_connections;
+        let admitted = pool
+            .idle_total
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |idle| (idle < max_idle).then_some(idle + 1))
+            .is_ok();
+        if !admitted {
+            return;
+        }
+
+        // The idle connection keeps the lease's slot
+        let Some(slot) = self.permit.take() else { return };
+        self.host.idle.lock().push_back(IdleConnection {
+            sender,
+            idle_since: Instant::now(),
+            slot,
+        });
+        self.host.stream_available.notify_waiters();
+    }
+}
+
+impl Drop for ConnectionLease {
+    fn drop(&mut self) {
+        self.host.active.fetch_sub(1, Ordering::AcqRel);
+        let Some(permit) = self.permit.take() else { return };
+
+        if let Some(Leased::Http2(connection)) = &self.connection {
+            *connection.last_active.lock() = Instant::now();
+            drop(permit);
+            self.host.stream_available.notify_waiters();
+        } else {
+            self.host.release_permit(permit);
+        }
+    }
+}
+
+impl fmt::Debug for ConnectionLease {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectionLease")
+            .field("origin", &self.host.key)
+            .field("http2", &self.is_http2())
+            .field("reused", &self.reused)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::http_server::{loopback_security, spawn_http_server};
+    use http_body_util::BodyExt;
+
+    fn pool(configure: impl FnOnce(&mut HttpConfig)) -> MolokaiConnectionPool {
+        let mut config = HttpConfig::default();
+        configure(&mut config);
+        let connector = Connector::new(&config, &loopback_security()).unwrap();
+        MolokaiConnectionPool::new(&config, connector)
+    }
+
+    async fn server() -> Url {
+        let addr = spawn_http_server(|_request: Request<Incoming>| async move {
+            Response::new(Full::new(Bytes::from_static(b"ok")))
+        })
+        .await;
+        Url::parse(&format!("http://{addr}/")).unwrap()
+    }
+
+    async fn exchange(lease: &mut ConnectionLease, url: &Url) {
+        let mut builder = Request::builder();
+        builder = if lease.is_http2() {
+            builder.uri(url.as_str())
+        } else {
+            builder.uri(url.path()).header("host", url.authority())
+        };
+        let response = lease.send_request(builder.body(Full::default()).unwrap()).await.unwrap();
+        response.into_body().collect().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_released_connections_are_reused() {
+        let url = server().await;
+        let pool = pool(|_| {});
+
+        for _ in 0..3 {
+            let mut lease = pool.acquire(&url).await.unwrap();
+            exchange(&mut lease, &url).await;
+            lease.release(Duration::from_millis(1));
+        }
+
+        let stats = pool.stats();
+        assert_eq!(stats.created, 1);
+        assert_eq!(stats.reused, 2);
+        assert_eq!(stats.idle, 1);
+        assert_eq!(stats.active, 0);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_leases_close_their_connection() {
+        let url = server().await;
+        let pool = pool(|_| {});
+
+        let mut lease = pool.acquire(&url).await.unwrap();
+        exchange(&mut lease, &url).await;
+        drop(lease);
+
+        assert_eq!(pool.stats().idle, 0);
+        assert!(!pool.acquire(&url).await.unwrap().is_reused());
+    }
+
+    #[tokio::test]
+    async fn test_waiters_time_out_when_host_is_saturated() {
+        let url = server().await;
+        let pool = pool(|config| {
+            config.connection_pool.max_connections_per_host = 1;
+            config.connection_pool.connect_timeout_ms = 50;
+        });
+
+        let _held = pool.acquire(&url).await.unwrap();
+        let error = pool.acquire(&url).await.unwrap_err();
+
+        assert!(matches!(error, HttpError::ConnectionPoolExhausted));
+        assert_eq!(pool.stats().exhausted, 1);
+    }
+
+    #[tokio::test]
+    async fn test_waiters_are_served_in_order() {
+        let url = server().await;
+        let pool = pool(|config| config.connection_pool.max_connections_per_host = 1);
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let held = pool.acquire(&url).await.unwrap();
+        let mut waiters = Vec::new();
+        for id in 0..3 {
+            let (pool, url, order) = (pool.clone(), url.clone(), order.clone());
+            waiters.push(tokio::spawn(async move {
+                let lease = pool.acquire(&url).await.unwrap();
+                order.lock().push(id);
+                drop(lease);
+            }));
+            // Let the waiter queue up before starting the next one
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(pool.stats().waiting, 3);
+
+        drop(held);
+        for waiter in waiters {
+            waiter.await.unwrap();
+        }
+        assert_eq!(*order.lock(), vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_reaping_expired_idle_connections() {
+        let url = server().await;
+        let pool = pool(|config| config.connection_pool.max_idle_time_sec = 30);
+
+        let mut lease = pool.acquire(&url).await.unwrap();
+        exchange(&mut lease, &url).await;
+        lease.release(Duration::from_millis(1));
+
+        pool.inner.reap(Instant::now());
+        assert_eq!(pool.stats().idle, 1);
+
+        pool.inner.reap(Instant::now() + Duration::from_secs(31));
+        let stats = pool.stats();
+        assert_eq!(stats.idle, 0);
+        assert_eq!(stats.reaped, 1);
+        assert!(stats.hosts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_idle_connections_keep_their_slot() {
+        let url = server().await;
+        let pool = pool(|config| {
+            config.connection_pool.max_connections_per_host = 1;
+            config.connection_pool.max_idle_time_sec = 30;
+        });
+
+        let mut lease = pool.acquire(&url).await.unwrap();
+        exchange(&mut lease, &url).await;
+        let host = lease.host.clone();
+
+        // A caller waiting on the saturated origin is handed the idle connection
+        let waiter = tokio::spawn({
+            let (pool, url) = (pool.clone(), url.clone());
+            async move {
+                let mut lease = pool.acquire(&url).await.unwrap();
+                assert!(lease.is_reused());
+                exchange(&mut lease, &url).await;
+                lease.release(Duration::from_millis(1));
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        lease.release(Duration::from_millis(1));
+        waiter.await.unwrap();
+
+        assert_eq!(pool.stats().idle, 1);
+        assert_eq!(host.permits.available_permits(), 0);
+
+        pool.inner.reap(Instant::now() + Duration::from_secs(31));
+        assert_eq!(pool.stats().idle, 0);
+        assert_eq!(host.permits.available_permits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_idle_connections_are_capped() {
+        let url = server().await;
+        let pool = pool(|config| config.connection_pool.max_idle_connections = 1);
+
+        let mut first = pool.acquire(&url).await.unwrap();
+        let mut second = pool.acquire(&url).await.unwrap();
+        exchange(&mut first, &url).await;
+        exchange(&mut second, &url).await;
+        first.release(Duration::from_millis(1));
+        second.release(Duration::from_millis(1));
+
+        assert_eq!(pool.stats().idle, 1);
+    }
+
+    #[tokio::test]
+    async fn test_http2_streams_share_a_connection() {
+        let url = server().await;
+        let pool = pool(|config| {
+            config.connection_pool.max_connections_per_host = 1;
+            config.connection_pool.connect_timeout_ms = 100;
+            config.client.http2_prior_knowledge_hosts = vec!["127.0.0.1".to_string()];
+            config.client.http2_max_concurrent_streams = 2;
+        });
+
+        let mut first = pool.acquire(&url).await.unwrap();
+        assert!(first.is_http2());
+        let mut second = pool.acquire(&url).await.unwrap();
+        assert!(second.is_http2() && second.is_reused());
+        exchange(&mut first, &url).await;
+        exchange(&mut second, &url).await;
+
+        // Both streams are taken and no other connection may be opened
+        assert!(matches!(pool.acquire(&url).await, Err(HttpError::ConnectionPoolExhausted)));
+
+        // A finished stream frees capacity for a waiting caller
+        let waiter = tokio::spawn({
+            let (pool, url) = (pool.clone(), url.clone());
+            async move { pool.acquire(&url).await.map(|lease| lease.is_http2()) }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        first.release(Duration::from_millis(1));
+        assert!(waiter.await.unwrap().unwrap());
+
+        let stats = pool.stats();
+        assert_eq!(stats.created, 1);
+        assert_eq!(stats.http2_connections, 1);
+    }
+
+    #[tokio::test]
+    async fn test_reaping_idle_http2_connections() {
+        let url = server().await;
+        let pool = pool(|config| {
+            config.connection_pool.max_idle_time_sec = 30;
+            config.client.http2_prior_knowledge_hosts = vec!["127.0.0.1".to_string()];
+        });
+
+        let mut lease = pool.acquire(&url).await.unwrap();
+        exchange(&mut lease, &url).await;
+        pool.inner.reap(Instant::now() + Duration::from_secs(31));
+        assert_eq!(pool.stats().http2_connections, 1, "busy connections are kept");
+
+        lease.release(Duration::from_millis(1));
+        pool.inner.reap(Instant::now() + Duration::from_secs(31));
+        assert_eq!(pool.stats().http2_connections, 0);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_http2_connections_retire_shrunk_slots() {
+        let url = server().await;
+        let pool = pool(|config| {
+            config.connection_pool.max_connections_per_host = 1;
+            config.connection_pool.max_idle_time_sec = 30;
+            config.client.http2_prior_knowledge_hosts = vec!["127.0.0.1".to_string()];
+        });
+
+        let mut lease = pool.acquire(&url).await.unwrap();
+        exchange(&mut lease, &url).await;
+        let host = lease.host.clone();
+
+        // The limit shrank while the connection held the only slot
+        host.shrink_debt.store(1, Ordering::Release);
+
+        lease.release(Duration::from_millis(1));
+        pool.inner.reap(Instant::now() + Duration::from_secs(31));
+        assert_eq!(pool.stats().http2_connections, 0);
+        assert_eq!(host.shrink_debt.load(Ordering::Acquire), 0);
+        assert_eq!(host.permits.available_permits(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_prior_knowledge_requires_http2_enabled() {
+        let url = server().await;
+        let pool = pool(|config| {
+            config.client.http2_enabled = false;
+            config.client.http2_prior_knowledge_hosts = vec!["127.0.0.1".to_string()];
+        });
+
+        assert!(!pool.acquire(&url).await.unwrap().is_http2());
+    }
+
+    #[test]
+    fn test_limit_adapts_to_latency() {
+        let host = HostPool::new(
+            PoolKey {
+                scheme: "http".to_string(),
+                host: "example.com".to_string(),
+                port: 80,
+            },
+            8,
+        );
+
+        for _ in 0..5 {
+            host.record_latency(Duration::from_millis(10));
+        }
+        assert_eq!(host.limit.load(Ordering::Acquire), 8);
+
+        // Latency degrades: back off
+        for _ in 0..20 {
+            host.record_latency(Duration::from_millis(200));
+        }
+        assert_eq!(host.limit.load(Ordering::Acquire), host.min_limit);
+        assert_eq!(host.permits.available_permits(), host.min_limit);
+
+        // Latency recovers while callers queue: grow back
+        host.waiting.store(1, Ordering::Release);
+        for _ in 0..100 {
+            host.record_latency(Duration::from_millis(10));
+        }
+        assert_eq!(host.limit.load(Ordering::Acquire), 8);
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Host resolution that is safe against DNS rebinding.
+//!
+//! Validating a URL only checks the name in it; the name may resolve to a
+//! public address when checked and to a loopback address when connected to.
+//! The [`Resolver`] therefore resolves a host once, checks every address
+//! against the address rules of the [`UrlValidator`], and hands the
+//! [`Connector`](super::connector::Connector) only the addresses that passed.
+//! The connection is made to those addresses without resolving again, so what
+//! was vetted is what is connected to. Every new connection, including those
+//! opened for redirect hops, goes through this check.
+
+use std::net::SocketAddr;
+
+use tracing::debug;
+use url::Host;
+
+use super::validator::UrlValidator;
+use crate::error::http::HttpError;
+
+/// Resolves hosts to vetted socket addresses.
+#[derive(Debug, Clone)]
+pub struct Resolver {
+    /// Rules the resolved addresses must pass
+    validator: UrlValidator,
+}
+
+impl Resolver {
+    /// Creates a resolver that vets addresses with `validator`.
+    pub fn new(validator: UrlValidator) -> Self {
+        Self { validator }
+    }
+
+    /// Resolves `host` and returns the addresses that may be connected to.
+    ///
+    /// Fails with [`HttpError::DnsResolutionFailed`] if the host does not
+    /// resolve, and with [`HttpError::InvalidUrl`] if none of its addresses
+    /// is allowed.
+    pub async fn resolve(&self, host: &Host<&str>, port: u16) -> Result<Vec<SocketAddr>, HttpError> {
+        let addrs: Vec<SocketAddr> = match host {
+            Host::Domain(domain) => tokio::net::lookup_host((*domain, port))
+                .await
+                .map_err(|e| HttpError::DnsResolutionFailed(format!("{domain}: {e}")))?
+                .collect(),
+            Host::Ipv4(ip) => vec![SocketAddr::from((*ip, port))],
+            Host::Ipv6(ip) => vec![SocketAddr::from((*ip, port))],
+        };
+
+        if addrs.is_empty() {
+            return Err(HttpError::DnsResolutionFailed(format!("{host}: no addresses found")));
+        }
+        self.vet(host, addrs)
+    }
+
+    /// Keeps the addresses that pass the address rules.
+    fn vet(&self, host: &Host<&str>, addrs: Vec<SocketAddr>) -> Result<Vec<SocketAddr>, HttpError> {
+        let mut vetted = Vec::with_capacity(addrs.len());
+        let mut rejection = None;
+        for addr in addrs {
+            match self.validator.check_ip(addr.ip()) {
+                Ok(()) => vetted.push(addr),
+                Err(e) => {
+                    debug!(%host, %addr, error = %e, "Discarding resolved address");
+                    rejection.get_or_insert(e);
+                }
+            }
+        }
+
+        match (vetted.is_empty(), rejection) {
+            (true, Some(HttpError::InvalidUrl(reason))) => {
+                Err(HttpError::InvalidUrl(format!("{reason} (resolved from {host})")))
+            }
+            (true, Some(e)) => Err(e),
+            _ => Ok(vetted),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::security::UrlValidationConfig;
+
+    fn resolver(configure: impl FnOnce(&mut UrlValidationConfig)) -> Resolver {
+        let mut config = UrlValidationConfig::default();
+        configure(&mut config);
+        Resolver::new(UrlValidator::new(&config))
+    }
+
+    #[tokio::test]
+    async fn test_names_resolving_to_blocked_addresses_are_rejected() {
+        let error = resolver(|_| {})
+            .resolve(&Host::Domain("localhost"), 80)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&error, HttpError::InvalidUrl(message) if message.starts_with("loopback_address")),
+            "{error}"
+        );
+
+        let addrs = resolver(|config| config.block_loopback = false)
+            .resolve(&Host::Domain("localhost"), 80)
+            .await
+            .unwrap();
+        assert!(addrs.iter().all(|addr| addr.ip().is_loopback() && addr.port() == 80));
+    }
+
+    #[tokio::test]
+    async fn test_unresolvable_names() {
+        let error = resolver(|_| {})
+            .resolve(&Host::Domain("does-not-exist.invalid"), 80)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, HttpError::DnsResolutionFailed(_)), "{error}");
+    }
+
+    #[test]
+    fn test_only_vetted_addresses_are_kept() {
+        let addrs: Vec<SocketAddr> = ["127.0.0.1:443", "93.184.216.34:443", "[fd00::1]:443", "[2606:2800:220:1::]:443"]
+            .iter()
+            .map(|addr| addr.parse().unwrap())
+            .collect();
+
+        let vetted = resolver(|_| {}).vet(&Host::Domain("rebind.example"), addrs).unwrap();
+        assert_eq!(
+            vetted,
+            vec![
+                "93.184.216.34:443".parse::<SocketAddr>().unwrap(),
+                "[2606:2800:220:1::]:443".parse().unwrap()
+            ]
+        );
+    }
+}
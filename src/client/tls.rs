@@ -0,0 +1,416 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! TLS settings of outbound connections.
+//!
+//! [`client_config`] turns a [`TlsConfig`] into the rustls configuration used
+//! by the [`Connector`](super::connector::Connector):
+//!
+//! - Server certificates are verified against the bundled web PKI roots plus
+//!   the PEM certificates in `ca_file`, so hosts behind a private CA can be
+//!   reached without losing the public ones.
+//! - `client_cert_file` and `client_key_file` present a client certificate
+//!   for mutual TLS.
+//! - `min_tls_version` and `allowed_ciphers` restrict what is negotiated.
+//!   TLS 1.2 is the oldest version implemented, so a minimum of 1.0 or 1.1
+//!   behaves like 1.2.
+//!
+//! Certificate verification failures carry a description of the chain the
+//! server presented, which [`handshake_error`] includes in the resulting
+//! [`HttpError::TlsError`].
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider, WebPkiSupportedAlgorithms};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::{
+    CertificateError, ClientConfig, DigitallySignedStruct, OtherError, RootCertStore, SignatureScheme,
+    SupportedProtocolVersion,
+};
+use webpki::EndEntityCert;
+
+use crate::config::security::TlsConfig;
+use crate::error::http::HttpError;
+
+/// Builds the TLS client configuration, offering `alpn_protocols` during the
+/// handshake.
+pub fn client_config(config: &TlsConfig, alpn_protocols: Vec<Vec<u8>>) -> Result<ClientConfig, HttpError> {
+    let provider = Arc::new(crypto_provider(&config.allowed_ciphers)?);
+
+    let builder = ClientConfig::builder_with_provider(provider.clone())
+        .with_protocol_versions(protocol_versions(&config.min_tls_version))
+        .map_err(|e| {
+            HttpError::TlsError(format!(
+                "no allowed cipher suite supports TLS {} or later: {e}",
+                config.min_tls_version
+            ))
+        })?;
+
+    let verifier: Arc<dyn ServerCertVerifier> = if config.verify_certificates {
+        let mut roots = RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        if let Some(ca_file) = &config.ca_file {
+            for certificate in load_certificates(ca_file)? {
+                roots
+                    .add(certificate)
+                    .map_err(|e| HttpError::TlsError(format!("invalid CA certificate in {}: {e}", ca_file.display())))?;
+            }
+        }
+        let webpki = WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+            .build()
+            .map_err(|e| HttpError::TlsError(e.to_string()))?;
+        Arc::new(ChainReportingVerifier { inner: webpki })
+    } else {
+        Arc::new(UnverifiedCertificates {
+            algorithms: provider.signature_verification_algorithms,
+        })
+    };
+    let builder = builder.dangerous().with_custom_certificate_verifier(verifier);
+
+    let mut tls = match (&config.client_cert_file, &config.client_key_file) {
+        (Some(cert_file), Some(key_file)) => {
+            let chain = load_certificates(cert_file)?;
+            let key = PrivateKeyDer::from_pem_file(key_file)
+                .map_err(|e| HttpError::TlsError(format!("cannot load client key {}: {e}", key_file.display())))?;
+            builder
+                .with_client_auth_cert(chain, key)
+                .map_err(|e| HttpError::TlsError(format!("invalid client certificate: {e}")))?
+        }
+        _ => builder.with_no_client_auth(),
+    };
+    tls.alpn_protocols = alpn_protocols;
+
+    Ok(tls)
+}
+
+/// Maps a failed TLS handshake with `host` to an [`HttpError::TlsError`].
+pub fn handshake_error(host: impl fmt::Display, error: io::Error) -> HttpError {
+    let chain = error
+        .get_ref()
+        .and_then(|inner| inner.downcast_ref::<rustls::Error>())
+        .and_then(|error| match error {
+            rustls::Error::InvalidCertificate(CertificateError::Other(OtherError(inner))) => {
+                inner.downcast_ref::<ChainError>()
+            }
+            _ => None,
+        });
+
+    match chain {
+        Some(chain) => HttpError::TlsError(format!("handshake with {host} failed: {chain}")),
+        None => HttpError::TlsError(format!("handshake with {host} failed: {error}")),
+    }
+}
+
+/// Returns the ring provider restricted to the allowed cipher suites.
+///
+/// Suites are named as rustls or IANA name them, e.g.
+/// `TLS13_AES_128_GCM_SHA256`, `TLS_AES_128_GCM_SHA256` or
+/// `TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256`, in any case.
+fn crypto_provider(allowed_ciphers: &[String]) -> Result<CryptoProvider, HttpError> {
+    let mut provider = rustls::crypto::ring::default_provider();
+    if allowed_ciphers.is_empty() {
+        return Ok(provider);
+    }
+
+    let mut cipher_suites = Vec::with_capacity(allowed_ciphers.len());
+    for name in allowed_ciphers {
+        let name = name.trim().to_ascii_uppercase();
+        let suite = provider
+            .cipher_suites
+            .iter()
+            .find(|suite| {
+                let rustls_name = suite.suite().as_str().unwrap_or_default();
+                rustls_name == name || rustls_name.strip_prefix("TLS13_") == name.strip_prefix("TLS_")
+            })
+            .ok_or_else(|| HttpError::TlsError(format!("unsupported cipher suite: {name}")))?;
+        if !cipher_suites.contains(suite) {
+            cipher_suites.push(*suite);
+        }
+    }
+    provider.cipher_suites = cipher_suites;
+
+    Ok(provider)
+}
+
+/// Protocol versions allowed with a minimum of TLS 1.3.
+static TLS13_ONLY: &[&SupportedProtocolVersion] = &[&rustls::version::TLS13];
+
+/// Returns the protocol versions at or above `min_tls_version`.
+fn protocol_versions(min_tls_version: &str) -> &'static [&'static SupportedProtocolVersion] {
+    match min_tls_version {
+        "1.3" => TLS13_ONLY,
+        _ => rustls::ALL_VERSIONS,
+    }
+}
+
+/// Loads every certificate of a PEM file.
+fn load_certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>, HttpError> {
+    let certificates = CertificateDer::pem_file_iter(path)
+        .and_then(|certificates| certificates.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| HttpError::TlsError(format!("cannot load certificates from {}: {e}", path.display())))?;
+    if certificates.is_empty() {
+        return Err(HttpError::TlsError(format!("no certificates found in {}", path.display())));
+    }
+    Ok(certificates)
+}
+
+/// A certificate verification failure and the chain that caused it.
+#[derive(Debug)]
+struct ChainError {
+    /// Reason verification failed
+    cause: CertificateError,
+
+    /// Description of each presented certificate, end entity first
+    chain: Vec<String>,
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid peer certificate: {}; presented chain: ", self.cause)?;
+        for (depth, certificate) in self.chain.iter().enumerate() {
+            if depth > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "[{depth}] {certificate}")?;
+        }
+        Ok(())
+    }
+}
+
+impl StdError for ChainError {}
+
+/// Web PKI verifier that attaches the presented chain to its failures.
+#[derive(Debug)]
+struct ChainReportingVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+}
+
+impl ServerCertVerifier for ChainReportingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        self.inner
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+            .map_err(|error| match error {
+                rustls::Error::InvalidCertificate(cause) => {
+                    let chain = std::iter::once(end_entity)
+                        .chain(intermediates)
+                        .map(describe_certificate)
+                        .collect();
+                    let error = ChainError { cause, chain };
+                    rustls::Error::InvalidCertificate(CertificateError::Other(OtherError(Arc::new(error))))
+                }
+                error => error,
+            })
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Verifier used when `verify_certificates` is off.
+///
+/// Any certificate is accepted, but the handshake signatures are still
+/// checked so the peer has to hold the key of the certificate it presents.
+#[derive(Debug)]
+struct UnverifiedCertificates {
+    algorithms: WebPkiSupportedAlgorithms,
+}
+
+impl ServerCertVerifier for UnverifiedCertificates {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.algorithms.supported_schemes()
+    }
+}
+
+/// Describes a certificate by its subject, issuer and DNS names.
+fn describe_certificate(certificate: &CertificateDer<'_>) -> String {
+    let Ok(parsed) = EndEntityCert::try_from(certificate) else {
+        return "unparsable certificate".to_string();
+    };
+
+    let mut description = format!(
+        "subject \"{}\" issued by \"{}\"",
+        format_name(parsed.subject()),
+        format_name(parsed.issuer())
+    );
+    let names: Vec<&str> = parsed.valid_dns_names().collect();
+    if !names.is_empty() {
+        description.push_str(&format!(" for {}", names.join(", ")));
+    }
+    description
+}
+
+/// Formats the contents of a DER `Name` as `CN=..., O=...`.
+///
+/// Only the common attributes are shown; others are skipped.
+fn format_name(mut name: &[u8]) -> String {
+    let mut attributes = Vec::new();
+    while let Some((_, mut set, rest)) = der_element(name) {
+        name = rest;
+        while let Some((_, attribute, rest)) = der_element(set) {
+            set = rest;
+            let Some((_, oid, value)) = der_element(attribute) else { continue };
+            let Some((_, value, _)) = der_element(value) else { continue };
+            let label = match oid {
+                [0x55, 0x04, 0x03] => "CN",
+                [0x55, 0x04, 0x06] => "C",
+                [0x55, 0x04, 0x0a] => "O",
+                [0x55, 0x04, 0x0b] => "OU",
+                _ => continue,
+            };
+            attributes.push(format!("{label}={}", String::from_utf8_lossy(value)));
+        }
+    }
+    attributes.join(", ")
+}
+
+/// Splits one DER element off `input`, returning its tag, contents and the
+/// remaining input.
+fn der_element(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&first, rest) = rest.split_first()?;
+    let (length, rest) = if first < 0x80 {
+        (first as usize, rest)
+    } else {
+        let octets = (first & 0x7f) as usize;
+        if octets == 0 || octets > 4 || rest.len() < octets {
+            return None;
+        }
+        let (length, rest) = rest.split_at(octets);
+        (length.iter().fold(0, |length, &octet| (length << 8) | octet as usize), rest)
+    };
+    if rest.len() < length {
+        return None;
+    }
+    let (contents, rest) = rest.split_at(length);
+    Some((tag, contents, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::tls_server::fixture;
+
+    #[test]
+    fn test_cipher_allowlist() {
+        let provider = crypto_provider(&[
+            "tls_aes_128_gcm_sha256".to_string(),
+            "TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384".to_string(),
+        ])
+        .unwrap();
+        let names: Vec<_> = provider.cipher_suites.iter().map(|suite| suite.suite()).collect();
+        assert_eq!(
+            names,
+            vec![
+                rustls::CipherSuite::TLS13_AES_128_GCM_SHA256,
+                rustls::CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384
+            ]
+        );
+
+        let error = crypto_provider(&["TLS_RSA_WITH_RC4_128_MD5".to_string()]).unwrap_err();
+        assert!(error.to_string().contains("unsupported cipher suite"), "{error}");
+    }
+
+    #[test]
+    fn test_minimum_version_must_leave_a_cipher_suite() {
+        let config = TlsConfig {
+            min_tls_version: "1.3".to_string(),
+            allowed_ciphers: vec!["TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384".to_string()],
+            ..Default::default()
+        };
+        let error = client_config(&config, Vec::new()).unwrap_err();
+        assert!(matches!(error, HttpError::TlsError(_)), "{error}");
+    }
+
+    #[test]
+    fn test_missing_files_are_reported() {
+        let config = TlsConfig {
+            ca_file: Some("/nonexistent/ca.pem".into()),
+            ..Default::default()
+        };
+        let error = client_config(&config, Vec::new()).unwrap_err();
+        assert!(error.to_string().contains("/nonexistent/ca.pem"), "{error}");
+
+        let config = TlsConfig {
+            client_cert_file: Some(fixture("client.pem")),
+            client_key_file: Some(fixture("ca.pem")),
+            ..Default::default()
+        };
+        let error = client_config(&config, Vec::new()).unwrap_err();
+        assert!(error.to_string().contains("client key"), "{error}");
+    }
+
+    #[test]
+    fn test_certificates_are_described() {
+        let certificate = load_certificates(&fixture("server.pem")).unwrap().remove(0);
+        assert_eq!(
+            describe_certificate(&certificate),
+            "subject \"CN=localhost\" issued by \"CN=Mauka Test CA, O=Mauka MCP\" for localhost"
+        );
+    }
+}
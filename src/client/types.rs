@@ -0,0 +1,139 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Request and response types of the outbound HTTP client.
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use hyper::header::{HeaderMap, CONTENT_TYPE};
+use hyper::{Method, StatusCode, Version};
+use url::Url;
+
+use super::body::DecodedBody;
+
+/// An outbound HTTP request.
+///
+/// Options left unset fall back to the client's
+/// [`HttpClientConfig`](crate::config::http::HttpClientConfig).
+#[derive(Debug, Clone)]
+pub struct FetchRequest {
+    /// Target URL
+    pub url: Url,
+
+    /// Request method
+    pub method: Method,
+
+    /// Request headers
+    pub headers: HeaderMap,
+
+    /// Request body
+    pub body: Option<Bytes>,
+
+    /// Timeout for the whole fetch, including redirects
+    pub timeout: Option<Duration>,
+
+    /// Whether to follow redirects
+    pub follow_redirects: Option<bool>,
+
+    /// Maximum number of redirects to follow
+    pub max_redirects: Option<usize>,
+}
+
+impl FetchRequest {
+    /// Creates a request with the given method and URL.
+    pub fn new(method: Method, url: Url) -> Self {
+        Self {
+            url,
+            method,
+            headers: HeaderMap::new(),
+            body: None,
+            timeout: None,
+            follow_redirects: None,
+            max_redirects: None,
+        }
+    }
+
+    /// Creates a `GET` request for the given URL.
+    pub fn get(url: Url) -> Self {
+        Self::new(Method::GET, url)
+    }
+
+    /// Sets the request headers.
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Sets the request body.
+    pub fn with_body(mut self, body: impl Into<Bytes>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Sets the timeout for the whole fetch.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether redirects are followed, and how many at most.
+    pub fn with_redirects(mut self, follow: bool, max_redirects: Option<usize>) -> Self {
+        self.follow_redirects = Some(follow);
+        self.max_redirects = max_redirects;
+        self
+    }
+}
+
+/// Time spent in the phases of a fetch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FetchTiming {
+    /// Time spent resolving and connecting, summed over all redirect hops
+    pub connect: Duration,
+
+    /// Time from the start of the fetch until the final response head arrived
+    pub first_byte: Duration,
+
+    /// Time from the start of the fetch until the body was fully read
+    pub total: Duration,
+}
+
+/// A completed HTTP response.
+#[derive(Debug, Clone)]
+pub struct FetchResponse {
+    /// URL the response was served from, after redirects
+    pub url: Url,
+
+    /// Response status
+    pub status: StatusCode,
+
+    /// HTTP version of the response
+    pub version: Version,
+
+    /// Response headers
+    pub headers: HeaderMap,
+
+    /// Raw response body
+    pub body: Bytes,
+
+    /// Number of redirects that were followed
+    pub redirects: usize,
+
+    /// Phase timings
+    pub timing: FetchTiming,
+}
+
+impl FetchResponse {
+    /// Returns the value of the `Content-Type` header, if present and valid.
+    pub fn content_type(&self) -> Option<&str> {
+        self.headers.get(CONTENT_TYPE).and_then(|value| value.to_str().ok())
+    }
+
+    /// Decodes the body according to its content type and charset.
+    pub fn decoded_body(&self) -> DecodedBody {
+        DecodedBody::decode(&self.body, self.content_type())
+    }
+}
@@ -0,0 +1,435 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! URL validation for outbound fetches.
+//!
+//! The [`UrlValidator`] enforces [`UrlValidationConfig`] on every URL the
+//! [`HttpClient`](super::HttpClient) is about to request, redirect targets
+//! included. Hosts are normalized before they are matched, so that spelling
+//! variants of a blocked host are blocked too:
+//!
+//! - percent-encoded hosts are decoded,
+//! - internationalized domain names are converted to punycode,
+//! - letters are lowercased and trailing dots removed,
+//! - IPv4-mapped IPv6 addresses are treated as the IPv4 address they embed.
+//!
+//! `disallowed_host_patterns` are regular expressions searched for in the
+//! normalized host, ignoring case, so `.*\.evil\.com$` blocks every
+//! subdomain of `evil.com`.
+//!
+//! A rejected URL yields [`HttpError::InvalidUrl`] whose message starts with
+//! the machine-readable [`UrlRejection`] code, e.g.
+//! `private_address: 10.0.0.1 is a private address`.
+//!
+//! Only addresses written into the URL are checked here; addresses a host
+//! name resolves to are vetted when connecting.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use percent_encoding::percent_decode_str;
+use regex::Regex;
+use url::{Host, Url};
+
+use crate::config::security::{host_pattern, UrlValidationConfig};
+use crate::error::http::HttpError;
+
+/// Reason a URL was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UrlRejection {
+    /// The URL could not be parsed
+    Malformed,
+
+    /// The URL is longer than `max_url_length`
+    TooLong,
+
+    /// The scheme is not in `allowed_schemes`
+    SchemeNotAllowed,
+
+    /// The URL has no host
+    MissingHost,
+
+    /// The host cannot be normalized
+    InvalidHost,
+
+    /// The host is listed in `disallowed_hosts`
+    HostDisallowed,
+
+    /// The host matches one of `disallowed_host_patterns`
+    HostPatternDisallowed,
+
+    /// The host is a loopback or unspecified address, or a `localhost` name
+    LoopbackAddress,
+
+    /// The host is a private, unique local or shared address
+    PrivateAddress,
+
+    /// The host is a link-local address
+    LinkLocalAddress,
+}
+
+impl UrlRejection {
+    /// Returns the machine-readable code of the rejection.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Malformed => "malformed_url",
+            Self::TooLong => "url_too_long",
+            Self::SchemeNotAllowed => "scheme_not_allowed",
+            Self::MissingHost => "missing_host",
+            Self::InvalidHost => "invalid_host",
+            Self::HostDisallowed => "host_disallowed",
+            Self::HostPatternDisallowed => "host_pattern_disallowed",
+            Self::LoopbackAddress => "loopback_address",
+            Self::PrivateAddress => "private_address",
+            Self::LinkLocalAddress => "link_local_address",
+        }
+    }
+
+    /// Creates the [`HttpError::InvalidUrl`] reporting this rejection.
+    pub fn error(self, detail: impl fmt::Display) -> HttpError {
+        HttpError::InvalidUrl(format!("{}: {detail}", self.code()))
+    }
+}
+
+impl fmt::Display for UrlRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Validates outbound URLs against [`UrlValidationConfig`].
+#[derive(Debug, Clone)]
+pub struct UrlValidator {
+    /// Whether URLs are validated at all
+    enabled: bool,
+
+    /// Maximum URL length in characters
+    max_url_length: usize,
+
+    /// Allowed schemes, lowercase
+    allowed_schemes: HashSet<String>,
+
+    /// Normalized disallowed hosts
+    disallowed_hosts: HashSet<String>,
+
+    /// Disallowed host patterns, compiled
+    disallowed_host_patterns: Vec<Regex>,
+
+    /// Whether private and link-local addresses are blocked
+    block_private_ips: bool,
+
+    /// Whether loopback addresses are blocked
+    block_loopback: bool,
+}
+
+impl UrlValidator {
+    /// Creates a validator from the URL validation configuration.
+    ///
+    /// Nothing is rejected if `validate_on_request` is off.
+    pub fn new(config: &UrlValidationConfig) -> Self {
+        Self {
+            enabled: config.validate_on_request,
+            max_url_length: config.max_url_length,
+            allowed_schemes: config.allowed_schemes.iter().map(|scheme| scheme.to_ascii_lowercase()).collect(),
+            disallowed_hosts: config
+                .disallowed_hosts
+                .iter()
+                .map(|host| normalize_domain(host).unwrap_or_else(|_| host.to_ascii_lowercase()))
+                .collect(),
+            disallowed_host_patterns: config
+                .disallowed_host_patterns
+                .iter()
+                .filter_map(|pattern| match host_pattern(pattern) {
+                    Ok(regex) => Some(regex),
+                    Err(e) => {
+                        tracing::warn!(pattern, error = %e, "Ignoring invalid disallowed host pattern");
+                        None
+                    }
+                })
+                .collect(),
+            block_private_ips: config.block_private_ips,
+            block_loopback: config.block_loopback,
+        }
+    }
+
+    /// Parses and validates a URL.
+    pub fn parse(&self, input: &str) -> Result<Url, HttpError> {
+        if self.enabled && input.chars().count() > self.max_url_length {
+            return Err(UrlRejection::TooLong.error(format!("URL exceeds {} characters", self.max_url_length)));
+        }
+        let url = Url::parse(input).map_err(|e| UrlRejection::Malformed.error(format!("{input}: {e}")))?;
+        self.validate(&url)?;
+        Ok(url)
+    }
+
+    /// Validates a parsed URL.
+    pub fn validate(&self, url: &Url) -> Result<(), HttpError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        if url.as_str().chars().count() > self.max_url_length {
+            return Err(UrlRejection::TooLong.error(format!("URL exceeds {} characters", self.max_url_length)));
+        }
+        if !self.allowed_schemes.contains(url.scheme()) {
+            return Err(UrlRejection::SchemeNotAllowed.error(format!("{} is not an allowed scheme", url.scheme())));
+        }
+
+        match normalize_host(url)? {
+            NormalizedHost::Domain(domain) => self.check_domain(&domain),
+            NormalizedHost::Ip(ip) => self.check_ip(ip),
+        }
+    }
+
+    /// Checks a normalized domain against the host rules.
+    fn check_domain(&self, domain: &str) -> Result<(), HttpError> {
+        if self.disallowed_hosts.contains(domain) {
+            return Err(UrlRejection::HostDisallowed.error(format!("{domain} is not allowed")));
+        }
+        if let Some(pattern) = self
+            .disallowed_host_patterns
+            .iter()
+            .find(|pattern| pattern.is_match(domain))
+        {
+            return Err(UrlRejection::HostPatternDisallowed.error(format!("{domain} matches {pattern}")));
+        }
+        if self.block_loopback && (domain == "localhost" || domain.ends_with(".localhost")) {
+            return Err(UrlRejection::LoopbackAddress.error(format!("{domain} is a loopback host")));
+        }
+        Ok(())
+    }
+
+    /// Checks an address against the address rules.
+    ///
+    /// Host rules apply to addresses too, so `disallowed_hosts` may list
+    /// addresses as well as names.
+    pub fn check_ip(&self, ip: IpAddr) -> Result<(), HttpError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let ip = canonical_ip(ip);
+        self.check_domain(&ip.to_string())?;
+
+        match classify(ip) {
+            Some(UrlRejection::LoopbackAddress) if self.block_loopback => {
+                Err(UrlRejection::LoopbackAddress.error(format!("{ip} is a loopback address")))
+            }
+            Some(UrlRejection::PrivateAddress) if self.block_private_ips => {
+                Err(UrlRejection::PrivateAddress.error(format!("{ip} is a private address")))
+            }
+            Some(UrlRejection::LinkLocalAddress) if self.block_private_ips => {
+                Err(UrlRejection::LinkLocalAddress.error(format!("{ip} is a link-local address")))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A host after normalization.
+#[derive(Debug, PartialEq, Eq)]
+enum NormalizedHost {
+    /// ASCII domain name, lowercase and without trailing dot
+    Domain(String),
+
+    /// IP address, with IPv4-mapped addresses unwrapped
+    Ip(IpAddr),
+}
+
+/// Normalizes the host of a URL.
+fn normalize_host(url: &Url) -> Result<NormalizedHost, HttpError> {
+    match url.host() {
+        None => Err(UrlRejection::MissingHost.error(format!("{url} has no host"))),
+        Some(Host::Ipv4(ip)) => Ok(NormalizedHost::Ip(IpAddr::V4(ip))),
+        Some(Host::Ipv6(ip)) => Ok(NormalizedHost::Ip(canonical_ip(IpAddr::V6(ip)))),
+        Some(Host::Domain(domain)) => {
+            let domain = normalize_domain(domain)?;
+            // Hosts of non-special schemes are opaque and only become
+            // addresses here, once decoded
+            match domain.parse::<IpAddr>() {
+                Ok(ip) => Ok(NormalizedHost::Ip(canonical_ip(ip))),
+                Err(_) => Ok(NormalizedHost::Domain(domain)),
+            }
+        }
+    }
+}
+
+/// Percent-decodes a domain, converts it to lowercase punycode and strips
+/// trailing dots.
+fn normalize_domain(domain: &str) -> Result<String, HttpError> {
+    let decoded = percent_decode_str(domain)
+        .decode_utf8()
+        .map_err(|_| UrlRejection::InvalidHost.error(format!("{domain} is not valid UTF-8")))?;
+    let trimmed = decoded.trim_end_matches('.');
+    if trimmed.is_empty() {
+        return Err(UrlRejection::MissingHost.error("host is empty"));
+    }
+    idna::domain_to_ascii(trimmed).map_err(|_| UrlRejection::InvalidHost.error(format!("{domain} is not a valid domain")))
+}
+
+/// Unwraps IPv4-mapped IPv6 addresses.
+fn canonical_ip(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => IpAddr::V4(v4),
+            None => IpAddr::V6(v6),
+        },
+        v4 => v4,
+    }
+}
+
+/// Returns the blocked category of an address, if any.
+fn classify(ip: IpAddr) -> Option<UrlRejection> {
+    match ip {
+        IpAddr::V4(ip) => classify_v4(ip),
+        IpAddr::V6(ip) => classify_v6(ip),
+    }
+}
+
+fn classify_v4(ip: Ipv4Addr) -> Option<UrlRejection> {
+    let [a, b, ..] = ip.octets();
+    if ip.is_loopback() || ip.is_unspecified() || a == 0 {
+        Some(UrlRejection::LoopbackAddress)
+    } else if ip.is_link_local() {
+        Some(UrlRejection::LinkLocalAddress)
+    } else if ip.is_private() || (a == 100 && (64..128).contains(&b)) || ip.is_broadcast() {
+        // 100.64.0.0/10 is shared address space behind carrier-grade NAT
+        Some(UrlRejection::PrivateAddress)
+    } else {
+        None
+    }
+}
+
+fn classify_v6(ip: Ipv6Addr) -> Option<UrlRejection> {
+    let first = ip.segments()[0];
+    if ip.is_loopback() || ip.is_unspecified() {
+        Some(UrlRejection::LoopbackAddress)
+    } else if first & 0xffc0 == 0xfe80 {
+        Some(UrlRejection::LinkLocalAddress)
+    } else if first & 0xfe00 == 0xfc00 {
+        // fc00::/7 unique local addresses
+        Some(UrlRejection::PrivateAddress)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validator(configure: impl FnOnce(&mut UrlValidationConfig)) -> UrlValidator {
+        let mut config = UrlValidationConfig::default();
+        configure(&mut config);
+        UrlValidator::new(&config)
+    }
+
+    fn rejection(validator: &UrlValidator, url: &str) -> Option<String> {
+        match validator.parse(url) {
+            Ok(_) => None,
+            Err(HttpError::InvalidUrl(message)) => Some(message.split(':').next().unwrap().to_string()),
+            Err(e) => panic!("unexpected error for {url}: {e}"),
+        }
+    }
+
+    #[test]
+    fn test_schemes_and_length() {
+        let validator = validator(|config| config.max_url_length = 40);
+
+        assert_eq!(rejection(&validator, "https://example.com/"), None);
+        assert_eq!(rejection(&validator, "ftp://example.com/").as_deref(), Some("scheme_not_allowed"));
+        assert_eq!(rejection(&validator, "not a url").as_deref(), Some("malformed_url"));
+        let long = format!("https://example.com/{}", "a".repeat(40));
+        assert_eq!(rejection(&validator, &long).as_deref(), Some("url_too_long"));
+    }
+
+    #[test]
+    fn test_hosts_are_normalized_before_matching() {
+        let validator = validator(|config| {
+            config.disallowed_hosts = ["blocked.example.com".to_string(), "bücher.example".to_string()].into();
+        });
+
+        for url in [
+            "https://blocked.example.com/",
+            "https://BLOCKED.example.com./",
+            "https://%62locked.example.com/",
+            "https://xn--bcher-kva.example/",
+            "https://BÜCHER.example/",
+        ] {
+            assert_eq!(rejection(&validator, url).as_deref(), Some("host_disallowed"), "{url}");
+        }
+        assert_eq!(rejection(&validator, "https://sub.blocked.example.com/"), None);
+    }
+
+    #[test]
+    fn test_regex_patterns() {
+        let validator = validator(|config| {
+            config.disallowed_host_patterns = vec![r".*\.evil\.com$".to_string(), r"^tracker-\d+\.example\.".to_string()];
+        });
+
+        assert_eq!(rejection(&validator, "https://a.evil.com/").as_deref(), Some("host_pattern_disallowed"));
+        assert_eq!(rejection(&validator, "https://a.b.EVIL.com./").as_deref(), Some("host_pattern_disallowed"));
+        assert_eq!(rejection(&validator, "https://tracker-1.example.org/").as_deref(), Some("host_pattern_disallowed"));
+        assert_eq!(rejection(&validator, "https://evil.com/"), None);
+        assert_eq!(rejection(&validator, "https://notevil.com/"), None);
+        assert_eq!(rejection(&validator, "https://tracker.example.org/"), None);
+
+        let config = UrlValidationConfig {
+            disallowed_host_patterns: vec!["*.evil.com".to_string()],
+            ..Default::default()
+        };
+        assert!(crate::config::Validate::validate(&config).is_err());
+    }
+
+    #[test]
+    fn test_blocked_addresses() {
+        let validator = validator(|_| {});
+
+        for (url, code) in [
+            ("http://127.0.0.1/", "loopback_address"),
+            ("http://0x7f.1/", "loopback_address"),
+            ("http://2130706433/", "loopback_address"),
+            ("http://0.0.0.0/", "loopback_address"),
+            ("http://[::1]/", "loopback_address"),
+            ("http://localhost./", "loopback_address"),
+            ("http://app.localhost/", "loopback_address"),
+            ("http://10.1.2.3/", "private_address"),
+            ("http://192.168.0.1/", "private_address"),
+            ("http://172.16.5.4/", "private_address"),
+            ("http://100.64.0.1/", "private_address"),
+            ("http://[fd00::1]/", "private_address"),
+            ("http://169.254.169.254/", "link_local_address"),
+            ("http://[fe80::1]/", "link_local_address"),
+            ("http://[::ffff:127.0.0.1]/", "loopback_address"),
+            ("http://[::ffff:10.0.0.1]/", "private_address"),
+        ] {
+            assert_eq!(rejection(&validator, url).as_deref(), Some(code), "{url}");
+        }
+
+        assert_eq!(rejection(&validator, "http://93.184.216.34/"), None);
+        assert_eq!(rejection(&validator, "http://[2606:2800:220:1::]/"), None);
+        assert_eq!(rejection(&validator, "http://[::ffff:93.184.216.34]/"), None);
+    }
+
+    #[test]
+    fn test_address_blocking_is_configurable() {
+        let permissive = validator(|config| {
+            config.block_loopback = false;
+            config.block_private_ips = false;
+        });
+        assert_eq!(rejection(&permissive, "http://127.0.0.1/"), None);
+        assert_eq!(rejection(&permissive, "http://10.0.0.1/"), None);
+
+        let disabled = validator(|config| config.validate_on_request = false);
+        assert_eq!(rejection(&disabled, "ftp://127.0.0.1/"), None);
+    }
+
+    #[test]
+    fn test_rejections_are_invalid_url_errors() {
+        let error = validator(|_| {}).parse("http://10.0.0.1/").unwrap_err();
+        assert_eq!(error.to_string(), "Invalid URL: private_address: 10.0.0.1 is a private address");
+    }
+}
@@ -261,11 +261,23 @@ pub struct HttpClientConfig {
     /// Whether to enable HTTP/2
     pub http2_enabled: bool,
 
-    /// Maximum idle HTTP/2 streams per connection
+    /// Maximum locally reset HTTP/2 streams tracked per connection
     pub http2_max_idle_streams: u32,
 
     /// Maximum concurrent HTTP/2 streams per connection
     pub http2_max_concurrent_streams: u32,
+
+    /// Maximum size of a response body in bytes
+    #[serde(default = "default_max_response_bytes")]
+    pub max_response_bytes: usize,
+
+    /// Plaintext hosts that are sent HTTP/2 without negotiation (h2c)
+    #[serde(default)]
+    pub http2_prior_knowledge_hosts: Vec<String>,
+}
+
+fn default_max_response_bytes() -> usize {
+    10 * 1024 * 1024 // 10 MiB
 }
 
 impl Default for HttpClientConfig {
@@ -278,6 +290,8 @@ impl Default for HttpClientConfig {
             http2_enabled: true,
             http2_max_idle_streams: 100,
             http2_max_concurrent_streams: 250,
+            max_response_bytes: default_max_response_bytes(),
+            http2_prior_knowledge_hosts: Vec::new(),
         }
     }
 }
@@ -305,6 +319,20 @@ impl Validate for HttpClientConfig {
             ));
         }
 
+        // Validate max_response_bytes
+        if self.max_response_bytes == 0 {
+            return Err(ConfigError::ValidationError(
+                "max_response_bytes must be greater than 0".to_string(),
+            ));
+        }
+
+        // Validate http2_max_concurrent_streams
+        if self.http2_enabled && self.http2_max_concurrent_streams == 0 {
+            return Err(ConfigError::ValidationError(
+                "http2_max_concurrent_streams must be greater than 0 when HTTP/2 is enabled".to_string(),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -5,6 +5,7 @@
 
 use super::{ConfigResult, Validate};
 use crate::error::config::ConfigError;
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::path::PathBuf;
@@ -23,6 +24,10 @@ pub struct SecurityConfig {
 
     /// Content Security Policy configuration
     pub content_security: ContentSecurityConfig,
+
+    /// Client authentication configuration
+    #[serde(default)]
+    pub auth: AuthConfig,
 }
 
 impl Validate for SecurityConfig {
@@ -31,6 +36,7 @@ impl Validate for SecurityConfig {
         self.url_validation.validate()?;
         self.robots.validate()?;
         self.content_security.validate()?;
+        self.auth.validate()?;
         Ok(())
     }
 }
@@ -160,10 +166,31 @@ impl Validate for UrlValidationConfig {
             ));
         }
 
+        // Validate disallowed_host_patterns
+        for pattern in &self.disallowed_host_patterns {
+            if pattern.trim().is_empty() {
+                return Err(ConfigError::ValidationError(
+                    "disallowed_host_patterns cannot contain empty patterns".to_string(),
+                ));
+            }
+            if let Err(e) = host_pattern(pattern) {
+                return Err(ConfigError::ValidationError(format!(
+                    "Invalid disallowed_host_patterns entry {pattern:?}: {e}"
+                )));
+            }
+        }
+
         Ok(())
     }
 }
 
+/// Compiles a disallowed host pattern, matching hosts case-insensitively.
+///
+/// Invalid patterns are rejected when the configuration is validated.
+pub fn host_pattern(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern).case_insensitive(true).build()
+}
+
 /// Robots.txt compliance configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RobotsConfig {
@@ -240,3 +267,45 @@ impl Validate for ContentSecurityConfig {
         Ok(())
     }
 }
+
+/// Client authentication configuration.
+///
+/// When any bearer tokens are configured, clients must present one of them in
+/// an `Authorization: Bearer` header, except on trusted transports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuthConfig {
+    /// Tokens clients may authenticate with; empty disables authentication
+    pub bearer_tokens: Vec<String>,
+
+    /// Transports whose clients are trusted without a token
+    pub trusted_transports: HashSet<String>,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            bearer_tokens: Vec::new(),
+            trusted_transports: HashSet::from(["stdio".to_string()]),
+        }
+    }
+}
+
+impl AuthConfig {
+    /// Returns true if clients must authenticate.
+    pub fn is_enabled(&self) -> bool {
+        !self.bearer_tokens.is_empty()
+    }
+}
+
+impl Validate for AuthConfig {
+    fn validate(&self) -> ConfigResult<()> {
+        if self.bearer_tokens.iter().any(|token| token.trim().is_empty()) {
+            return Err(ConfigError::ValidationError(
+                "bearer_tokens cannot contain empty tokens".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
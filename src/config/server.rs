@@ -7,8 +7,10 @@ use super::ConfigResult;
 use super::Validate;
 use crate::error::config::ConfigError;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use tokio::sync::Semaphore;
 
 /// Transport type for the MCP server.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -18,7 +20,9 @@ pub enum TransportType {
     WebSocket,
     /// Standard I/O transport
     Stdio,
-    /// Support both transports
+    /// MCP Streamable HTTP transport (HTTP POST with Server-Sent Events)
+    Http,
+    /// Support both transports (stdio and WebSocket) at the same time
     Both,
 }
 
@@ -28,6 +32,19 @@ impl Default for TransportType {
     }
 }
 
+/// Order in which the calls of a JSON-RPC batch are run.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchMode {
+    /// Calls start in batch order and responses keep that order, so a slow
+    /// call holds back the calls more than the parallelism behind it
+    #[default]
+    Ordered,
+    /// A call starts whenever another finishes and responses come in
+    /// completion order
+    Unordered,
+}
+
 /// Server configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerConfig {
@@ -37,7 +54,7 @@ pub struct ServerConfig {
     /// Transport to use for communication
     pub transport: TransportType,
 
-    /// Address to bind to for WebSocket transport
+    /// Address to bind to for the WebSocket or Streamable HTTP transport
     pub address: SocketAddr,
 
     /// Number of worker threads for request processing
@@ -54,6 +71,67 @@ pub struct ServerConfig {
 
     /// Maximum message size in bytes
     pub max_message_size: usize,
+
+    /// Time in milliseconds that transports get to finish in-flight requests on shutdown
+    #[serde(default = "default_shutdown_drain_ms")]
+    pub shutdown_drain_ms: u64,
+
+    /// Maximum number of requests and notifications in one JSON-RPC batch
+    #[serde(default = "default_max_batch_size")]
+    pub max_batch_size: usize,
+
+    /// Maximum number of calls of one batch running at once
+    #[serde(default = "default_max_batch_parallelism")]
+    pub max_batch_parallelism: usize,
+
+    /// Order in which the calls of a batch are run
+    #[serde(default)]
+    pub batch_mode: BatchMode,
+
+    /// Limits of individual JSON-RPC methods, by method name
+    #[serde(default)]
+    pub methods: HashMap<String, MethodLimits>,
+
+    /// Time in milliseconds a request id stays taken on its session after the
+    /// request completes; 0 lets ids be reused as soon as their request completes
+    #[serde(default)]
+    pub replay_window_ms: u64,
+
+    /// Number of request ids a session is expected to complete within the replay window
+    #[serde(default = "default_replay_window_ids")]
+    pub replay_window_ids: usize,
+
+    /// Origins allowed to reach the Streamable HTTP and WebSocket endpoints
+    /// besides those of the local machine, e.g. `https://app.example.com`
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+}
+
+/// Limits of one JSON-RPC method, overriding the server-wide ones.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct MethodLimits {
+    /// Request timeout in milliseconds, instead of `default_timeout_ms`
+    pub timeout_ms: Option<u64>,
+
+    /// Maximum number of calls of the method running at once
+    pub max_concurrent: Option<usize>,
+}
+
+fn default_shutdown_drain_ms() -> u64 {
+    10000
+}
+
+fn default_max_batch_size() -> usize {
+    1000
+}
+
+fn default_max_batch_parallelism() -> usize {
+    16
+}
+
+fn default_replay_window_ids() -> usize {
+    10000
 }
 
 impl Default for ServerConfig {
@@ -67,6 +145,14 @@ impl Default for ServerConfig {
             default_timeout_ms: 30000,
             state_dir: PathBuf::from("/var/lib/mauka-mcp"),
             max_message_size: 10 * 1024 * 1024, // 10 MiB
+            shutdown_drain_ms: default_shutdown_drain_ms(),
+            max_batch_size: default_max_batch_size(),
+            max_batch_parallelism: default_max_batch_parallelism(),
+            batch_mode: BatchMode::default(),
+            methods: HashMap::new(),
+            replay_window_ms: 0,
+            replay_window_ids: default_replay_window_ids(),
+            allowed_origins: Vec::new(),
         }
     }
 }
@@ -93,6 +179,12 @@ impl Validate for ServerConfig {
                 "max_concurrent_requests must be greater than 0".to_string(),
             ));
         }
+        if self.max_concurrent_requests > Semaphore::MAX_PERMITS {
+            return Err(ConfigError::ValidationError(format!(
+                "max_concurrent_requests must be at most {}",
+                Semaphore::MAX_PERMITS
+            )));
+        }
 
         // Validate default_timeout_ms
         if self.default_timeout_ms == 0 {
@@ -108,6 +200,42 @@ impl Validate for ServerConfig {
             ));
         }
 
+        // Validate shutdown_drain_ms
+        if self.shutdown_drain_ms == 0 {
+            return Err(ConfigError::ValidationError(
+                "shutdown_drain_ms must be greater than 0".to_string(),
+            ));
+        }
+
+        // Validate batch limits
+        if self.max_batch_size == 0 || self.max_batch_parallelism == 0 {
+            return Err(ConfigError::ValidationError(
+                "max_batch_size and max_batch_parallelism must be greater than 0".to_string(),
+            ));
+        }
+
+        // Validate replay window
+        if self.replay_window_ms > 0 && self.replay_window_ids == 0 {
+            return Err(ConfigError::ValidationError(
+                "replay_window_ids must be greater than 0 when replay_window_ms is set".to_string(),
+            ));
+        }
+
+        // Validate per-method limits
+        for (method, limits) in &self.methods {
+            if limits.timeout_ms == Some(0) || limits.max_concurrent == Some(0) {
+                return Err(ConfigError::ValidationError(format!(
+                    "limits of method {method} must be greater than 0"
+                )));
+            }
+            if limits.max_concurrent.is_some_and(|max| max > Semaphore::MAX_PERMITS) {
+                return Err(ConfigError::ValidationError(format!(
+                    "max_concurrent of method {method} must be at most {}",
+                    Semaphore::MAX_PERMITS
+                )));
+            }
+        }
+
         Ok(())
     }
 }
@@ -12,6 +12,8 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 
+use parking_lot::Mutex;
+
 use crate::data_structures::kona_bloom_filter::config::KonaBloomFilterConfig;
 use crate::data_structures::kona_bloom_filter::hash::{FnvMultiHasher, MultiHasher};
 
@@ -22,7 +24,8 @@ use crate::data_structures::kona_bloom_filter::hash::{FnvMultiHasher, MultiHashe
 /// tracks which items are worth caching based on past access patterns.
 ///
 /// This implementation uses atomic operations to ensure thread safety without locks
-/// and supports generational rotation for approximate time-based expiry.
+/// and supports generational rotation for approximate time-based expiry; only
+/// rotating a generation takes a brief lock.
 ///
 /// # Type Parameters
 ///
@@ -60,8 +63,14 @@ pub struct KonaBloomFilter<T: Hash + Eq> {
     /// Current active generation index
     current_generation: AtomicU64,
     
-    /// Time when the current generation started - using Cell for interior mutability
-    generation_start: std::cell::Cell<Instant>,
+    /// Time the start of each generation is measured from
+    epoch: Instant,
+    
+    /// Nanoseconds from `epoch` to the start of the current generation
+    generation_start: AtomicU64,
+    
+    /// Held while rotating, so that one thread rotates at a time
+    rotation: Mutex<()>,
     
     /// Hasher for computing bit positions
     hasher: FnvMultiHasher<T>,
@@ -101,7 +110,9 @@ impl<T: Hash + Eq> KonaBloomFilter<T> {
             config,
             generations,
             current_generation: AtomicU64::new(0),
-            generation_start: std::cell::Cell::new(Instant::now()),
+            epoch: Instant::now(),
+            generation_start: AtomicU64::new(0),
+            rotation: Mutex::new(()),
             hasher: FnvMultiHasher::new(),
             _marker: PhantomData,
         }
@@ -196,9 +207,9 @@ impl<T: Hash + Eq> KonaBloomFilter<T> {
             }
         }
         
+        let _rotation = self.rotation.lock();
         self.current_generation.store(0, Ordering::Relaxed);
-        // Reset generation timer using Cell API - thread-safe interior mutability
-        self.generation_start.set(Instant::now());
+        self.generation_start.store(self.nanos_since_epoch(), Ordering::Release);
     }
     
     /// Get the estimated fill ratio of the filter.
@@ -257,6 +268,10 @@ impl<T: Hash + Eq> KonaBloomFilter<T> {
     }
     
     /// Check if it's time to rotate to a new generation and perform the rotation if needed.
+    ///
+    /// The filter rotates once for every generation duration that passed since
+    /// the current generation started, however long it sat unused, emptying
+    /// each generation it rotates into.
     fn maybe_rotate_generation(&self) {
         // Skip if generations aren't being used
         if !self.config.get_use_generations() { 
@@ -264,33 +279,43 @@ impl<T: Hash + Eq> KonaBloomFilter<T> {
         }
         
         // Check if it's time to rotate generations
-        let now = Instant::now();
-        let elapsed = now.duration_since(self.generation_start.get());
+        let duration = self.config.get_generation_duration().as_nanos().clamp(1, u64::MAX as u128) as u64;
+        let start = self.generation_start.load(Ordering::Acquire);
+        if self.nanos_since_epoch().saturating_sub(start) < duration {
+            return;
+        }
         
-        if elapsed >= self.config.get_generation_duration() {
-            // Update the current generation atomically
-            let current_gen = self.current_generation.fetch_add(1, Ordering::AcqRel) + 1;
-            let wrapped_gen = current_gen as usize % self.config.get_generation_count();
-            
-            // Reset the new generation's bit array
-            let gen = &self.generations[wrapped_gen];
+        // Rotate, unless another thread did while this one waited for the lock
+        let _rotation = self.rotation.lock();
+        let now = self.nanos_since_epoch();
+        let elapsed = now.saturating_sub(self.generation_start.load(Ordering::Acquire));
+        if elapsed < duration {
+            return;
+        }
+        
+        // Reset every generation rotated into, all of them at most
+        let gen_count = self.generations.len() as u64;
+        let rotations = elapsed / duration;
+        let current_gen = self.current_generation.load(Ordering::Acquire);
+        for step in 1..=rotations.min(gen_count) {
+            let gen = &self.generations[((current_gen + step) % gen_count) as usize];
             for bit in gen.iter() {
                 bit.store(0, Ordering::Relaxed);
             }
-            
-            // Update the generation start time using Cell API
-            // This is thread-safe for interior mutability without locks
-            // Cell provides single-threaded interior mutability, which is sufficient here
-            // since timestamp precision isn't critical for correctness
-            self.generation_start.set(Instant::now());
         }
+        let new_gen = (current_gen + rotations % gen_count) % gen_count;
+        self.current_generation.store(new_gen, Ordering::Release);
+        
+        // Keep the time already spent in the new generation
+        self.generation_start.store(now - elapsed % duration, Ordering::Release);
+    }
+    
+    /// Get the nanoseconds elapsed since `epoch`.
+    fn nanos_since_epoch(&self) -> u64 {
+        self.epoch.elapsed().as_nanos() as u64
     }
 }
 
-// Safe to be shared across threads
-unsafe impl<T: Hash + Eq + Send> Send for KonaBloomFilter<T> {}
-unsafe impl<T: Hash + Eq + Sync> Sync for KonaBloomFilter<T> {}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -298,6 +323,12 @@ mod tests {
     use std::sync::Barrier;
     use std::thread;
     
+    /// Makes the current generation of `filter` look `age` old.
+    fn age_generation(filter: &mut KonaBloomFilter<String>, age: Duration) {
+        filter.epoch = Instant::now() - age;
+        filter.generation_start.store(0, Ordering::Relaxed);
+    }
+    
     #[test]
     fn test_bloom_filter_basic() {
         let filter = KonaBloomFilter::<String>::new();
@@ -399,12 +430,12 @@ mod tests {
             .with_generation_count(3)
             .with_generation_duration(Duration::from_millis(100)); // Short duration for testing
             
-        let filter = KonaBloomFilter::<String>::with_config(config);
+        let mut filter = KonaBloomFilter::<String>::with_config(config);
         
         filter.insert("gen0".to_string());
         
         // Force a generation rotation by simulating time passing
-        filter.generation_start.set(Instant::now() - Duration::from_millis(200));
+        age_generation(&mut filter, Duration::from_millis(150));
         
         filter.insert("gen1".to_string());
         
@@ -413,7 +444,7 @@ mod tests {
         assert!(filter.check("gen1".to_string()));
         
         // Force another generation rotation
-        filter.generation_start.set(Instant::now() - Duration::from_millis(200));
+        age_generation(&mut filter, Duration::from_millis(150));
         
         filter.insert("gen2".to_string());
         
@@ -423,7 +454,7 @@ mod tests {
         assert!(filter.check("gen2".to_string()));
         
         // Force one more generation rotation (should wrap around and clear gen0)
-        filter.generation_start.set(Instant::now() - Duration::from_millis(200));
+        age_generation(&mut filter, Duration::from_millis(150));
         
         // Access an item to trigger rotation
         filter.check("trigger_rotation".to_string());
@@ -433,7 +464,30 @@ mod tests {
         assert!(filter.check("gen1".to_string()));
         assert!(filter.check("gen2".to_string()));
     }
-    
+
+    #[test]
+    fn test_idle_filter_expires_all_generations() {
+        let config = KonaBloomFilterConfig::new()
+            .with_generations(true)
+            .with_generation_count(3)
+            .with_generation_duration(Duration::from_millis(100));
+
+        let mut filter = KonaBloomFilter::<String>::with_config(config);
+
+        // Rotating many times over keeps the generation index in bounds
+        for round in 0..10 {
+            filter.insert(format!("item{round}"));
+            age_generation(&mut filter, Duration::from_millis(150));
+        }
+        assert!(filter.check("item9".to_string()));
+
+        // An idle gap longer than all generations together empties every one
+        age_generation(&mut filter, Duration::from_millis(1000));
+        for round in 0..10 {
+            assert!(!filter.check(format!("item{round}")));
+        }
+    }
+
     #[test]
     fn test_false_positive_rate() {
         let expected_items = 10_000;
@@ -11,6 +11,7 @@ use thiserror::Error;
 
 pub mod config;
 pub mod http;
+pub mod prompt;
 pub mod protocol;
 pub mod transport;
 
@@ -28,6 +29,10 @@ pub enum MaukaError {
     #[error("Protocol error: {0}")]
     Protocol(#[from] protocol::ProtocolError),
 
+    /// Errors from requests the server sends to the client.
+    #[error("Client request error: {0}")]
+    ClientRequest(#[from] protocol::ClientRequestError),
+
     /// Errors related to transport mechanisms (WebSocket, Stdio).
     #[error("Transport error: {0}")]
     Transport(#[from] transport::TransportError),
@@ -36,6 +41,10 @@ pub enum MaukaError {
     #[error("HTTP client error: {0}")]
     Http(#[from] http::HttpError),
 
+    /// Errors related to prompt templates.
+    #[error("Prompt error: {0}")]
+    Prompt(#[from] prompt::PromptError),
+
     /// IO errors that may occur during file operations.
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -0,0 +1,40 @@
+//! Prompt error module.
+//!
+//! This module defines error types that may occur while loading prompt
+//! templates and rendering them for `prompts/get`.
+
+use thiserror::Error;
+
+/// Errors that can occur during prompt operations.
+#[derive(Error, Debug)]
+pub enum PromptError {
+    /// Error when no prompt with the requested name exists.
+    #[error("Unknown prompt: {0}")]
+    UnknownPrompt(String),
+
+    /// Error when an argument the prompt does not declare is supplied.
+    #[error("Unknown argument: {0}")]
+    UnknownArgument(String),
+
+    /// Error when a required argument is not supplied.
+    #[error("Missing required argument: {0}")]
+    MissingArgument(String),
+
+    /// Error when an argument value does not match its declared type.
+    #[error("Invalid value for argument {name}: {reason}")]
+    InvalidArgument {
+        /// Name of the argument
+        name: String,
+        /// Why the value was rejected
+        reason: String,
+    },
+
+    /// Error when a template file cannot be parsed or is inconsistent.
+    #[error("Invalid prompt template {source_name}: {reason}")]
+    InvalidTemplate {
+        /// File or name the template was loaded from
+        source_name: String,
+        /// What is wrong with the template
+        reason: String,
+    },
+}
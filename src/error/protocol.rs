@@ -5,6 +5,8 @@
 
 use thiserror::Error;
 
+use crate::protocol::jsonrpc::error::JsonRpcError;
+
 /// Errors that can occur during protocol operations.
 #[derive(Error, Debug)]
 pub enum ProtocolError {
@@ -57,3 +59,32 @@ pub enum ProtocolError {
     #[error("Protocol error: {0}")]
     Other(String),
 }
+
+/// Errors that can occur when the server sends a request to the client.
+#[derive(Error, Debug)]
+pub enum ClientRequestError {
+    /// The session has no channel to the client.
+    #[error("Client is not connected")]
+    NotConnected,
+
+    /// The client did not announce the capability the request needs.
+    #[error("Client does not support {0}")]
+    Unsupported(&'static str),
+
+    /// The client did not answer in time.
+    #[error("Client did not respond to {method} within {timeout_ms} ms")]
+    Timeout {
+        /// Method of the request
+        method: String,
+        /// How long the server waited, in milliseconds
+        timeout_ms: u64,
+    },
+
+    /// The client answered with an error.
+    #[error("Client returned error {}: {}", .0.code, .0.message)]
+    Rejected(JsonRpcError),
+
+    /// The client's result does not have the expected shape.
+    #[error("Invalid client response: {0}")]
+    InvalidResponse(String),
+}
@@ -16,10 +16,15 @@
 //! - Lock-free concurrency for high throughput
 
 // Re-export public modules
+pub mod client;
 pub mod config;
 pub mod data_structures;
 pub mod error;
+pub mod prompts;
 pub mod protocol;
+pub mod resources;
+pub mod tools;
+pub mod transport;
 pub mod utils;
 
 // Internal modules that are not part of the public API
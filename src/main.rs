@@ -3,19 +3,17 @@
 //! This is the main entry point for the Mauka MCP Server application.
 //! It initializes the logging system, loads configuration, and starts the server.
 
-mod config;
-mod error;
-mod protocol;
-mod utils;
-
-#[cfg(test)]
-mod tests;
-
 use clap::{Parser, Subcommand};
-use error::{set_error_reporter, MaukaError, MaukaResult, TracingErrorReporter};
+use mauka_mcp_lib::error::{set_error_reporter, MaukaError, MaukaResult, TracingErrorReporter};
+use mauka_mcp_lib::protocol::jsonrpc::methods::discover::discover_document;
+use mauka_mcp_lib::protocol::jsonrpc::{register_standard_methods, register_standard_middlewares, DispatchLimits, JsonRpcHandler};
+use mauka_mcp_lib::transport::TransportSupervisor;
+use mauka_mcp_lib::{client, config, prompts, protocol, resources, tools};
 use std::path::PathBuf;
 use std::process;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 use tracing::info;
 
 /// Command line arguments for the Mauka MCP Server.
@@ -46,11 +44,22 @@ enum Command {
         #[clap(short, long, value_parser)]
         output: PathBuf,
     },
+
+    /// Print the OpenRPC document describing the server's methods and tools
+    #[clap(name = "openrpc")]
+    OpenRpc {
+        /// Path to output document, instead of stdout
+        #[clap(short, long, value_parser)]
+        output: Option<PathBuf>,
+    },
 }
 
 /// Initialize the logging system.
+///
+/// Logs are written to stderr so that stdout stays reserved for the stdio transport.
 fn init_logging() -> MaukaResult<()> {
     let subscriber = tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
         .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
         .with_line_number(true)
         .with_file(true)
@@ -101,11 +110,66 @@ fn main() -> MaukaResult<()> {
                 server_config.name, server_config.transport, server_config.address
             );
 
-            // TODO: Initialize and start server components
-            // This will be implemented in subsequent phases
-            info!("Server initialized successfully");
-
-            Ok(())
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(server_config.worker_threads)
+                .enable_all()
+                .build()
+                .map_err(MaukaError::Io)?;
+
+            let result = runtime.block_on(async {
+                let config = global_config.get();
+                let client = Arc::new(client::HttpClient::new(&config.http, &config.security)?);
+
+                let tools = Arc::new(tools::ToolRegistry::new());
+                tools::register_builtin_tools(&tools, client.clone());
+
+                let methods = Arc::new(protocol::jsonrpc::middleware::MethodMetrics::new());
+                let resources = Arc::new(resources::ResourceRegistry::new());
+                resources::register_builtin_resources(&resources, Arc::new(config.clone()), client, methods.clone())?;
+
+                // Notify clients when resources or prompts change
+                let watcher = CancellationToken::new();
+                tokio::spawn(
+                    resources
+                        .clone()
+                        .watch(resources::DEFAULT_WATCH_INTERVAL, watcher.clone()),
+                );
+
+                // Prompts are only offered when the server has a prompts directory
+                let prompts_dir = prompts::prompts_dir(&config.server);
+                let prompts = if prompts_dir.is_dir() {
+                    let prompts = Arc::new(prompts::PromptRegistry::from_dir(&prompts_dir)?);
+                    info!("Serving prompts from {}", prompts_dir.display());
+                    tokio::spawn(
+                        prompts
+                            .clone()
+                            .watch(prompts::DEFAULT_WATCH_INTERVAL, watcher.clone()),
+                    );
+                    Some(prompts)
+                } else {
+                    None
+                };
+
+                let mut builder = JsonRpcHandler::builder();
+                register_standard_methods(&mut builder, tools, resources, prompts);
+                register_standard_middlewares(&mut builder, config, methods);
+                builder.set_dispatch_limits(DispatchLimits::from_config(&config.server));
+                let handler = builder.build();
+                info!("Server initialized successfully");
+
+                let result = TransportSupervisor::from_config(config)
+                    .run_until_signal(handler)
+                    .await;
+                watcher.cancel();
+                result?;
+
+                info!("Server stopped");
+                Ok(())
+            });
+
+            // A pending stdin read can't be interrupted, so don't wait for it
+            runtime.shutdown_timeout(Duration::from_secs(1));
+            result
         }
         Command::Validate => {
             info!("Validating configuration");
@@ -139,5 +203,39 @@ fn main() -> MaukaResult<()> {
             info!("Default configuration written to {:?}", output);
             Ok(())
         }
+        Command::OpenRpc { output } => {
+            let config = match config_loader.load() {
+                Ok(config) => config,
+                Err(e) => {
+                    tracing::error!("Configuration error: {}", e);
+                    process::exit(1);
+                }
+            };
+
+            // Register what the server would serve, without starting it
+            let client = Arc::new(client::HttpClient::new(&config.http, &config.security)?);
+            let tools = Arc::new(tools::ToolRegistry::new());
+            tools::register_builtin_tools(&tools, client);
+            let prompts_dir = prompts::prompts_dir(&config.server);
+            let prompts = if prompts_dir.is_dir() {
+                Some(Arc::new(prompts::PromptRegistry::from_dir(&prompts_dir)?))
+            } else {
+                None
+            };
+            let mut builder = JsonRpcHandler::builder();
+            register_standard_methods(&mut builder, tools.clone(), Arc::new(resources::ResourceRegistry::new()), prompts);
+
+            let document = discover_document(builder.methods(), &tools);
+            let json = serde_json::to_string_pretty(&document)
+                .map_err(|e| MaukaError::Custom(format!("Failed to serialize OpenRPC document: {e}")))?;
+            match output {
+                Some(output) => {
+                    std::fs::write(&output, json).map_err(MaukaError::Io)?;
+                    info!("OpenRPC document written to {:?}", output);
+                }
+                None => println!("{json}"),
+            }
+            Ok(())
+        }
     }
 }
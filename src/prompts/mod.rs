@@ -0,0 +1,33 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! MCP prompts.
+//!
+//! Prompts are reusable message templates, such as "summarize this page",
+//! loaded from TOML files in the [`PROMPTS_DIR`] directory under the server's
+//! state directory. See [`template`] for the file format. The prompts
+//! capability is only offered when that directory exists.
+
+pub mod registry;
+pub mod template;
+pub mod types;
+
+use std::path::PathBuf;
+
+use crate::config::server::ServerConfig;
+
+// Re-exports
+pub use registry::{PromptRegistry, DEFAULT_WATCH_INTERVAL, PROMPTS_LIST_CHANGED_NOTIFICATION};
+pub use template::{ArgumentSpec, ArgumentType, MessageTemplate, PromptTemplate};
+pub use types::{Completion, GetPromptParams, GetPromptResult, Prompt, PromptArgument, PromptMessage, Role};
+
+/// Name of the directory under [`ServerConfig::state_dir`] holding prompt templates.
+pub const PROMPTS_DIR: &str = "prompts";
+
+/// Returns the directory prompt templates are loaded from.
+pub fn prompts_dir(server: &ServerConfig) -> PathBuf {
+    server.state_dir.join(PROMPTS_DIR)
+}
@@ -0,0 +1,336 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Prompt registry.
+//!
+//! The registry holds the [`PromptTemplate`]s loaded from a prompts directory
+//! and backs `prompts/list`, `prompts/get` and prompt argument completion.
+//!
+//! Sessions that have listed or fetched prompts are remembered as listeners.
+//! [`PromptRegistry::watch`] reloads the directory whenever its contents change
+//! and sends `notifications/prompts/list_changed` to every listener.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, SystemTime};
+
+use parking_lot::{Mutex, RwLock};
+use tokio_util::sync::CancellationToken;
+
+use super::template::PromptTemplate;
+use super::types::{Completion, GetPromptResult, Prompt};
+use crate::error::prompt::PromptError;
+use crate::error::MaukaResult;
+use crate::protocol::jsonrpc::types::Request;
+use crate::protocol::Session;
+use crate::resources::registry::{paginate, Page, DEFAULT_PAGE_SIZE};
+
+/// Method of the notification sent to listeners when the prompt list changes.
+pub const PROMPTS_LIST_CHANGED_NOTIFICATION: &str = "notifications/prompts/list_changed";
+
+/// Extension of prompt template files.
+pub const TEMPLATE_EXTENSION: &str = "toml";
+
+/// Default interval between checks of the prompts directory.
+pub const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Name, modification time and size of every template file in a directory.
+type Fingerprint = Vec<(PathBuf, Option<SystemTime>, u64)>;
+
+/// Registry of the prompts exposed by the server.
+///
+/// Prompts are listed in name order, with the same cursor pagination as
+/// resources.
+pub struct PromptRegistry {
+    /// Directory templates are loaded from, if any
+    dir: Option<PathBuf>,
+
+    /// Loaded templates, keyed by prompt name
+    prompts: RwLock<BTreeMap<String, Arc<PromptTemplate>>>,
+
+    /// Sessions to notify of list changes, keyed by session id
+    listeners: Mutex<HashMap<String, Weak<Session>>>,
+
+    /// Entries returned per page
+    page_size: usize,
+}
+
+impl Default for PromptRegistry {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            prompts: RwLock::default(),
+            listeners: Mutex::default(),
+            page_size: DEFAULT_PAGE_SIZE,
+        }
+    }
+}
+
+impl PromptRegistry {
+    /// Creates an empty registry that is not backed by a directory.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry holding the templates in `dir`.
+    ///
+    /// Fails if the directory cannot be read. Templates that fail to load are
+    /// logged and skipped.
+    pub fn from_dir(dir: impl Into<PathBuf>) -> MaukaResult<Self> {
+        let registry = Self {
+            dir: Some(dir.into()),
+            ..Self::default()
+        };
+        registry.reload()?;
+        Ok(registry)
+    }
+
+    /// Sets the number of entries returned per page.
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Returns the directory templates are loaded from.
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    /// Adds a template, replacing any prompt with the same name.
+    pub fn insert(&self, template: PromptTemplate) {
+        self.prompts.write().insert(template.name.clone(), Arc::new(template));
+    }
+
+    /// Returns whether no prompts are loaded.
+    pub fn is_empty(&self) -> bool {
+        self.prompts.read().is_empty()
+    }
+
+    /// Replaces the loaded templates with the current contents of the directory.
+    ///
+    /// A directory that no longer exists holds no prompts. Does nothing for a
+    /// registry not backed by a directory.
+    pub fn reload(&self) -> MaukaResult<()> {
+        let Some(dir) = &self.dir else {
+            return Ok(());
+        };
+
+        let mut prompts = BTreeMap::new();
+        for path in template_files(dir)? {
+            match PromptTemplate::load(&path) {
+                Ok(template) => {
+                    prompts.insert(template.name.clone(), Arc::new(template));
+                }
+                Err(e) => tracing::warn!(path = %path.display(), error = %e, "Skipping prompt template"),
+            }
+        }
+
+        tracing::debug!(dir = %dir.display(), count = prompts.len(), "Loaded prompt templates");
+        *self.prompts.write() = prompts;
+        Ok(())
+    }
+
+    /// Returns one page of prompt metadata.
+    ///
+    /// Returns `None` if `cursor` was not issued by this registry.
+    pub fn list(&self, cursor: Option<&str>) -> Option<Page<Prompt>> {
+        let prompts = self.prompts.read();
+        paginate(&prompts, cursor, self.page_size, |template| template.prompt())
+    }
+
+    /// Renders the prompt called `name` with the given argument values.
+    pub fn get(&self, name: &str, arguments: &HashMap<String, String>) -> Result<GetPromptResult, PromptError> {
+        self.template(name)?.render(arguments)
+    }
+
+    /// Suggests values for an argument of the prompt called `name`.
+    ///
+    /// Arguments the prompt does not declare have no suggestions.
+    pub fn complete(&self, name: &str, argument: &str, value: &str) -> Result<Completion, PromptError> {
+        Ok(self.template(name)?.complete(argument, value).unwrap_or_default())
+    }
+
+    fn template(&self, name: &str) -> Result<Arc<PromptTemplate>, PromptError> {
+        self.prompts
+            .read()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| PromptError::UnknownPrompt(name.to_string()))
+    }
+
+    /// Remembers `session` as a listener for list changes.
+    pub fn observe(&self, session: &Arc<Session>) {
+        self.listeners
+            .lock()
+            .insert(session.id().to_string(), Arc::downgrade(session));
+    }
+
+    /// Sends `notifications/prompts/list_changed` to every listener.
+    ///
+    /// Listeners whose session has ended or can no longer be reached are
+    /// dropped. Returns the number of sessions notified.
+    pub fn notify_list_changed(&self) -> usize {
+        let notification = Request::notification(PROMPTS_LIST_CHANGED_NOTIFICATION, None);
+        let Ok(message) = serde_json::to_string(&notification) else {
+            return 0;
+        };
+
+        let mut listeners = self.listeners.lock();
+        listeners.retain(|_, session| session.upgrade().is_some_and(|session| session.send(message.clone())));
+        listeners.len()
+    }
+
+    /// Checks the directory every `interval`, reloading the templates and
+    /// notifying listeners whenever its contents change, until `shutdown` is
+    /// cancelled.
+    pub async fn watch(self: Arc<Self>, interval: Duration, shutdown: CancellationToken) {
+        let Some(dir) = self.dir.clone() else {
+            return;
+        };
+
+        let mut last = fingerprint(&dir);
+        let mut ticks = tokio::time::interval(interval);
+        loop {
+            tokio::select! {
+                _ = ticks.tick() => {}
+                _ = shutdown.cancelled() => return,
+            }
+
+            let registry = self.clone();
+            let dir = dir.clone();
+            let last_seen = last.clone();
+            let reloaded = tokio::task::spawn_blocking(move || {
+                let current = fingerprint(&dir);
+                if current == last_seen {
+                    return None;
+                }
+                if let Err(e) = registry.reload() {
+                    tracing::warn!(dir = %dir.display(), error = %e, "Failed to reload prompt templates");
+                }
+                Some(current)
+            })
+            .await;
+
+            if let Ok(Some(current)) = reloaded {
+                last = current;
+                let notified = self.notify_list_changed();
+                tracing::info!(notified, "Prompt templates changed");
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for PromptRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PromptRegistry")
+            .field("dir", &self.dir)
+            .field("prompts", &self.prompts.read().keys().collect::<Vec<_>>())
+            .field("page_size", &self.page_size)
+            .finish()
+    }
+}
+
+/// Returns the template files in `dir`, in name order.
+fn template_files(dir: &Path) -> MaukaResult<Vec<PathBuf>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == TEMPLATE_EXTENSION))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Fingerprints the template files in `dir` to detect changes.
+fn fingerprint(dir: &Path) -> Fingerprint {
+    template_files(dir)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|path| {
+            let metadata = std::fs::metadata(&path).ok();
+            let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+            let len = metadata.map_or(0, |m| m.len());
+            (path, modified, len)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    const COMPARE: &str = r#"
+        description = "Compare two pages"
+
+        [[arguments]]
+        name = "first"
+        type = "url"
+        required = true
+
+        [[arguments]]
+        name = "second"
+        type = "url"
+        required = true
+
+        [[messages]]
+        role = "user"
+        text = "Compare {{first}} with {{second}}."
+    "#;
+
+    #[test]
+    fn test_loads_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("compare-urls.toml"), COMPARE).unwrap();
+        std::fs::write(dir.path().join("broken.toml"), "messages = 1").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not a template").unwrap();
+
+        let registry = PromptRegistry::from_dir(dir.path()).unwrap();
+        let page = registry.list(None).unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].name, "compare-urls");
+        assert_eq!(page.items[0].arguments.len(), 2);
+
+        let arguments = HashMap::from([
+            ("first".to_string(), "https://a.example".to_string()),
+            ("second".to_string(), "https://b.example".to_string()),
+        ]);
+        let result = registry.get("compare-urls", &arguments).unwrap();
+        assert_eq!(result.description.as_deref(), Some("Compare two pages"));
+        assert!(matches!(registry.get("nope", &arguments), Err(PromptError::UnknownPrompt(_))));
+    }
+
+    #[tokio::test]
+    async fn test_watch_reloads_and_notifies() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = Arc::new(PromptRegistry::from_dir(dir.path()).unwrap());
+        assert!(registry.is_empty());
+
+        let session = Arc::new(Session::new("test"));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        session.attach_outbound(tx);
+        registry.observe(&session);
+
+        let shutdown = CancellationToken::new();
+        let watcher = tokio::spawn(registry.clone().watch(Duration::from_millis(10), shutdown.clone()));
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        std::fs::write(dir.path().join("compare-urls.toml"), COMPARE).unwrap();
+
+        let message = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap().unwrap();
+        assert!(message.contains(PROMPTS_LIST_CHANGED_NOTIFICATION));
+        assert!(!registry.is_empty());
+
+        shutdown.cancel();
+        watcher.await.unwrap();
+    }
+}
@@ -0,0 +1,404 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! File-backed prompt templates.
+//!
+//! A template is a TOML file declaring the prompt's metadata, its typed
+//! arguments and the messages it renders to:
+//!
+//! ```toml
+//! title = "Summarize a page"
+//! description = "Fetch a page and summarize it"
+//!
+//! [[arguments]]
+//! name = "url"
+//! type = "url"
+//! required = true
+//!
+//! [[arguments]]
+//! name = "length"
+//! type = "enum"
+//! values = ["short", "medium", "long"]
+//! default = "short"
+//!
+//! [[messages]]
+//! role = "user"
+//! text = "Fetch {{url}} and write a {{length}} summary of it."
+//! ```
+//!
+//! The prompt is named after the file unless `name` is set. Messages refer to
+//! arguments as `{{name}}`; an optional argument that is not supplied renders
+//! as its default, or as nothing.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Deserialize;
+use url::Url;
+
+use super::types::{Completion, GetPromptResult, Prompt, PromptArgument, PromptMessage, Role};
+use crate::error::prompt::PromptError;
+use crate::tools::ToolContent;
+
+/// Type of a prompt argument's value.
+///
+/// Values are always passed as strings; the type decides which strings are
+/// accepted and which values are offered for completion.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArgumentType {
+    /// Any text
+    #[default]
+    String,
+
+    /// An absolute `http` or `https` URL
+    Url,
+
+    /// A whole number
+    Integer,
+
+    /// Any number
+    Number,
+
+    /// `true` or `false`
+    Boolean,
+
+    /// One of the argument's `values`
+    Enum,
+}
+
+/// An argument declared by a template.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ArgumentSpec {
+    /// Programmatic name
+    pub name: String,
+
+    /// Human-readable display name
+    #[serde(default)]
+    pub title: Option<String>,
+
+    /// Description of the argument
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Type of the argument's value
+    #[serde(default, rename = "type")]
+    pub kind: ArgumentType,
+
+    /// Whether the argument must be supplied
+    #[serde(default)]
+    pub required: bool,
+
+    /// Value used when the argument is not supplied
+    #[serde(default)]
+    pub default: Option<String>,
+
+    /// Accepted values of an `enum` argument
+    #[serde(default)]
+    pub values: Vec<String>,
+
+    /// Values offered for completion in addition to the accepted values
+    #[serde(default)]
+    pub suggestions: Vec<String>,
+}
+
+impl ArgumentSpec {
+    /// Checks that `value` is acceptable for this argument.
+    fn check(&self, value: &str) -> Result<(), PromptError> {
+        let reason = match self.kind {
+            ArgumentType::String => None,
+            ArgumentType::Url => match Url::parse(value) {
+                Ok(url) if matches!(url.scheme(), "http" | "https") => None,
+                Ok(url) => Some(format!("unsupported URL scheme {}", url.scheme())),
+                Err(e) => Some(format!("not a URL: {e}")),
+            },
+            ArgumentType::Integer => value.parse::<i64>().err().map(|_| "not an integer".to_string()),
+            ArgumentType::Number => value.parse::<f64>().err().map(|_| "not a number".to_string()),
+            ArgumentType::Boolean => value.parse::<bool>().err().map(|_| "not true or false".to_string()),
+            ArgumentType::Enum => (!self.values.iter().any(|allowed| allowed == value))
+                .then(|| format!("expected one of {}", self.values.join(", "))),
+        };
+        match reason {
+            Some(reason) => Err(PromptError::InvalidArgument {
+                name: self.name.clone(),
+                reason,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the values offered for completion.
+    fn candidates(&self) -> Vec<&str> {
+        let mut candidates: Vec<&str> = match self.kind {
+            ArgumentType::Boolean => vec!["false", "true"],
+            _ => self.values.iter().map(String::as_str).collect(),
+        };
+        for suggestion in &self.suggestions {
+            if !candidates.contains(&suggestion.as_str()) {
+                candidates.push(suggestion);
+            }
+        }
+        candidates
+    }
+}
+
+/// A message declared by a template.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MessageTemplate {
+    /// Speaker of the message
+    pub role: Role,
+
+    /// Text of the message, with `{{argument}}` placeholders
+    pub text: String,
+}
+
+/// A prompt template loaded from a file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PromptTemplate {
+    /// Programmatic name, defaulting to the file stem
+    #[serde(default)]
+    pub name: String,
+
+    /// Human-readable display name
+    #[serde(default)]
+    pub title: Option<String>,
+
+    /// Description of what the prompt is for
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Arguments the prompt accepts
+    #[serde(default)]
+    pub arguments: Vec<ArgumentSpec>,
+
+    /// Messages the prompt renders to
+    pub messages: Vec<MessageTemplate>,
+}
+
+impl PromptTemplate {
+    /// Parses a template, naming it `default_name` unless it sets a name.
+    pub fn parse(default_name: &str, source: &str) -> Result<Self, PromptError> {
+        let invalid = |reason: String| PromptError::InvalidTemplate {
+            source_name: default_name.to_string(),
+            reason,
+        };
+
+        let mut template: Self = toml::from_str(source).map_err(|e| invalid(e.to_string()))?;
+        if template.name.is_empty() {
+            template.name = default_name.to_string();
+        }
+        template.validate().map_err(invalid)?;
+        Ok(template)
+    }
+
+    /// Loads a template from a file, named after the file stem.
+    pub fn load(path: &Path) -> Result<Self, PromptError> {
+        let name = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+        let source = std::fs::read_to_string(path).map_err(|e| PromptError::InvalidTemplate {
+            source_name: path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+        Self::parse(name, &source)
+    }
+
+    /// Checks that the template is consistent.
+    fn validate(&self) -> Result<(), String> {
+        if self.messages.is_empty() {
+            return Err("a prompt needs at least one message".to_string());
+        }
+        for (index, argument) in self.arguments.iter().enumerate() {
+            if self.arguments[..index].iter().any(|other| other.name == argument.name) {
+                return Err(format!("argument {} is declared twice", argument.name));
+            }
+            if argument.kind == ArgumentType::Enum && argument.values.is_empty() {
+                return Err(format!("enum argument {} has no values", argument.name));
+            }
+            if let Some(default) = &argument.default {
+                argument.check(default).map_err(|e| format!("default of {e}"))?;
+            }
+        }
+        for message in &self.messages {
+            for placeholder in placeholders(&message.text) {
+                if self.argument(placeholder).is_none() {
+                    return Err(format!("message refers to undeclared argument {placeholder}"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the declared argument called `name`.
+    pub fn argument(&self, name: &str) -> Option<&ArgumentSpec> {
+        self.arguments.iter().find(|argument| argument.name == name)
+    }
+
+    /// Returns the metadata advertised by `prompts/list`.
+    pub fn prompt(&self) -> Prompt {
+        Prompt {
+            name: self.name.clone(),
+            title: self.title.clone(),
+            description: self.description.clone(),
+            arguments: self
+                .arguments
+                .iter()
+                .map(|argument| PromptArgument {
+                    name: argument.name.clone(),
+                    title: argument.title.clone(),
+                    description: argument.description.clone(),
+                    required: argument.required,
+                })
+                .collect(),
+        }
+    }
+
+    /// Renders the template with the given argument values.
+    ///
+    /// Fails if an argument is unknown, missing, or not of its declared type.
+    pub fn render(&self, arguments: &HashMap<String, String>) -> Result<GetPromptResult, PromptError> {
+        if let Some(unknown) = arguments.keys().find(|name| self.argument(name).is_none()) {
+            return Err(PromptError::UnknownArgument(unknown.clone()));
+        }
+
+        let mut values = HashMap::new();
+        for argument in &self.arguments {
+            let value = match arguments.get(&argument.name) {
+                Some(value) => {
+                    argument.check(value)?;
+                    value.as_str()
+                }
+                None if argument.required => return Err(PromptError::MissingArgument(argument.name.clone())),
+                None => argument.default.as_deref().unwrap_or_default(),
+            };
+            values.insert(argument.name.as_str(), value);
+        }
+
+        let messages = self
+            .messages
+            .iter()
+            .map(|message| PromptMessage {
+                role: message.role,
+                content: ToolContent::text(substitute(&message.text, &values)),
+            })
+            .collect();
+        Ok(GetPromptResult {
+            description: self.description.clone(),
+            messages,
+        })
+    }
+
+    /// Suggests values for `argument` starting with `value`.
+    ///
+    /// Returns `None` if the template declares no such argument.
+    pub fn complete(&self, argument: &str, value: &str) -> Option<Completion> {
+        let argument = self.argument(argument)?;
+        Some(Completion::matching(argument.candidates(), value))
+    }
+}
+
+/// Returns the names of the `{{name}}` placeholders in `text`.
+fn placeholders(text: &str) -> impl Iterator<Item = &str> {
+    text.split("{{").skip(1).filter_map(|rest| rest.split_once("}}")).map(|(name, _)| name.trim())
+}
+
+/// Replaces the `{{name}}` placeholders in `text` with their values.
+fn substitute(text: &str, values: &HashMap<&str, &str>) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+        let name = rest[start + 2..start + end].trim();
+        output.push_str(values.get(name).copied().unwrap_or_default());
+        rest = &rest[start + end + 2..];
+    }
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUMMARIZE: &str = r#"
+        title = "Summarize a page"
+
+        [[arguments]]
+        name = "url"
+        type = "url"
+        required = true
+
+        [[arguments]]
+        name = "length"
+        type = "enum"
+        values = ["short", "medium", "long"]
+        default = "short"
+
+        [[messages]]
+        role = "user"
+        text = "Fetch {{url}} and write a {{ length }} summary of it."
+    "#;
+
+    fn arguments(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_render_with_defaults() {
+        let template = PromptTemplate::parse("summarize-page", SUMMARIZE).unwrap();
+        assert_eq!(template.prompt().name, "summarize-page");
+        assert!(template.prompt().arguments[0].required);
+
+        let result = template.render(&arguments(&[("url", "https://example.com")])).unwrap();
+        assert_eq!(
+            result.messages[0].content,
+            ToolContent::text("Fetch https://example.com and write a short summary of it.")
+        );
+    }
+
+    #[test]
+    fn test_render_checks_arguments() {
+        let template = PromptTemplate::parse("summarize-page", SUMMARIZE).unwrap();
+        let cases = [
+            (arguments(&[]), "Missing required argument: url"),
+            (arguments(&[("url", "ftp://example.com")]), "unsupported URL scheme ftp"),
+            (arguments(&[("url", "https://example.com"), ("length", "huge")]), "expected one of"),
+            (arguments(&[("url", "https://example.com"), ("tone", "dry")]), "Unknown argument: tone"),
+        ];
+        for (arguments, expected) in cases {
+            let error = template.render(&arguments).unwrap_err().to_string();
+            assert!(error.contains(expected), "{error}");
+        }
+    }
+
+    #[test]
+    fn test_complete() {
+        let template = PromptTemplate::parse("summarize-page", SUMMARIZE).unwrap();
+        assert_eq!(template.complete("length", "m").unwrap().values, vec!["medium"]);
+        assert!(template.complete("url", "").unwrap().values.is_empty());
+        assert!(template.complete("tone", "").is_none());
+    }
+
+    #[test]
+    fn test_rejects_inconsistent_templates() {
+        let cases = [
+            "messages = []",
+            "[[messages]]\nrole = \"user\"\ntext = \"{{missing}}\"",
+            "[[arguments]]\nname = \"a\"\ntype = \"enum\"\n[[messages]]\nrole = \"user\"\ntext = \"x\"",
+            "[[arguments]]\nname = \"n\"\ntype = \"integer\"\ndefault = \"x\"\n[[messages]]\nrole = \"user\"\ntext = \"x\"",
+        ];
+        for source in cases {
+            assert!(
+                matches!(PromptTemplate::parse("bad", source), Err(PromptError::InvalidTemplate { .. })),
+                "{source}"
+            );
+        }
+    }
+}
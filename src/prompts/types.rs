@@ -0,0 +1,168 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! MCP prompt wire types.
+//!
+//! These types mirror the shapes defined by the MCP specification for
+//! `prompts/list`, `prompts/get` and `completion/complete`, and serialize with
+//! the spec's camelCase field names.
+
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::tools::ToolContent;
+
+/// Most completion values returned in one response, as set by the MCP spec.
+pub const MAX_COMPLETION_VALUES: usize = 100;
+
+/// Metadata describing a prompt, as advertised by `prompts/list`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Prompt {
+    /// Programmatic name
+    pub name: String,
+
+    /// Human-readable display name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Description of what the prompt is for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Arguments the prompt accepts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<PromptArgument>,
+}
+
+/// An argument accepted by a prompt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptArgument {
+    /// Programmatic name
+    pub name: String,
+
+    /// Human-readable display name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Description of the argument
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Whether the argument must be supplied
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Speaker of a prompt message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// The user
+    User,
+
+    /// The assistant
+    Assistant,
+}
+
+/// One message of a rendered prompt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PromptMessage {
+    /// Speaker of the message
+    pub role: Role,
+
+    /// Content of the message
+    pub content: ToolContent,
+}
+
+/// Result of a `prompts/get` request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct GetPromptResult {
+    /// Description of the rendered prompt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Messages of the rendered prompt
+    pub messages: Vec<PromptMessage>,
+}
+
+/// Parameters of a `prompts/get` request.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetPromptParams {
+    /// Name of the prompt
+    pub name: String,
+
+    /// Argument values, keyed by argument name
+    #[serde(default)]
+    pub arguments: HashMap<String, String>,
+}
+
+/// Suggested values for an argument, as returned by `completion/complete`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Completion {
+    /// Suggested values, at most [`MAX_COMPLETION_VALUES`]
+    pub values: Vec<String>,
+
+    /// Number of matching values, including those not returned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<usize>,
+
+    /// Whether more values match than were returned
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+impl Completion {
+    /// Builds a completion from every candidate starting with `prefix`,
+    /// ignoring case.
+    pub fn matching<'a>(candidates: impl IntoIterator<Item = &'a str>, prefix: &str) -> Self {
+        let prefix = prefix.to_lowercase();
+        let matches: Vec<&str> = candidates
+            .into_iter()
+            .filter(|candidate| candidate.to_lowercase().starts_with(&prefix))
+            .collect();
+        Self {
+            values: matches.iter().take(MAX_COMPLETION_VALUES).map(|value| value.to_string()).collect(),
+            total: Some(matches.len()),
+            has_more: matches.len() > MAX_COMPLETION_VALUES,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_prompt_message_shape() {
+        let message = PromptMessage {
+            role: Role::User,
+            content: ToolContent::text("hello"),
+        };
+        assert_eq!(
+            serde_json::to_value(message).unwrap(),
+            json!({"role": "user", "content": {"type": "text", "text": "hello"}})
+        );
+    }
+
+    #[test]
+    fn test_completion_matching() {
+        let completion = Completion::matching(["Short", "shorter", "long"], "sh");
+        assert_eq!(completion.values, vec!["Short", "shorter"]);
+        assert_eq!(completion.total, Some(2));
+        assert!(!completion.has_more);
+
+        let many: Vec<String> = (0..150).map(|i| format!("v{i}")).collect();
+        let completion = Completion::matching(many.iter().map(String::as_str), "");
+        assert_eq!(completion.values.len(), MAX_COMPLETION_VALUES);
+        assert!(completion.has_more);
+    }
+}
@@ -0,0 +1,218 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Builder assembling a [`JsonRpcHandler`].
+//!
+//! Registration is plain synchronous code with no runtime involved, so a
+//! handler can be put together anywhere: in `main` before Tokio starts, on a
+//! current-thread runtime, or in a CLI tool. [`JsonRpcHandlerBuilder::build`]
+//! freezes the methods into a [`MethodTable`] that the handler reads without
+//! locking.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use super::dispatch::DispatchLimits;
+use super::error::JsonRpcError;
+use super::handler::{ContextProvider, JsonRpcHandler, MethodContext, MethodResult, NotificationResult};
+use super::middleware::Middleware;
+use super::table::MethodTable;
+
+/// Collects the methods, middlewares and settings of a [`JsonRpcHandler`].
+#[derive(Default)]
+pub struct JsonRpcHandlerBuilder {
+    /// Methods registered so far
+    methods: MethodTable,
+
+    /// Optional global context provider
+    context_provider: Option<ContextProvider>,
+
+    /// Whether calls on a session are gated by its MCP lifecycle phase
+    enforce_lifecycle: bool,
+
+    /// Middlewares every call passes through, outermost first
+    middlewares: Vec<Arc<dyn Middleware>>,
+
+    /// Concurrency caps and timeouts of method calls
+    limits: DispatchLimits,
+}
+
+impl JsonRpcHandlerBuilder {
+    /// Creates a builder with no methods.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a method handler function.
+    pub fn register_method<F, Fut>(&mut self, method: impl Into<String>, handler: F) -> &mut Self
+    where
+        F: Send + Sync + 'static + Fn(Option<Value>, MethodContext) -> Fut,
+        Fut: Future<Output = MethodResult> + Send + 'static,
+    {
+        self.methods.register_method(method, handler);
+        self
+    }
+
+    /// Registers a method whose params and result are Rust types.
+    ///
+    /// See [`MethodTable::register_typed`].
+    pub fn register_typed<P, R, F, Fut>(&mut self, method: impl Into<String>, handler: F) -> &mut Self
+    where
+        P: DeserializeOwned + JsonSchema + Send + 'static,
+        R: Serialize + JsonSchema + 'static,
+        F: Send + Sync + 'static + Fn(P, MethodContext) -> Fut,
+        Fut: Future<Output = std::result::Result<R, JsonRpcError>> + Send + 'static,
+    {
+        self.methods.register_typed(method, handler);
+        self
+    }
+
+    /// Registers a notification handler function.
+    ///
+    /// See [`MethodTable::register_notification`].
+    pub fn register_notification<F, Fut>(&mut self, method: impl Into<String>, handler: F) -> &mut Self
+    where
+        F: Send + Sync + 'static + Fn(Option<Value>, MethodContext) -> Fut,
+        Fut: Future<Output = NotificationResult> + Send + 'static,
+    {
+        self.methods.register_notification(method, handler);
+        self
+    }
+
+    /// Registers a notification handler whose params are a Rust type.
+    ///
+    /// See [`MethodTable::register_typed_notification`].
+    pub fn register_typed_notification<P, F, Fut>(&mut self, method: impl Into<String>, handler: F) -> &mut Self
+    where
+        P: DeserializeOwned + Send + 'static,
+        F: Send + Sync + 'static + Fn(P, MethodContext) -> Fut,
+        Fut: Future<Output = NotificationResult> + Send + 'static,
+    {
+        self.methods.register_typed_notification(method, handler);
+        self
+    }
+
+    /// Adds a middleware wrapping the dispatch of every call.
+    ///
+    /// Middlewares run in the order they are added, so the first one added
+    /// sees each call first and its result last. See [`middleware`](super::middleware).
+    pub fn add_middleware(&mut self, middleware: impl Middleware + 'static) -> &mut Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// Gates calls arriving with a session on the session's MCP lifecycle phase.
+    ///
+    /// Once enabled, only `initialize` and `ping` are served before the session
+    /// is initialized, and nothing new is served after `shutdown`. Calls without
+    /// a session are never gated. See [`lifecycle`](crate::protocol::lifecycle)
+    /// for the rules.
+    pub fn enforce_lifecycle(&mut self) -> &mut Self {
+        self.enforce_lifecycle = true;
+        self
+    }
+
+    /// Registers a context provider function that is called for each request
+    /// arriving without a context.
+    pub fn register_context_provider<F>(&mut self, provider: F) -> &mut Self
+    where
+        F: Fn() -> MethodContext + Send + Sync + 'static,
+    {
+        self.context_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Sets the concurrency caps and timeouts calls are dispatched with.
+    ///
+    /// By default calls are neither capped nor timed out.
+    pub fn set_dispatch_limits(&mut self, limits: DispatchLimits) -> &mut Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Returns the methods registered so far.
+    pub fn methods(&self) -> &MethodTable {
+        &self.methods
+    }
+
+    /// Freezes the registrations into a handler.
+    pub fn build(self) -> JsonRpcHandler {
+        JsonRpcHandler::from_parts(
+            self.methods,
+            self.context_provider,
+            self.enforce_lifecycle,
+            self.middlewares.into(),
+            self.limits,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn echo_handler() -> JsonRpcHandler {
+        let mut builder = JsonRpcHandler::builder();
+        builder
+            .register_method("echo", |params, _ctx| async move { Ok(params.unwrap_or(Value::Null)) })
+            .register_context_provider(|| MethodContext::default().with_metadata("from", "provider"))
+            .register_method("from", |_params, ctx: MethodContext| async move {
+                Ok(json!(ctx.metadata.get("from")))
+            });
+        builder.build()
+    }
+
+    #[test]
+    fn test_builds_outside_a_runtime() {
+        let handler = echo_handler();
+        assert!(handler.methods().contains("echo"));
+
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let output = runtime.block_on(handler.handle_request(
+            r#"{"jsonrpc":"2.0","method":"echo","params":[1],"id":1}"#,
+            None,
+        ));
+        assert_eq!(output, r#"{"jsonrpc":"2.0","result":[1],"id":1}"#);
+    }
+
+    #[tokio::test]
+    async fn test_runs_on_current_thread_runtime() {
+        let mut handler = echo_handler();
+        let output = handler.handle_request(r#"{"jsonrpc":"2.0","method":"from","id":1}"#, None).await;
+        assert!(output.contains(r#""result":"provider""#));
+
+        handler.register_method("late", |_params, _ctx| async { Ok(json!("late")) });
+        let output = handler.handle_request(r#"{"jsonrpc":"2.0","method":"late","id":2}"#, None).await;
+        assert!(output.contains(r#""result":"late""#));
+    }
+
+    #[tokio::test]
+    async fn test_swapping_methods_is_seen_by_clones() {
+        let handler = echo_handler();
+        let clone = handler.clone();
+        let before = handler.methods();
+
+        handler.update_methods(|methods| {
+            methods.remove("echo");
+        });
+        assert!(before.contains("echo"));
+        assert!(!clone.methods().contains("echo"));
+        let output = clone.handle_request(r#"{"jsonrpc":"2.0","method":"echo","id":1}"#, None).await;
+        assert!(output.contains("-32601"));
+
+        let mut table = MethodTable::new();
+        table.register_method("echo", |_params, _ctx| async { Ok(json!("swapped")) });
+        handler.replace_methods(table);
+        let output = clone.handle_request(r#"{"jsonrpc":"2.0","method":"echo","id":2}"#, None).await;
+        assert!(output.contains(r#""result":"swapped""#));
+    }
+}
@@ -0,0 +1,556 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Limits enforced when a call reaches its method.
+//!
+//! [`DispatchLimits`] bounds how many calls run at once, across all methods
+//! and per method, and how long a call may take. It also bounds how many
+//! calls a batch may hold and how many of them run at once; a batch over the
+//! size limit is rejected whole before any of it runs. A call first waits for a
+//! permit and then runs; its timeout covers both, so a client is answered
+//! even when the server is saturated. How long the call spent in each phase
+//! is left in its context's [`CallTimings`] for middlewares to report.
+//! Notification handlers and the [`CONTROL_METHODS`] never wait for a permit and only
+//! time out when given a timeout of their own, so a client can still cancel
+//! a call or shut down a saturated server.
+//!
+//! A request reusing the id of one still in flight on its session is answered
+//! with `-32003` and never runs. With a [`ReplayWindow`], so is a request
+//! reusing the id of one completed within the window.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::json;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use super::error::{ErrorCode, JsonRpcError};
+pub use crate::config::server::BatchMode;
+use crate::config::server::ServerConfig;
+use crate::error::protocol::ProtocolError;
+use crate::protocol::jsonrpc::types::Id;
+use crate::protocol::lifecycle::{INITIALIZE, PING, SHUTDOWN};
+pub use crate::protocol::session::ReplayWindow;
+
+/// Methods that manage the session rather than do work for the client.
+pub const CONTROL_METHODS: [&str; 3] = [INITIALIZE, PING, SHUTDOWN];
+
+/// Concurrency caps and timeouts of method calls.
+#[derive(Debug, Clone, Default)]
+pub struct DispatchLimits {
+    /// Timeout of methods without their own
+    default_timeout: Option<Duration>,
+
+    /// Timeouts by method name
+    method_timeouts: HashMap<String, Duration>,
+
+    /// Permits shared by all calls
+    global: Option<Arc<Semaphore>>,
+
+    /// Permits of each capped method
+    methods: HashMap<String, Arc<Semaphore>>,
+
+    /// Most calls a batch may hold
+    max_batch_size: Option<usize>,
+
+    /// Most calls of a batch running at once
+    batch_parallelism: Option<usize>,
+
+    /// Order in which the calls of a batch are run
+    batch_mode: BatchMode,
+
+    /// How long request ids stay taken on their session after completing
+    replay_window: Option<ReplayWindow>,
+}
+
+/// Permits held by a running call.
+#[derive(Debug)]
+pub struct DispatchPermits {
+    _global: Option<OwnedSemaphorePermit>,
+    _method: Option<OwnedSemaphorePermit>,
+}
+
+impl DispatchLimits {
+    /// Creates limits that let every call run at once, for as long as it takes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the limits `config` asks for.
+    pub fn from_config(config: &ServerConfig) -> Self {
+        let mut limits = Self::new()
+            .with_default_timeout(Duration::from_millis(config.default_timeout_ms))
+            .with_max_concurrent(config.max_concurrent_requests)
+            .with_max_batch_size(config.max_batch_size)
+            .with_batch_parallelism(config.max_batch_parallelism, config.batch_mode);
+        if config.replay_window_ms > 0 {
+            let duration = Duration::from_millis(config.replay_window_ms);
+            limits = limits.with_replay_window(ReplayWindow::new(duration, config.replay_window_ids));
+        }
+        for (method, method_limits) in &config.methods {
+            if let Some(timeout_ms) = method_limits.timeout_ms {
+                limits = limits.with_method_timeout(method.clone(), Duration::from_millis(timeout_ms));
+            }
+            if let Some(max_concurrent) = method_limits.max_concurrent {
+                limits = limits.with_method_concurrency(method.clone(), max_concurrent);
+            }
+        }
+        limits
+    }
+
+    /// Sets the timeout of methods without their own.
+    pub fn with_default_timeout(mut self, timeout: Duration) -> Self {
+        self.default_timeout = Some(timeout);
+        self
+    }
+
+    /// Caps the number of calls running at once across all methods.
+    pub fn with_max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.global = Some(Arc::new(Semaphore::new(max_concurrent)));
+        self
+    }
+
+    /// Sets the timeout of one method.
+    pub fn with_method_timeout(mut self, method: impl Into<String>, timeout: Duration) -> Self {
+        self.method_timeouts.insert(method.into(), timeout);
+        self
+    }
+
+    /// Caps the number of calls of one method running at once.
+    pub fn with_method_concurrency(mut self, method: impl Into<String>, max_concurrent: usize) -> Self {
+        self.methods.insert(method.into(), Arc::new(Semaphore::new(max_concurrent)));
+        self
+    }
+
+    /// Caps the number of calls a batch may hold.
+    pub fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = Some(max_batch_size);
+        self
+    }
+
+    /// Caps the number of calls of a batch running at once, started in `mode`.
+    pub fn with_batch_parallelism(mut self, parallelism: usize, mode: BatchMode) -> Self {
+        self.batch_parallelism = Some(parallelism.max(1));
+        self.batch_mode = mode;
+        self
+    }
+
+    /// Keeps the ids of completed requests taken on their session for `window`.
+    pub fn with_replay_window(mut self, window: ReplayWindow) -> Self {
+        self.replay_window = Some(window);
+        self
+    }
+
+    /// Returns the most calls a batch may hold, if it is bounded.
+    pub fn max_batch_size(&self) -> Option<usize> {
+        self.max_batch_size
+    }
+
+    /// Returns how many calls of a batch of `size` may run at once.
+    pub fn batch_parallelism(&self, size: usize) -> usize {
+        self.batch_parallelism.unwrap_or(size).max(1)
+    }
+
+    /// Returns the order in which the calls of a batch are run.
+    pub fn batch_mode(&self) -> BatchMode {
+        self.batch_mode
+    }
+
+    /// Returns how long request ids stay taken after completing, if they do.
+    pub fn replay_window(&self) -> Option<&ReplayWindow> {
+        self.replay_window.as_ref()
+    }
+
+    /// Returns how long a call of `method` may take, if it is bounded.
+    pub fn timeout(&self, method: &str) -> Option<Duration> {
+        self.method_timeouts.get(method).copied().or(self.default_timeout)
+    }
+
+    /// Returns the timeout set for `method` by name, if any.
+    pub fn method_timeout(&self, method: &str) -> Option<Duration> {
+        self.method_timeouts.get(method).copied()
+    }
+
+    /// Returns whether a call of `method` skips the permits and the default timeout.
+    ///
+    /// True for the [`CONTROL_METHODS`], and for calls served by a handler
+    /// registered as a notification handler, as `notification` tells. A
+    /// request method is never exempt, however it is called.
+    pub fn is_exempt(method: &str, notification: bool) -> bool {
+        notification || CONTROL_METHODS.contains(&method)
+    }
+
+    /// Waits until a call of `method` may run.
+    ///
+    /// The method's own permit is taken first, so calls of a saturated method
+    /// do not hold global permits other methods could use.
+    pub async fn acquire(&self, method: &str) -> DispatchPermits {
+        let method = match self.methods.get(method) {
+            Some(semaphore) => Some(acquire(semaphore).await),
+            None => None,
+        };
+        let global = match &self.global {
+            Some(semaphore) => Some(acquire(semaphore).await),
+            None => None,
+        };
+        DispatchPermits {
+            _global: global,
+            _method: method,
+        }
+    }
+}
+
+async fn acquire(semaphore: &Arc<Semaphore>) -> OwnedSemaphorePermit {
+    semaphore
+        .clone()
+        .acquire_owned()
+        .await
+        .expect("dispatch semaphores are never closed")
+}
+
+/// Time a call spent waiting for a permit and running.
+///
+/// Clones share the same timings. Each dispatched call starts with fresh ones.
+#[derive(Debug, Clone, Default)]
+pub struct CallTimings {
+    inner: Arc<CallTimingsInner>,
+}
+
+#[derive(Debug, Default)]
+struct CallTimingsInner {
+    queued_us: AtomicU64,
+    executing_us: AtomicU64,
+}
+
+impl CallTimings {
+    /// Returns how long the call waited for a permit.
+    pub fn queued(&self) -> Duration {
+        Duration::from_micros(self.inner.queued_us.load(Ordering::Relaxed))
+    }
+
+    /// Returns how long the method ran.
+    pub fn executing(&self) -> Duration {
+        Duration::from_micros(self.inner.executing_us.load(Ordering::Relaxed))
+    }
+
+    pub(super) fn record_queued(&self, queued: Duration) {
+        self.inner.queued_us.store(micros(queued), Ordering::Relaxed);
+    }
+
+    pub(super) fn record_executing(&self, executing: Duration) {
+        self.inner.executing_us.store(micros(executing), Ordering::Relaxed);
+    }
+}
+
+fn micros(duration: Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
+
+/// Error answering a call that ran out of time.
+pub fn timeout_error(method: &str, timeout: Duration, queued: bool) -> JsonRpcError {
+    let phase = if queued { "waiting to run" } else { "running" };
+    JsonRpcError::with_data(
+        ErrorCode::RequestTimeout,
+        format!("Request timed out after {}ms while {phase}: {method}", timeout.as_millis()),
+        json!({ "timeoutMs": micros(timeout) / 1000, "queued": queued }),
+    )
+}
+
+/// Error answering a batch holding more calls than allowed.
+pub fn batch_too_large_error(size: usize, max_batch_size: usize) -> JsonRpcError {
+    JsonRpcError::with_data(
+        ErrorCode::InvalidRequest,
+        format!("Invalid Request: batch of {size} calls exceeds the limit of {max_batch_size}"),
+        json!({ "batchSize": size, "maxBatchSize": max_batch_size }),
+    )
+}
+
+/// Error answering a request whose id its session refused.
+pub fn duplicate_id_error(id: &Id, error: ProtocolError) -> JsonRpcError {
+    JsonRpcError::with_data(ErrorCode::DuplicateRequestId, error.to_string(), json!({ "id": id }))
+}
+
+/// Error answering a call whose method panicked.
+///
+/// The correlation id is logged along with the panic message, which is not
+/// sent to the client.
+pub fn panic_error(method: &str, panic: &(dyn std::any::Any + Send)) -> JsonRpcError {
+    let correlation_id = uuid::Uuid::new_v4().to_string();
+    let message = panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic");
+    tracing::error!(method, correlation_id = %correlation_id, panic = message, "Method panicked");
+    JsonRpcError::with_data(
+        ErrorCode::InternalError,
+        format!("Internal error: {method} failed unexpectedly (correlation id {correlation_id})"),
+        json!({ "correlationId": correlation_id }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::jsonrpc::handler::MethodContext;
+    use crate::protocol::jsonrpc::methods::{register_cancelled_notification, register_ping_method};
+    use crate::protocol::jsonrpc::{Id, JsonRpcHandler, Response};
+    use crate::protocol::Session;
+
+    fn limited_handler(limits: DispatchLimits) -> JsonRpcHandler {
+        let mut builder = JsonRpcHandler::builder();
+        builder
+            .register_method("panic", |_params, _ctx| async { panic!("secret detail") })
+            .register_method("sleep", |_params, ctx: MethodContext| async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok(json!(ctx.timings.queued().as_millis() as u64))
+            })
+            .register_method("hang", |_params, _ctx| std::future::pending())
+            .register_notification("sleep", |_params, _ctx| async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok(())
+            })
+            .set_dispatch_limits(limits);
+        register_ping_method(&mut builder);
+        register_cancelled_notification(&mut builder);
+        builder.build()
+    }
+
+    async fn call(handler: &JsonRpcHandler, method: &str) -> Response {
+        let request = json!({"jsonrpc": "2.0", "method": method, "id": 1}).to_string();
+        serde_json::from_str(&handler.handle_request(request, None).await).unwrap()
+    }
+
+    #[test]
+    fn test_method_timeout_overrides_default() {
+        let limits = DispatchLimits::new()
+            .with_default_timeout(Duration::from_secs(30))
+            .with_method_timeout("tools/call", Duration::from_secs(120));
+        assert_eq!(limits.timeout("tools/call"), Some(Duration::from_secs(120)));
+        assert_eq!(limits.timeout("ping"), Some(Duration::from_secs(30)));
+        assert_eq!(DispatchLimits::new().timeout("ping"), None);
+        assert_eq!(limits.method_timeout("ping"), None);
+    }
+
+    #[tokio::test]
+    async fn test_method_cap_is_independent_of_other_methods() {
+        let limits = DispatchLimits::new()
+            .with_max_concurrent(2)
+            .with_method_concurrency("slow", 1);
+
+        let _slow = limits.acquire("slow").await;
+        let blocked = tokio::time::timeout(Duration::from_millis(20), limits.acquire("slow")).await;
+        assert!(blocked.is_err());
+
+        let _other = limits.acquire("fast").await;
+        let blocked = tokio::time::timeout(Duration::from_millis(20), limits.acquire("fast")).await;
+        assert!(blocked.is_err());
+    }
+
+    #[test]
+    fn test_panic_error_hides_message() {
+        let error = panic_error("boom", &"secret detail");
+        assert_eq!(error.code, ErrorCode::InternalError.code());
+        assert!(!error.message.contains("secret detail"));
+        let correlation_id = error.data.unwrap()["correlationId"].as_str().unwrap().to_string();
+        assert!(error.message.contains(&correlation_id));
+    }
+
+    #[tokio::test]
+    async fn test_panicking_method_is_answered() {
+        let handler = limited_handler(DispatchLimits::new());
+        let error = call(&handler, "panic").await.error.unwrap();
+        assert_eq!(error.code, ErrorCode::InternalError.code());
+        assert!(error.data.unwrap()["correlationId"].is_string());
+
+        // The handler keeps serving
+        assert!(call(&handler, "sleep").await.result.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_timeout_while_running_and_while_queued() {
+        let handler = limited_handler(DispatchLimits::new().with_method_timeout("sleep", Duration::from_millis(10)));
+        let error = call(&handler, "sleep").await.error.unwrap();
+        assert_eq!(error.code, ErrorCode::RequestTimeout.code());
+        assert_eq!(error.data.unwrap()["queued"], false);
+
+        let handler = limited_handler(
+            DispatchLimits::new()
+                .with_default_timeout(Duration::from_millis(30))
+                .with_method_timeout("sleep", Duration::from_secs(5))
+                .with_max_concurrent(1),
+        );
+        let (slow, queued) = tokio::join!(call(&handler, "sleep"), async {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            call(&handler, "panic").await
+        });
+        assert!(slow.result.is_some());
+        let error = queued.error.unwrap();
+        assert_eq!(error.code, ErrorCode::RequestTimeout.code());
+        assert_eq!(error.data.unwrap()["queued"], true);
+    }
+
+    #[tokio::test]
+    async fn test_queued_time_is_reported() {
+        let handler = limited_handler(DispatchLimits::new().with_method_concurrency("sleep", 1));
+        let (first, second) = tokio::join!(call(&handler, "sleep"), call(&handler, "sleep"));
+        let mut queued: Vec<_> = [first, second]
+            .into_iter()
+            .map(|response| response.result.and_then(|value| value.as_u64()).unwrap())
+            .collect();
+        queued.sort_unstable();
+        assert!(queued[0] < 10, "{queued:?}");
+        assert!(queued[1] >= 40, "{queued:?}");
+    }
+
+    #[tokio::test]
+    async fn test_oversized_batch_is_rejected_whole() {
+        let handler = limited_handler(DispatchLimits::new().with_max_batch_size(2));
+        let batch = json!([
+            {"jsonrpc": "2.0", "method": "sleep", "id": 1},
+            {"jsonrpc": "2.0", "method": "sleep", "id": 2},
+            {"jsonrpc": "2.0", "method": "panic", "id": 3},
+        ]);
+        let output = handler.handle_request(batch.to_string(), None).await;
+        let response: Response = serde_json::from_str(&output).unwrap();
+        let error = response.error.unwrap();
+        assert_eq!(error.code, ErrorCode::InvalidRequest.code());
+        assert_eq!(error.data, Some(json!({"batchSize": 3, "maxBatchSize": 2})));
+    }
+
+    #[tokio::test]
+    async fn test_batch_parallelism_and_order() {
+        let batch = json!([
+            {"jsonrpc": "2.0", "method": "sleep", "id": 1},
+            {"jsonrpc": "2.0", "method": "sleep"},
+            {"jsonrpc": "2.0", "method": "sleep", "id": 3},
+        ])
+        .to_string();
+
+        // One at a time: the notification counts against the budget too
+        let handler = limited_handler(DispatchLimits::new().with_batch_parallelism(1, BatchMode::Ordered));
+        let started = std::time::Instant::now();
+        let output = handler.handle_request(&batch, None).await;
+        assert!(started.elapsed() >= Duration::from_millis(150));
+        let responses: Vec<Response> = serde_json::from_str(&output).unwrap();
+        let ids: Vec<_> = responses.into_iter().map(|response| response.id).collect();
+        assert_eq!(ids, [Id::Number(1), Id::Number(3)]);
+
+        let handler = limited_handler(DispatchLimits::new().with_batch_parallelism(3, BatchMode::Unordered));
+        let started = std::time::Instant::now();
+        let output = handler.handle_request(&batch, None).await;
+        assert!(started.elapsed() < Duration::from_millis(140));
+        assert_eq!(serde_json::from_str::<Vec<Response>>(&output).unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_request_ids_are_refused() {
+        let session = Arc::new(crate::protocol::Session::new("test"));
+        let context = MethodContext::for_session(session.clone());
+        let request = json!({"jsonrpc": "2.0", "method": "sleep", "id": "a"}).to_string();
+        let call = |handler: &JsonRpcHandler| {
+            let handler = handler.clone();
+            let (request, context) = (request.clone(), context.clone());
+            async move {
+                let output = handler.handle_request(request, Some(context)).await;
+                serde_json::from_str::<Response>(&output).unwrap()
+            }
+        };
+
+        let handler = limited_handler(DispatchLimits::new());
+        let (first, second) = tokio::join!(call(&handler), async {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            call(&handler).await
+        });
+        assert!(first.result.is_some());
+        let error = second.error.unwrap();
+        assert_eq!(error.code, ErrorCode::DuplicateRequestId.code());
+        assert_eq!(error.data, Some(json!({"id": "a"})));
+        assert_eq!(second.id, Id::String("a".to_string()));
+        assert!(call(&handler).await.result.is_some());
+
+        let window = ReplayWindow::new(Duration::from_secs(60), 10);
+        let handler = limited_handler(DispatchLimits::new().with_replay_window(window));
+        let session = Arc::new(crate::protocol::Session::new("test"));
+        let context = MethodContext::for_session(session.clone());
+        let first = handler.handle_request(request.clone(), Some(context.clone())).await;
+        assert!(first.contains("result"));
+        let replayed = handler.handle_request(request.clone(), Some(context)).await;
+        assert!(replayed.contains("-32003"), "{replayed}");
+        assert_eq!(session.replay_window(), Some(window));
+        assert_eq!(session.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_gets_through_saturated_server() {
+        let handler = limited_handler(
+            DispatchLimits::new()
+                .with_default_timeout(Duration::from_millis(200))
+                .with_method_timeout("hang", Duration::from_secs(5))
+                .with_max_concurrent(1),
+        );
+        let session = Arc::new(Session::new("test"));
+        let context = MethodContext::for_session(session.clone());
+        let send = |message: serde_json::Value| {
+            let (handler, context) = (handler.clone(), context.clone());
+            async move { handler.handle_request(message.to_string(), Some(context)).await }
+        };
+
+        let hanging = tokio::spawn(send(json!({"jsonrpc": "2.0", "method": "hang", "id": "a"})));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(session.in_flight(), 1);
+
+        // The only permit is taken, yet control methods and notifications run
+        let pong = send(json!({"jsonrpc": "2.0", "method": "ping", "id": "b"})).await;
+        assert!(pong.contains("result"), "{pong}");
+        let queued = send(json!({"jsonrpc": "2.0", "method": "sleep", "id": "c"})).await;
+        assert!(queued.contains("-32001"), "{queued}");
+
+        let cancel = json!({"jsonrpc": "2.0", "method": "notifications/cancelled", "params": {"requestId": "a"}});
+        assert_eq!(send(cancel).await, "");
+        let output = tokio::time::timeout(Duration::from_secs(1), hanging).await.unwrap().unwrap();
+        assert_eq!(output, "");
+        assert_eq!(session.in_flight(), 0);
+
+        let response = send(json!({"jsonrpc": "2.0", "method": "sleep", "id": "d"})).await;
+        assert!(response.contains("result"), "{response}");
+    }
+
+    #[tokio::test]
+    async fn test_request_methods_without_id_stay_limited() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut builder = JsonRpcHandler::builder();
+        builder
+            .register_method("tools/call", {
+                let calls = calls.clone();
+                move |_params, _ctx| {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    std::future::pending()
+                }
+            })
+            .set_dispatch_limits(
+                DispatchLimits::new()
+                    .with_default_timeout(Duration::from_millis(50))
+                    .with_max_concurrent(1),
+            );
+        let handler = builder.build();
+
+        // A flood of id-less calls never reaches the request method, so none
+        // of them runs without a permit and a deadline
+        let notification = json!({"jsonrpc": "2.0", "method": "tools/call", "params": {"name": "fetch_url"}}).to_string();
+        let flood = (0..20).map(|_| handler.handle_request(&notification, None));
+        let outputs = tokio::time::timeout(Duration::from_secs(1), futures::future::join_all(flood))
+            .await
+            .unwrap();
+        assert!(outputs.iter().all(String::is_empty));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let response = call(&handler, "tools/call").await;
+        assert_eq!(response.error.unwrap().code, ErrorCode::RequestTimeout.code());
+    }
+}
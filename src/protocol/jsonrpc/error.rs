@@ -39,6 +39,18 @@ pub enum ErrorCode {
     /// Internal JSON-RPC error.
     InternalError = -32603,
     
+    /// Resource not found (-32002)
+    /// The requested MCP resource does not exist.
+    ResourceNotFound = -32002,
+    
+    /// Duplicate request id (-32003)
+    /// The request reuses the id of a request still in flight or recently completed.
+    DuplicateRequestId = -32003,
+    
+    /// Request timed out (-32001)
+    /// The request did not complete within its time limit.
+    RequestTimeout = -32001,
+    
     /// Server error (-32000 to -32099)
     /// Reserved for implementation-defined server errors.
     ServerError = -32000,
@@ -61,6 +73,23 @@ pub enum ErrorCode {
 }
 
 impl ErrorCode {
+    /// Every predefined error code.
+    pub const ALL: [ErrorCode; 13] = [
+        ErrorCode::ParseError,
+        ErrorCode::InvalidRequest,
+        ErrorCode::MethodNotFound,
+        ErrorCode::InvalidParams,
+        ErrorCode::InternalError,
+        ErrorCode::ResourceNotFound,
+        ErrorCode::DuplicateRequestId,
+        ErrorCode::RequestTimeout,
+        ErrorCode::ServerError,
+        ErrorCode::ApplicationError,
+        ErrorCode::Unauthorized,
+        ErrorCode::RateLimitExceeded,
+        ErrorCode::RequestCancelled,
+    ];
+    
     /// Returns a string description of the error code.
     pub fn description(&self) -> &'static str {
         match self {
@@ -69,6 +98,9 @@ impl ErrorCode {
             ErrorCode::MethodNotFound => "Method not found",
             ErrorCode::InvalidParams => "Invalid params",
             ErrorCode::InternalError => "Internal error",
+            ErrorCode::ResourceNotFound => "Resource not found",
+            ErrorCode::DuplicateRequestId => "Duplicate request id",
+            ErrorCode::RequestTimeout => "Request timed out",
             ErrorCode::ServerError => "Server error",
             ErrorCode::ApplicationError => "Application error",
             ErrorCode::Unauthorized => "Unauthorized",
@@ -91,6 +123,9 @@ impl ErrorCode {
             -32401 => Some(ErrorCode::Unauthorized),
             -32429 => Some(ErrorCode::RateLimitExceeded),
             -32800 => Some(ErrorCode::RequestCancelled),
+            -32002 => Some(ErrorCode::ResourceNotFound),
+            -32003 => Some(ErrorCode::DuplicateRequestId),
+            -32001 => Some(ErrorCode::RequestTimeout),
             c if (-32099..=-32000).contains(&c) => Some(ErrorCode::ServerError),
             _ => None,
         }
@@ -245,10 +280,17 @@ mod tests {
         assert_eq!(ErrorCode::from_code(-32603), Some(ErrorCode::InternalError));
         
         // Server error range
+        assert_eq!(ErrorCode::from_code(-32002), Some(ErrorCode::ResourceNotFound));
+        assert_eq!(ErrorCode::from_code(-32003), Some(ErrorCode::DuplicateRequestId));
+        assert_eq!(ErrorCode::from_code(-32001), Some(ErrorCode::RequestTimeout));
         assert_eq!(ErrorCode::from_code(-32000), Some(ErrorCode::ServerError));
         assert_eq!(ErrorCode::from_code(-32099), Some(ErrorCode::ServerError));
         assert_eq!(ErrorCode::from_code(-32050), Some(ErrorCode::ServerError));
         
+        for code in ErrorCode::ALL {
+            assert_eq!(ErrorCode::from_code(code.code()), Some(code));
+        }
+        
         // Invalid codes
         assert_eq!(ErrorCode::from_code(0), None);
         assert_eq!(ErrorCode::from_code(-1), None);
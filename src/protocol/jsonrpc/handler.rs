@@ -9,36 +9,112 @@
 //! This module provides the core handler for JSON-RPC 2.0 requests, supporting
 //! method registration, request dispatching, and asynchronous execution.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::future::Future;
+use std::panic::AssertUnwindSafe;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use arc_swap::ArcSwap;
 use async_trait::async_trait;
-use futures::future::{BoxFuture, FutureExt};
+use futures::future::{self, BoxFuture, FutureExt};
+use futures::stream::{self, StreamExt};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::value::RawValue;
 use serde_json::Value;
-use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
 
-use super::error::{Error, ErrorCode, JsonRpcError, Result};
-use super::types::{BatchRequest, BatchResponse, Id, Request, Response};
-use super::validation::{validate_request, ValidatedRequest};
+use super::error::{Error, JsonRpcError, Result};
+use crate::protocol::lifecycle::{self, SHUTDOWN};
+use crate::protocol::peer::ClientPeer;
+use crate::protocol::progress::ProgressReporter;
+use crate::protocol::session::Session;
+use super::builder::JsonRpcHandlerBuilder;
+use super::dispatch::{self, BatchMode, CallTimings, DispatchLimits};
+use super::table::MethodTable;
+use super::typed::MethodSchema;
+use super::types::{BatchResponse, Id, Response};
+use super::message::{parse_bytes, OutgoingMessage, RawMessage, RawRequest};
+use super::middleware::{MethodCall, Middleware, Next};
 
 /// A method handler context containing additional information about the request.
 #[derive(Debug, Clone, Default)]
 pub struct MethodContext {
     /// Optional metadata associated with this request
     pub metadata: HashMap<String, String>,
+    
+    /// Session of the connection the request arrived on, if any
+    pub session: Option<Arc<Session>>,
+    
+    /// Cancelled when the client cancels the request
+    ///
+    /// The handler's future is dropped on cancellation; handlers that start
+    /// detached work can watch this token to stop it as well.
+    pub cancellation: CancellationToken,
+    
+    /// Reports progress to the client, if it asked for progress updates
+    pub progress: Option<ProgressReporter>,
+    
+    /// How long the call waited for a permit and ran, filled in by dispatch
+    pub timings: CallTimings,
+    
+    /// Method table the call was dispatched from, filled in by dispatch
+    pub methods: Option<Arc<MethodTable>>,
+}
+
+impl MethodContext {
+    /// Creates a context for a request arriving on the given session.
+    pub fn for_session(session: Arc<Session>) -> Self {
+        Self {
+            session: Some(session),
+            ..Self::default()
+        }
+    }
+    
+    /// Adds a metadata entry to the context.
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+    
+    /// Returns true once the client has cancelled the request.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+    
+    /// Returns a peer for sending requests to the client, if the request
+    /// arrived on a session.
+    pub fn client(&self) -> Option<ClientPeer> {
+        self.session.clone().map(ClientPeer::new)
+    }
+    
+    /// Reports progress if the client asked for it, returning whether a
+    /// notification was sent. See [`ProgressReporter::report`].
+    pub fn report_progress(&self, progress: f64, total: Option<f64>, message: Option<&str>) -> bool {
+        self.progress
+            .as_ref()
+            .is_some_and(|reporter| reporter.report(progress, total, message))
+    }
 }
 
 /// Type alias for method handler response.
 pub type MethodResult = std::result::Result<Value, JsonRpcError>;
 
+/// Type alias for notification handler response.
+pub type NotificationResult = std::result::Result<(), JsonRpcError>;
+
 /// Type alias for method handler's future return type.
 pub type MethodHandlerFuture = BoxFuture<'static, MethodResult>;
 
 /// Type alias for asynchronous method handler functions.
 pub type MethodHandlerFn = Arc<dyn MethodHandler + Send + Sync>;
 
+/// Function building the context of requests that arrive without one.
+pub type ContextProvider = Arc<dyn Fn() -> MethodContext + Send + Sync>;
+
 /// Trait for method handlers to implement.
 pub trait MethodHandler {
     /// Handle a method call asynchronously.
@@ -50,6 +126,18 @@ pub trait MethodHandler {
     /// # Returns
     /// A boxed future that resolves to a JSON-RPC result.
     fn handle(&self, params: Option<Value>, context: MethodContext) -> MethodHandlerFuture;
+    
+    /// Handle a method call whose params are still JSON text.
+    ///
+    /// The default implementation parses the params into a [`Value`] and
+    /// calls [`handle`](Self::handle); handlers that decode params into their
+    /// own types can do so from the text directly.
+    fn handle_raw(&self, params: Option<&RawValue>, context: MethodContext) -> MethodHandlerFuture {
+        match params.map(|params| serde_json::from_str(params.get())).transpose() {
+            Ok(params) => self.handle(params, context),
+            Err(err) => Box::pin(future::ready(Err(JsonRpcError::invalid_params(err.to_string())))),
+        }
+    }
 }
 
 // Implement MethodHandler for async functions
@@ -66,52 +154,126 @@ where
 /// Handler for JSON-RPC 2.0 requests.
 ///
 /// This struct is responsible for:
-/// - Registering method handlers
 /// - Validating incoming requests
 /// - Dispatching requests to appropriate handlers
 /// - Collecting and formatting responses
 ///
-/// The handler is thread-safe and supports asynchronous method execution.
+/// Handlers are put together with a [`JsonRpcHandlerBuilder`]. Methods are
+/// looked up in an immutable [`MethodTable`] without taking a lock; the table
+/// can be swapped at runtime, and clones of a handler share it.
+///
+/// Calls arriving with a session are counted as in flight on that session while
+/// they run, except `shutdown`, which waits for the others.
 #[derive(Default)]
 pub struct JsonRpcHandler {
-    /// Registered method handlers
-    methods: Arc<RwLock<HashMap<String, MethodHandlerFn>>>,
+    /// Current method table
+    methods: Arc<ArcSwap<MethodTable>>,
     
     /// Optional global context provider
-    context_provider: Option<Arc<dyn Fn() -> MethodContext + Send + Sync>>,
+    context_provider: Option<ContextProvider>,
+    
+    /// Whether calls on a session are gated by its MCP lifecycle phase
+    enforce_lifecycle: bool,
+    
+    /// Middlewares every call passes through, outermost first
+    middlewares: Arc<[Arc<dyn Middleware>]>,
+    
+    /// Concurrency caps and timeouts of method calls
+    limits: Arc<DispatchLimits>,
 }
 
 impl JsonRpcHandler {
-    /// Creates a new JSON-RPC handler.
+    /// Creates a handler with no methods.
     pub fn new() -> Self {
         Self::default()
     }
     
-    /// Registers a method handler function.
+    /// Returns a builder for a handler.
+    pub fn builder() -> JsonRpcHandlerBuilder {
+        JsonRpcHandlerBuilder::new()
+    }
+    
+    /// Assembles a handler from what a [`JsonRpcHandlerBuilder`] collected.
+    pub(super) fn from_parts(
+        methods: MethodTable,
+        context_provider: Option<ContextProvider>,
+        enforce_lifecycle: bool,
+        middlewares: Arc<[Arc<dyn Middleware>]>,
+        limits: DispatchLimits,
+    ) -> Self {
+        Self {
+            methods: Arc::new(ArcSwap::from_pointee(methods)),
+            context_provider,
+            enforce_lifecycle,
+            middlewares,
+            limits: Arc::new(limits),
+        }
+    }
+    
+    /// Registers a method handler function on a running handler.
+    ///
+    /// This swaps in a copy of the method table with the method added, which
+    /// clones of this handler see as well. Prefer registering on a
+    /// [`JsonRpcHandlerBuilder`] when the methods are known up front.
     pub fn register_method<F, Fut>(&mut self, method: impl Into<String>, handler: F)
     where
         F: Send + Sync + 'static + Fn(Option<Value>, MethodContext) -> Fut,
         Fut: Future<Output = MethodResult> + Send + 'static,
     {
-        let method_name = method.into();
-        let handler_fn = Arc::new(handler) as MethodHandlerFn;
-        
-        tokio::task::block_in_place(|| {
-            futures::executor::block_on(async {
-                let mut methods = self.methods.write().await;
-                methods.insert(method_name, handler_fn);
-            })
-        });
+        let mut added = MethodTable::new();
+        added.register_method(method, handler);
+        self.update_methods(|methods| methods.extend(&added));
     }
     
-    /// Registers a context provider function that is called for each request.
-    ///
-    /// The context provider allows injecting context information into all method calls.
-    pub fn register_context_provider<F>(&mut self, provider: F)
+    /// Registers a method whose params and result are Rust types on a running
+    /// handler. See [`MethodTable::register_typed`] and
+    /// [`register_method`](Self::register_method).
+    pub fn register_typed<P, R, F, Fut>(&mut self, method: impl Into<String>, handler: F)
     where
-        F: Fn() -> MethodContext + Send + Sync + 'static,
+        P: DeserializeOwned + JsonSchema + Send + 'static,
+        R: Serialize + JsonSchema + 'static,
+        F: Send + Sync + 'static + Fn(P, MethodContext) -> Fut,
+        Fut: Future<Output = std::result::Result<R, JsonRpcError>> + Send + 'static,
     {
-        self.context_provider = Some(Arc::new(provider));
+        let mut added = MethodTable::new();
+        added.register_typed(method, handler);
+        self.update_methods(|methods| methods.extend(&added));
+    }
+    
+    /// Returns the current method table.
+    ///
+    /// The returned table does not change; later swaps are not reflected in it.
+    pub fn methods(&self) -> Arc<MethodTable> {
+        self.methods.load_full()
+    }
+    
+    /// Atomically replaces the method table.
+    ///
+    /// Calls already dispatched finish against the table they started with.
+    pub fn replace_methods(&self, methods: MethodTable) {
+        self.methods.store(Arc::new(methods));
+    }
+    
+    /// Atomically replaces the method table with an updated copy of it.
+    ///
+    /// `update` may run more than once if another update races with it, so it
+    /// should do nothing but change the table it is given.
+    pub fn update_methods(&self, mut update: impl FnMut(&mut MethodTable)) {
+        self.methods.rcu(|current| {
+            let mut methods = MethodTable::clone(current);
+            update(&mut methods);
+            methods
+        });
+    }
+    
+    /// Returns the params and result schemas of a method registered with types.
+    pub fn method_schema(&self, method: &str) -> Option<MethodSchema> {
+        self.methods.load().schema(method).cloned()
+    }
+    
+    /// Returns the schemas of all methods registered with types, by method name.
+    pub fn method_schemas(&self) -> BTreeMap<String, MethodSchema> {
+        self.methods.load().schemas().clone()
     }
     
     /// Handles a JSON-RPC request string.
@@ -124,196 +286,398 @@ impl JsonRpcHandler {
     /// * `context` - Optional context to pass to method handlers.
     ///
     /// # Returns
-    /// A JSON string containing the JSON-RPC response.
+    /// A JSON string containing the JSON-RPC response, or an empty string if
+    /// no response is due, as for notifications and cancelled requests.
     pub async fn handle_request(
         &self,
         request_str: impl AsRef<str>,
         context: Option<MethodContext>,
     ) -> String {
-        // Validate request
-        let validated = match validate_request(request_str) {
-            Ok(req) => req,
-            Err(err) => {
-                return match err {
-                    Error::Json(_) => {
-                        // Parse error - could not parse the JSON
-                        let error = JsonRpcError::parse_error();
-                        let response = Response {
-                            jsonrpc: "2.0".to_string(),
-                            result: None,
-                            error: Some(error),
-                            id: Id::Null,
-                        };
-                        
-                        serde_json::to_string(&response).unwrap_or_else(|_| {
-                            r#"{\"jsonrpc\":\"2.0\",\"error\":{\"code\":-32700,\"message\":\"Parse error\"},\"id\":null}"#.to_string()
-                        })
-                    },
-                    Error::JsonRpc(msg) => {
-                        // Invalid request format
-                        let error = JsonRpcError::invalid_request();
-                        let response = Response {
-                            jsonrpc: "2.0".to_string(),
-                            result: None,
-                            error: Some(error),
-                            id: Id::Null,
-                        };
-                        
-                        serde_json::to_string(&response).unwrap_or_else(|_| {
-                            r#"{\"jsonrpc\":\"2.0\",\"error\":{\"code\":-32600,\"message\":\"Invalid Request\"},\"id\":null}"#.to_string()
-                        })
-                    },
-                    _ => {
-                        // Other errors
-                        let error = JsonRpcError::internal_error(err.to_string());
-                        let response = Response {
-                            jsonrpc: "2.0".to_string(),
-                            result: None,
-                            error: Some(error),
-                            id: Id::Null,
-                        };
-                        
-                        serde_json::to_string(&response).unwrap_or_else(|_| {
-                            r#"{\"jsonrpc\":\"2.0\",\"error\":{\"code\":-32603,\"message\":\"Internal error\"},\"id\":null}"#.to_string()
-                        })
-                    },
-                };
-            }
+        self.handle_message(request_str, context).await.unwrap_or_default()
+    }
+    
+    /// Handles a JSON-RPC message string, producing output only when a reply is due.
+    ///
+    /// This method returns `None` for notifications and for batches made up
+    /// entirely of notifications, since JSON-RPC 2.0 forbids replying to them,
+    /// where [`handle_request`](Self::handle_request) returns an empty string.
+    /// Transports should use this entry point so that nothing is written back
+    /// to the client in those cases.
+    ///
+    /// # Parameters
+    /// * `message` - The JSON-RPC message string to handle.
+    /// * `context` - Optional context to pass to method handlers.
+    ///
+    /// # Returns
+    /// The JSON response string, or `None` if no response should be sent.
+    pub async fn handle_message(
+        &self,
+        message: impl AsRef<str>,
+        context: Option<MethodContext>,
+    ) -> Option<String> {
+        let reply = self.handle_bytes(message.as_ref().as_bytes(), context).await?;
+        Some(reply.to_json_string())
+    }
+    
+    /// Handles a JSON-RPC message as raw bytes, returning the reply unserialized.
+    ///
+    /// The message is parsed without building a [`Value`] for it: params are
+    /// handed to methods as slices of `message`, and decoded only by the
+    /// method that takes them. Replies are returned as [`OutgoingMessage`] so
+    /// that transports can serialize them straight into their own framing.
+    /// Returns `None` when no reply is due, as with
+    /// [`handle_message`](Self::handle_message).
+    ///
+    /// # Parameters
+    /// * `message` - The JSON-RPC message, such as a request body or a frame.
+    /// * `context` - Optional context to pass to method handlers.
+    pub async fn handle_bytes(
+        &self,
+        message: impl AsRef<[u8]>,
+        context: Option<MethodContext>,
+    ) -> Option<OutgoingMessage> {
+        let message = match parse_bytes(message.as_ref()) {
+            Ok(message) => message,
+            Err(err) => return Some(OutgoingMessage::Single(validation_error_response(err))),
+        };
+        
+        match message {
+            RawMessage::Responses(responses) => {
+                self.route_responses(responses, context).await;
+                None
+            },
+            RawMessage::Call(request) => {
+                // Notifications and cancelled requests get no response
+                let ctx = self.resolve_context(context);
+                self.handle_single_request(request, ctx).await.map(OutgoingMessage::Single)
+            },
+            RawMessage::Batch(requests) => {
+                let ctx = self.resolve_context(context);
+                match self.handle_batch_request(requests, ctx).await {
+                    Ok(responses) if responses.responses.is_empty() => None,
+                    Ok(responses) => Some(OutgoingMessage::Batch(responses)),
+                    Err(error) => Some(OutgoingMessage::Single(Response::error(Id::Null, error))),
+                }
+            },
+        }
+    }
+    
+    /// Hands responses from the client to the session that sent the requests.
+    async fn route_responses(&self, responses: Vec<Response>, context: Option<MethodContext>) {
+        let Some(session) = context.and_then(|c| c.session) else {
+            tracing::debug!(count = responses.len(), "Dropping responses received outside a session");
+            return;
         };
         
-        // Get the context for this request
-        let ctx = match context {
+        for response in responses {
+            let id = response.id.clone();
+            if !session.correlate(response).await {
+                tracing::debug!(id = ?id, session = session.id(), "Dropping response to unknown request");
+            }
+        }
+    }
+    
+    /// Returns the caller-supplied context, falling back to the context provider.
+    fn resolve_context(&self, context: Option<MethodContext>) -> MethodContext {
+        match context {
             Some(c) => c,
             None => match &self.context_provider {
                 Some(provider) => (provider)(),
                 None => MethodContext::default(),
             },
-        };
-        
-        // Dispatch request(s) to handler(s)
-        match validated {
-            ValidatedRequest::Single(request) => {
-                let response = self.handle_single_request(request, ctx).await;
-                serde_json::to_string(&response).unwrap_or_else(|_| {
-                    // Error serializing response - should never happen with valid response
-                    r#"{\"jsonrpc\":\"2.0\",\"error\":{\"code\":-32603,\"message\":\"Internal error: Error serializing response\"},\"id\":null}"#.to_string()
-                })
-            },
-            ValidatedRequest::Batch(batch) => {
-                let responses = self.handle_batch_request(batch, ctx).await;
-                
-                // Empty response array is invalid according to the spec
-                if responses.responses.is_empty() {
-                    return r#"{\"jsonrpc\":\"2.0\",\"error\":{\"code\":-32600,\"message\":\"Invalid Request: Batch request contained only notifications\"},\"id\":null}"#.to_string();
-                }
-                
-                serde_json::to_string(&responses.responses).unwrap_or_else(|_| {
-                    r#"{\"jsonrpc\":\"2.0\",\"error\":{\"code\":-32603,\"message\":\"Internal error: Error serializing response\"},\"id\":null}"#.to_string()
-                })
-            },
         }
     }
     
     /// Handles a single JSON-RPC request.
-    async fn handle_single_request(&self, request: Request, context: MethodContext) -> Response {
-        // For notifications (no ID), we still process but return no response
-        let id = match request.id {
-            Some(id) => id,
-            None => {
-                // It's a notification, process it but return no response
-                self.process_method_call(&request.method, request.params.clone(), context.clone()).await;
-                return Response {
-                    jsonrpc: "2.0".to_string(),
-                    result: None, 
-                    error: None,
-                    id: Id::Null, // This should actually never be returned in a real scenario
-                };
-            }
+    ///
+    /// Returns `None` for a notification, which is processed but never
+    /// answered, and if the client cancelled the request before it completed.
+    /// A request with a null id is answered like any other.
+    async fn handle_single_request(&self, request: RawRequest<'_>, mut context: MethodContext) -> Option<Response> {
+        let Some(id) = request.id.clone() else {
+            self.handle_notification(request, context).await;
+            return None;
         };
         
         // Check if method exists
-        let methods = self.methods.read().await;
-        if !methods.contains_key(&request.method) {
-            return Response::error(
+        if !self.methods.load().contains(&request.method) {
+            return Some(Response::error(
                 id,
-                JsonRpcError::method_not_found(&request.method),
-            );
+                JsonRpcError::method_not_found(request.method.as_ref()),
+            ));
+        }
+        
+        // Give the request its own cancellation token and, if asked for, progress reporting
+        context.cancellation = context.cancellation.child_token();
+        if let Some(session) = &context.session {
+            context.progress = ProgressReporter::from_raw_params(request.params, session);
         }
+        let cancellation = context.cancellation.clone();
+        let method = request.method.clone();
         
-        // Process method call
-        match self.process_method_call(&request.method, request.params, context).await {
-            Ok(result) => Response::success(id, result),
-            Err(error) => Response::error(id, error),
+        // Process method call, dropping it if the request is cancelled
+        let result = tokio::select! {
+            result = self.dispatch(MethodCall::from_raw(request, context)) => result,
+            _ = cancellation.cancelled() => {
+                tracing::debug!(method = %method, id = ?id, "Request cancelled");
+                return None;
+            }
+        };
+        match result {
+            Ok(result) => Some(Response::success(id, result)),
+            Err(error) => Some(Response::error(id, error)),
         }
     }
     
     /// Handles a batch of JSON-RPC requests.
+    ///
+    /// Requests and notifications share the batch's parallelism budget, and
+    /// the batch is answered once all of them are done. A batch larger than
+    /// [`DispatchLimits`] allows is rejected without running any of it.
     async fn handle_batch_request(
         &self,
-        batch: BatchRequest,
+        batch: Vec<RawRequest<'_>>,
         context: MethodContext,
-    ) -> BatchResponse {
-        let mut responses = Vec::with_capacity(batch.requests.len());
-        let mut futures = Vec::with_capacity(batch.requests.len());
-        
-        // Create futures for all requests with IDs
-        for request in batch.requests.iter() {
-            // Skip notifications (we process them but don't include in responses)
-            if request.id.is_some() {
-                let req_clone = request.clone();
-                let ctx_clone = context.clone();
-                let this = self.clone();
-                
-                futures.push(async move {
-                    this.handle_single_request(req_clone, ctx_clone).await
-                });
-            } else {
-                // Process notification in background
-                let method = request.method.clone();
-                let params = request.params.clone();
-                let ctx_clone = context.clone();
-                let this = self.clone();
-                
-                tokio::spawn(async move {
-                    this.process_method_call(&method, params, ctx_clone).await;
-                });
-            }
+    ) -> std::result::Result<BatchResponse, JsonRpcError> {
+        let size = batch.len();
+        if let Some(max_batch_size) = self.limits.max_batch_size().filter(|max| size > *max) {
+            return Err(dispatch::batch_too_large_error(size, max_batch_size));
         }
         
-        // Wait for all futures to complete
-        if !futures.is_empty() {
-            responses = futures::future::join_all(futures).await;
-        }
+        // Collected up front, as a stream mapping with a closure over the
+        // borrowed requests would not be `Send`
+        let calls: Vec<_> = batch
+            .into_iter()
+            .map(|request| self.handle_single_request(request, context.clone()))
+            .collect();
+        let calls = stream::iter(calls);
         
-        BatchResponse { responses }
+        let parallelism = self.limits.batch_parallelism(size);
+        let responses = match self.limits.batch_mode() {
+            BatchMode::Ordered => calls.buffered(parallelism).filter_map(future::ready).collect().await,
+            BatchMode::Unordered => calls.buffer_unordered(parallelism).filter_map(future::ready).collect().await,
+        };
+        Ok(BatchResponse { responses })
+    }
+    
+    /// Processes a notification, logging the error it fails with, if any.
+    async fn handle_notification(&self, request: RawRequest<'_>, context: MethodContext) {
+        let method = request.method.clone();
+        if let Err(error) = self.dispatch(MethodCall::from_raw(request, context)).await {
+            tracing::debug!(method = %method, code = error.code, message = %error.message, "Notification failed");
+        }
+    }
+    
+    /// Passes a call through the middleware chain to its method.
+    async fn dispatch(&self, mut call: MethodCall<'_>) -> MethodResult {
+        call.context.timings = CallTimings::default();
+        Next::new(self, &self.middlewares).run(call).await
     }
     
     /// Processes a method call by dispatching it to the registered handler.
-    async fn process_method_call(
+    ///
+    /// Requests with an `id` can be cancelled through their session while
+    /// they run, and are refused with `-32003` if the session already has a
+    /// request with that id in flight or within its replay window. The call
+    /// waits for the permits [`DispatchLimits`] asks for, unless it is a
+    /// notification or control method, fails with `-32001` if it runs out of
+    /// time, and a panicking method is answered with `-32603` instead of
+    /// taking the connection down.
+    pub(super) async fn process_method_call(
         &self,
         method: &str,
-        params: Option<Value>,
-        context: MethodContext,
+        params: Option<&RawValue>,
+        mut context: MethodContext,
+        id: Option<&Id>,
     ) -> MethodResult {
-        let methods = self.methods.read().await;
-        
-        // Get method handler
-        let handler = match methods.get(method) {
+        // Get method handler, or notification handler for a notification
+        let methods = self.methods.load_full();
+        let (handler, notification) = match id {
+            Some(_) => (methods.get(method), false),
+            None => (methods.notification(method), true),
+        };
+        let handler = match handler {
             Some(h) => h.clone(),
             None => return Err(JsonRpcError::method_not_found(method)),
         };
+        context.methods = Some(methods);
+        
+        let session = context.session.clone();
+        if let Some(session) = session.as_deref().filter(|_| self.enforce_lifecycle) {
+            lifecycle::admit(session.phase(), method)?;
+        }
         
         // Call handler and return result
-        handler.handle(params, context).await
+        let _in_flight = match session.as_deref().filter(|_| method != SHUTDOWN) {
+            Some(session) => Some(match id {
+                Some(id) => {
+                    if let Some(window) = self.limits.replay_window() {
+                        session.detect_replays(window);
+                    }
+                    session
+                        .track_request(id, context.cancellation.clone())
+                        .map_err(|error| dispatch::duplicate_id_error(id, error))?
+                }
+                None => session.track(),
+            }),
+            None => None,
+        };
+        
+        let exempt = DispatchLimits::is_exempt(method, notification);
+        let timeout = if exempt {
+            self.limits.method_timeout(method)
+        } else {
+            self.limits.timeout(method)
+        };
+        let started = Instant::now();
+        let timings = context.timings.clone();
+        let _permits = if exempt {
+            None
+        } else {
+            let permits = within(timeout, self.limits.acquire(method))
+                .await
+                .ok_or_else(|| dispatch::timeout_error(method, timeout.unwrap_or_default(), true))?;
+            Some(permits)
+        };
+        let queued = started.elapsed();
+        timings.record_queued(queued);
+        
+        let executing = async {
+            let future = std::panic::catch_unwind(AssertUnwindSafe(|| handler.handle_raw(params, context)))?;
+            AssertUnwindSafe(future).catch_unwind().await
+        };
+        let remaining = timeout.map(|timeout| timeout.saturating_sub(queued));
+        let result = within(remaining, executing).await;
+        timings.record_executing(started.elapsed() - queued);
+        match result {
+            Some(Ok(result)) => result,
+            Some(Err(panic)) => Err(dispatch::panic_error(method, panic.as_ref())),
+            None => Err(dispatch::timeout_error(method, timeout.unwrap_or_default(), false)),
+        }
+    }
+}
+
+/// Runs `future` to completion or until `timeout` elapses, returning `None` on timeout.
+async fn within<F: Future>(timeout: Option<Duration>, future: F) -> Option<F::Output> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, future).await.ok(),
+        None => Some(future.await),
     }
 }
 
+/// Builds the error response for a message that failed validation.
+fn validation_error_response(err: Error) -> Response {
+    let error = match err {
+        // Parse error - could not parse the JSON
+        Error::Json(_) => JsonRpcError::parse_error(),
+        // Invalid request format
+        Error::JsonRpc(_) => JsonRpcError::invalid_request(),
+        // Other errors
+        _ => JsonRpcError::internal_error(err.to_string()),
+    };
+    Response::error(Id::Null, error)
+}
+
 // Clone implementation for JsonRpcHandler
 impl Clone for JsonRpcHandler {
     fn clone(&self) -> Self {
         Self {
             methods: self.methods.clone(),
             context_provider: self.context_provider.clone(),
+            enforce_lifecycle: self.enforce_lifecycle,
+            middlewares: self.middlewares.clone(),
+            limits: self.limits.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::jsonrpc::error::ErrorCode;
+    use serde_json::json;
+    
+    fn handler() -> JsonRpcHandler {
+        let mut builder = JsonRpcHandler::builder();
+        builder.register_method("echo", |params, _ctx| async move { Ok(params.unwrap_or(Value::Null)) });
+        builder.build()
+    }
+    
+    #[tokio::test]
+    async fn test_handle_bytes_returns_structured_replies() {
+        let handler = handler();
+        
+        let message = br#"{"jsonrpc":"2.0","method":"echo","params":{"a":[1,2]},"id":1}"#;
+        let Some(OutgoingMessage::Single(response)) = handler.handle_bytes(&message[..], None).await else {
+            panic!("expected a single response");
+        };
+        assert_eq!(response.result, Some(json!({"a": [1, 2]})));
+        
+        let batch = r#"[{"jsonrpc":"2.0","method":"echo","params":[1],"id":1},{"jsonrpc":"2.0","method":"echo"}]"#;
+        let Some(OutgoingMessage::Batch(batch)) = handler.handle_bytes(batch, None).await else {
+            panic!("expected a batch response");
+        };
+        assert_eq!(batch.responses.len(), 1);
+        assert_eq!(
+            OutgoingMessage::Batch(batch).to_json_string(),
+            r#"[{"jsonrpc":"2.0","result":[1],"id":1}]"#
+        );
+        
+        let Some(OutgoingMessage::Single(response)) = handler.handle_bytes(b"{", None).await else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(response.error.unwrap().code, ErrorCode::ParseError.code());
+    }
+    
+    #[tokio::test]
+    async fn test_no_reply_to_notifications() {
+        let handler = handler();
+        assert!(handler.handle_bytes(r#"{"jsonrpc":"2.0","method":"echo"}"#, None).await.is_none());
+        assert!(handler
+            .handle_bytes(r#"[{"jsonrpc":"2.0","method":"echo"},{"jsonrpc":"2.0","method":"echo"}]"#, None)
+            .await
+            .is_none());
+        assert_eq!(handler.handle_request(r#"{"jsonrpc":"2.0","method":"echo"}"#, None).await, "");
+        
+        // A null id is a request, not a notification
+        let output = handler.handle_request(r#"{"jsonrpc":"2.0","method":"echo","params":[1],"id":null}"#, None).await;
+        assert_eq!(output, r#"{"jsonrpc":"2.0","result":[1],"id":null}"#);
+    }
+    
+    #[tokio::test]
+    async fn test_notification_handlers_are_separate_from_methods() {
+        let notified = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut builder = JsonRpcHandler::builder();
+        builder.register_notification("notify", {
+            let notified = notified.clone();
+            move |_params, _ctx| {
+                notified.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                async { Ok(()) }
+            }
+        });
+        builder.register_method("request", {
+            let notified = notified.clone();
+            move |_params, _ctx| {
+                notified.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                async { Ok(Value::Null) }
+            }
+        });
+        let handler = builder.build();
+        
+        assert!(handler.handle_message(r#"{"jsonrpc":"2.0","method":"notify"}"#, None).await.is_none());
+        assert_eq!(notified.load(std::sync::atomic::Ordering::SeqCst), 1);
+        
+        // Requests are never served by notification handlers
+        let output = handler.handle_request(r#"{"jsonrpc":"2.0","method":"notify","id":1}"#, None).await;
+        let response: Response = serde_json::from_str(&output).unwrap();
+        assert_eq!(response.error.unwrap().code, ErrorCode::MethodNotFound.code());
+        assert_eq!(notified.load(std::sync::atomic::Ordering::SeqCst), 1);
+        
+        // Nor are notifications served by request methods
+        assert!(handler.handle_message(r#"{"jsonrpc":"2.0","method":"request"}"#, None).await.is_none());
+        assert_eq!(notified.load(std::sync::atomic::Ordering::SeqCst), 1);
+        
+        // Unknown notifications are dropped without a reply
+        assert!(handler.handle_message(r#"{"jsonrpc":"2.0","method":"unknown"}"#, None).await.is_none());
+    }
+}
@@ -0,0 +1,325 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Classification of incoming JSON-RPC messages.
+//!
+//! Both ends of an MCP session send requests, so a message arriving from the
+//! client is either a request or notification for the server, or a response to
+//! a request the server sent earlier. [`parse_bytes`] tells them apart so
+//! that requests are dispatched to method handlers and responses are routed to
+//! the session's [`RequestResponseCorrelator`](super::RequestResponseCorrelator).
+//!
+//! It does so without building a [`Value`] for the message: the method name
+//! is borrowed from the input where it can be, and params are kept as a
+//! [`RawValue`] slice of it until the method decodes them.
+//! [`OutgoingMessage`] is what the handler answers with, for transports to
+//! serialize straight into their own framing.
+
+use std::borrow::Cow;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::error::Category;
+use serde_json::value::RawValue;
+use serde_json::Value;
+
+use super::error::{Error, Result};
+use super::types::{BatchResponse, Id, Request, Response};
+
+/// Kind of a single JSON-RPC message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    /// A call expecting a response
+    Request,
+
+    /// A call without an id, expecting no response
+    Notification,
+
+    /// The result or error of an earlier request
+    Response,
+}
+
+/// A validated call borrowing from the message it was parsed from.
+#[derive(Debug, Clone)]
+pub struct RawRequest<'a> {
+    /// Name of the method
+    pub method: Cow<'a, str>,
+
+    /// Params of the call, an object or an array, not yet decoded
+    pub params: Option<&'a RawValue>,
+
+    /// Id of the request, or `None` for a notification
+    pub id: Option<Id>,
+}
+
+impl RawRequest<'_> {
+    /// Returns true if the call expects no response.
+    pub fn is_notification(&self) -> bool {
+        self.id.is_none()
+    }
+
+    /// Copies the call into an owned [`Request`].
+    pub fn to_request(&self) -> Result<Request> {
+        Ok(Request {
+            jsonrpc: "2.0".to_string(),
+            method: self.method.to_string(),
+            params: self.params.map(|params| serde_json::from_str(params.get())).transpose()?,
+            id: self.id.clone(),
+        })
+    }
+}
+
+/// An incoming message parsed by [`parse_bytes`].
+#[derive(Debug, Clone)]
+pub enum RawMessage<'a> {
+    /// A single request or notification
+    Call(RawRequest<'a>),
+
+    /// A batch of requests and notifications
+    Batch(Vec<RawRequest<'a>>),
+
+    /// Responses to requests the server sent, from a single message or a batch
+    Responses(Vec<Response>),
+}
+
+/// A reply to an incoming message.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum OutgoingMessage {
+    /// The response to a single request, or an error about the whole message
+    Single(Response),
+
+    /// The responses to the requests of a batch
+    Batch(BatchResponse),
+}
+
+impl OutgoingMessage {
+    /// Serializes the reply as JSON.
+    pub fn to_vec(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap_or_else(|_| SERIALIZATION_ERROR.as_bytes().to_vec())
+    }
+
+    /// Serializes the reply as a JSON string.
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| SERIALIZATION_ERROR.to_string())
+    }
+}
+
+/// Reply sent when a reply cannot be serialized.
+const SERIALIZATION_ERROR: &str =
+    r#"{"jsonrpc":"2.0","error":{"code":-32603,"message":"Internal error: Error serializing response"},"id":null}"#;
+
+/// Every member a message may have. Members that are present, even as
+/// `null`, are `Some`.
+#[derive(Deserialize)]
+struct Envelope<'a> {
+    #[serde(borrow, default, deserialize_with = "borrowed_str")]
+    jsonrpc: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "borrowed_str")]
+    method: Option<Cow<'a, str>>,
+
+    #[serde(borrow, default, deserialize_with = "present")]
+    params: Option<&'a RawValue>,
+
+    #[serde(default, deserialize_with = "present")]
+    id: Option<Id>,
+
+    #[serde(borrow, default, deserialize_with = "present")]
+    result: Option<&'a RawValue>,
+
+    #[serde(default, deserialize_with = "present")]
+    error: Option<Value>,
+}
+
+fn present<'de, D: Deserializer<'de>, T: Deserialize<'de>>(deserializer: D) -> std::result::Result<Option<T>, D::Error> {
+    T::deserialize(deserializer).map(Some)
+}
+
+/// Borrows a string from the input unless it has escapes, which serde's own
+/// `Cow` implementation never does.
+fn borrowed_str<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<Cow<'de, str>>, D::Error> {
+    struct CowStr;
+
+    impl<'de> Visitor<'de> for CowStr {
+        type Value = Cow<'de, str>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            formatter.write_str("a string")
+        }
+
+        fn visit_borrowed_str<E: de::Error>(self, value: &'de str) -> std::result::Result<Self::Value, E> {
+            Ok(Cow::Borrowed(value))
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> std::result::Result<Self::Value, E> {
+            Ok(Cow::Owned(value.to_string()))
+        }
+
+        fn visit_string<E: de::Error>(self, value: String) -> std::result::Result<Self::Value, E> {
+            Ok(Cow::Owned(value))
+        }
+    }
+
+    deserializer.deserialize_str(CowStr).map(Some)
+}
+
+impl<'a> Envelope<'a> {
+    fn kind(&self) -> Option<MessageKind> {
+        match (&self.method, &self.id) {
+            (Some(_), Some(_)) => Some(MessageKind::Request),
+            (Some(_), None) => Some(MessageKind::Notification),
+            (None, _) if self.result.is_some() || self.error.is_some() => Some(MessageKind::Response),
+            (None, _) => None,
+        }
+    }
+
+    /// Validates a request or notification.
+    fn into_call(self) -> Result<RawRequest<'a>> {
+        if self.jsonrpc.as_deref() != Some("2.0") {
+            return Err(Error::JsonRpc(format!(
+                "Invalid JSON-RPC version: {}, must be 2.0",
+                self.jsonrpc.as_deref().unwrap_or("none")
+            )));
+        }
+        let method = match self.method {
+            Some(method) if !method.is_empty() => method,
+            Some(_) => return Err(Error::JsonRpc("Method cannot be empty".to_string())),
+            None => return Err(Error::JsonRpc("Invalid JSON-RPC message, missing method".to_string())),
+        };
+        let params = match self.params.map(|params| (params, params.get().as_bytes()[0])) {
+            None => None,
+            Some((params, b'{' | b'[')) => Some(params),
+            Some((params, _)) if params.get() == "null" => None,
+            Some(_) => return Err(Error::JsonRpc("Params must be an object, array, or null".to_string())),
+        };
+        Ok(RawRequest {
+            method,
+            params,
+            id: self.id,
+        })
+    }
+
+    fn into_response(self) -> Result<Response> {
+        Ok(Response {
+            jsonrpc: "2.0".to_string(),
+            result: self.result.map(|result| serde_json::from_str(result.get())).transpose()?,
+            error: self.error.filter(|error| !error.is_null()).map(serde_json::from_value).transpose()?,
+            id: self.id.unwrap_or(Id::Null),
+        })
+    }
+}
+
+/// Parses and classifies an incoming message, borrowing from it.
+///
+/// A message, or a batch, made up only of responses is returned as
+/// [`RawMessage::Responses`]; anything else is validated as calls. Input that
+/// is not JSON is an [`Error::Json`], and JSON that is not a valid message an
+/// [`Error::JsonRpc`].
+pub fn parse_bytes(message: &[u8]) -> Result<RawMessage<'_>> {
+    let is_batch = message.iter().find(|byte| !byte.is_ascii_whitespace()) == Some(&b'[');
+    if !is_batch {
+        let envelope: Envelope<'_> = serde_json::from_slice(message).map_err(classify)?;
+        return match envelope.kind() {
+            Some(MessageKind::Response) => Ok(RawMessage::Responses(vec![envelope.into_response()?])),
+            _ => envelope.into_call().map(RawMessage::Call),
+        };
+    }
+
+    let envelopes: Vec<Envelope<'_>> = serde_json::from_slice(message).map_err(classify)?;
+    if envelopes.is_empty() {
+        return Err(Error::JsonRpc("Empty batch requests are invalid".to_string()));
+    }
+    if envelopes.iter().all(|envelope| envelope.kind() == Some(MessageKind::Response)) {
+        let responses = envelopes.into_iter().map(Envelope::into_response).collect::<Result<_>>()?;
+        return Ok(RawMessage::Responses(responses));
+    }
+    envelopes
+        .into_iter()
+        .map(Envelope::into_call)
+        .collect::<Result<_>>()
+        .map(RawMessage::Batch)
+}
+
+/// Tells malformed JSON apart from well-formed JSON of the wrong shape.
+fn classify(error: serde_json::Error) -> Error {
+    match error.classify() {
+        Category::Data => Error::JsonRpc(format!("Invalid JSON-RPC message: {error}")),
+        _ => Error::Json(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::jsonrpc::types::Id;
+
+    #[test]
+    fn test_parse_bytes_routes_responses() {
+        let RawMessage::Responses(responses) = parse_bytes(br#"{"jsonrpc":"2.0","result":{"roots":[]},"id":3}"#).unwrap() else {
+            panic!("expected a response");
+        };
+        assert_eq!(responses[0].id, Id::Number(3));
+
+        let batch = br#"[{"jsonrpc":"2.0","result":{},"id":1},{"jsonrpc":"2.0","error":{"code":1,"message":"x"},"id":2}]"#;
+        assert!(matches!(parse_bytes(batch).unwrap(), RawMessage::Responses(r) if r.len() == 2));
+
+        // A batch mixing calls and responses is validated as calls
+        let mixed = br#"[{"jsonrpc":"2.0","result":{},"id":1},{"jsonrpc":"2.0","method":"ping","id":2}]"#;
+        assert!(parse_bytes(mixed).is_err());
+        assert!(parse_bytes(br#"{"jsonrpc":"2.0","id":1}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_bytes_borrows_params() {
+        let message = br#"{"jsonrpc":"2.0","method":"tools/call","params":{"name":"fetch","arguments":{"url":"x"}},"id":"a"}"#;
+        let RawMessage::Call(call) = parse_bytes(message).unwrap() else {
+            panic!("expected a call");
+        };
+        assert!(matches!(call.method, Cow::Borrowed("tools/call")));
+        let RawMessage::Call(escaped) = parse_bytes(br#"{"jsonrpc":"2.0","method":"a\/b"}"#).unwrap() else {
+            panic!("expected a call");
+        };
+        assert_eq!(escaped.method, "a/b");
+        assert_eq!(call.params.unwrap().get(), r#"{"name":"fetch","arguments":{"url":"x"}}"#);
+        assert_eq!(call.id, Some(Id::String("a".to_string())));
+
+        let RawMessage::Batch(calls) =
+            parse_bytes(br#" [{"jsonrpc":"2.0","method":"a","params":null},{"jsonrpc":"2.0","method":"b","id":2}]"#).unwrap()
+        else {
+            panic!("expected a batch");
+        };
+        assert!(calls[0].is_notification() && calls[0].params.is_none());
+        assert_eq!(calls[1].id, Some(Id::Number(2)));
+
+        // A null id is a request to be answered with a null id
+        let RawMessage::Call(call) = parse_bytes(br#"{"jsonrpc":"2.0","method":"a","id":null}"#).unwrap() else {
+            panic!("expected a call");
+        };
+        assert_eq!(call.id, Some(Id::Null));
+        assert!(!call.is_notification());
+    }
+
+    #[test]
+    fn test_parse_bytes_errors() {
+        assert!(matches!(parse_bytes(b"{"), Err(Error::Json(_))));
+        for message in [
+            &b"1"[..],
+            b"[]",
+            br#"{"jsonrpc":"1.0","method":"a"}"#,
+            br#"{"jsonrpc":"2.0","method":"a","params":1}"#,
+            br#"[{"jsonrpc":"2.0","method":"a"},{"jsonrpc":"2.0"}]"#,
+        ] {
+            assert!(matches!(parse_bytes(message), Err(Error::JsonRpc(_))), "{}", String::from_utf8_lossy(message));
+        }
+
+        // A null result is still a response
+        let RawMessage::Responses(responses) = parse_bytes(br#"{"jsonrpc":"2.0","result":null,"id":1}"#).unwrap() else {
+            panic!("expected a response");
+        };
+        assert_eq!(responses[0].result, Some(Value::Null));
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Implementation of the MCP "notifications/cancelled" notification handler.
+//!
+//! The client sends this notification to abandon a request it sent earlier on
+//! the same session. The request's handler is dropped and no response is sent
+//! for it. Cancellations of requests that have already finished, or that are
+//! unknown, are ignored, as the MCP specification requires.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::protocol::jsonrpc::error::JsonRpcError;
+use crate::protocol::jsonrpc::builder::JsonRpcHandlerBuilder;
+use crate::protocol::jsonrpc::handler::MethodContext;
+use crate::protocol::jsonrpc::types::Id;
+use crate::protocol::lifecycle::CANCELLED;
+
+/// Parameters of the cancelled notification.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelledParams {
+    /// Id of the request to cancel
+    pub request_id: Id,
+
+    /// Why the request was cancelled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Registers the notifications/cancelled handler with the JSON-RPC handler builder.
+pub fn register_cancelled_notification(builder: &mut JsonRpcHandlerBuilder) {
+    builder.register_typed_notification(CANCELLED, handle_cancelled);
+}
+
+/// Handles the cancelled notification.
+async fn handle_cancelled(params: CancelledParams, context: MethodContext) -> Result<(), JsonRpcError> {
+    let cancelled = context
+        .session
+        .as_ref()
+        .is_some_and(|session| session.cancel(&params.request_id));
+    tracing::debug!(
+        request_id = ?params.request_id,
+        reason = params.reason.as_deref().unwrap_or_default(),
+        cancelled,
+        "Cancellation requested"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::jsonrpc::typed::decode_params;
+    use crate::protocol::Session;
+    use std::sync::Arc;
+    use tokio_util::sync::CancellationToken;
+
+    #[tokio::test]
+    async fn test_cancels_request_on_session() {
+        let session = Arc::new(Session::new("test"));
+        let token = CancellationToken::new();
+        let _in_flight = session.track_request(&Id::String("req".to_string()), token.clone()).unwrap();
+
+        let params = CancelledParams {
+            request_id: Id::String("req".to_string()),
+            reason: Some("user aborted".to_string()),
+        };
+        handle_cancelled(params, MethodContext::for_session(session.clone()))
+            .await
+            .unwrap();
+        assert!(token.is_cancelled());
+
+        // Unknown requests and missing sessions are ignored
+        let unknown = CancelledParams { request_id: Id::Number(9), reason: None };
+        handle_cancelled(unknown.clone(), MethodContext::for_session(session.clone()))
+            .await
+            .unwrap();
+        handle_cancelled(unknown, MethodContext::default()).await.unwrap();
+        assert!(decode_params::<CancelledParams>(CANCELLED, None).is_err());
+    }
+}
@@ -0,0 +1,142 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Implementation of the JSON-RPC "completion/complete" method handler.
+//!
+//! Prompt arguments are completed from the values and suggestions declared by
+//! their templates. Resource templates declare no completion sources, so
+//! references to them complete to nothing.
+
+use std::sync::Arc;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::prompts::{Completion, PromptRegistry};
+use crate::protocol::jsonrpc::error::{ErrorCode, JsonRpcError};
+use crate::protocol::jsonrpc::builder::JsonRpcHandlerBuilder;
+use crate::protocol::jsonrpc::handler::MethodContext;
+
+/// What is being completed.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type")]
+pub enum CompletionReference {
+    /// An argument of a prompt
+    #[serde(rename = "ref/prompt")]
+    Prompt {
+        /// Name of the prompt
+        name: String,
+    },
+
+    /// A variable of a resource template
+    #[serde(rename = "ref/resource")]
+    Resource {
+        /// URI template
+        uri: String,
+    },
+}
+
+/// The argument being completed and its partial value.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CompletionArgument {
+    /// Name of the argument
+    pub name: String,
+
+    /// Value typed so far
+    pub value: String,
+}
+
+/// Request parameters for the completion/complete method.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CompleteParams {
+    /// What is being completed
+    #[serde(rename = "ref")]
+    pub reference: CompletionReference,
+
+    /// The argument being completed
+    pub argument: CompletionArgument,
+}
+
+/// Response for the completion/complete method.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CompleteResult {
+    /// Suggested values
+    pub completion: Completion,
+}
+
+/// Registers the completion/complete method handler with the JSON-RPC handler builder.
+pub fn register_completion_complete_method(builder: &mut JsonRpcHandlerBuilder, registry: Arc<PromptRegistry>) {
+    builder.register_typed("completion/complete", move |params, context| {
+        let registry = registry.clone();
+        async move { handle_completion_complete(&registry, params, context).await }
+    });
+}
+
+/// Handles the completion/complete method call.
+async fn handle_completion_complete(
+    registry: &PromptRegistry,
+    params: CompleteParams,
+    _context: MethodContext,
+) -> Result<CompleteResult, JsonRpcError> {
+    let completion = match &params.reference {
+        CompletionReference::Prompt { name } => registry
+            .complete(name, &params.argument.name, &params.argument.value)
+            .map_err(|e| JsonRpcError::new(ErrorCode::InvalidParams, e.to_string()))?,
+        CompletionReference::Resource { .. } => Completion::default(),
+    };
+
+    Ok(CompleteResult { completion })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prompts::PromptTemplate;
+    use crate::protocol::jsonrpc::handler::MethodResult;
+    use crate::protocol::jsonrpc::typed::{decode_params, encode_result};
+    use serde_json::{json, Value};
+
+    fn registry() -> PromptRegistry {
+        let registry = PromptRegistry::new();
+        let source = "[[arguments]]\nname = \"length\"\ntype = \"enum\"\nvalues = [\"short\", \"long\"]\n[[messages]]\nrole = \"user\"\ntext = \"{{length}}\"";
+        registry.insert(PromptTemplate::parse("summarize", source).unwrap());
+        registry
+    }
+
+    /// Calls completion/complete the way the typed registration does.
+    async fn complete(params: Value) -> MethodResult {
+        let params = decode_params("completion/complete", Some(params))?;
+        let result = handle_completion_complete(&registry(), params, MethodContext::default()).await?;
+        encode_result("completion/complete", &result)
+    }
+
+    #[tokio::test]
+    async fn test_completes_prompt_arguments() {
+        let params = json!({
+            "ref": {"type": "ref/prompt", "name": "summarize"},
+            "argument": {"name": "length", "value": "s"}
+        });
+        let result = complete(params).await.unwrap();
+        assert_eq!(result, json!({"completion": {"values": ["short"], "total": 1, "hasMore": false}}));
+
+        let params = json!({
+            "ref": {"type": "ref/resource", "uri": "mauka://config/{section}"},
+            "argument": {"name": "section", "value": ""}
+        });
+        let result = complete(params).await.unwrap();
+        assert_eq!(result["completion"]["values"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_prompts() {
+        let params = json!({
+            "ref": {"type": "ref/prompt", "name": "nope"},
+            "argument": {"name": "length", "value": ""}
+        });
+        let error = complete(params).await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidParams.code());
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Implementation of the OpenRPC "rpc.discover" method handler.
+//!
+//! The server answers with an [`OpenRpcDocument`] describing the methods it
+//! serves at the time of the call. The tools `tools/call` can run are listed
+//! with their schemas under the [`TOOLS_EXTENSION`] extension, as the
+//! document has no place for them otherwise.
+
+use std::sync::Arc;
+
+use crate::protocol::jsonrpc::builder::JsonRpcHandlerBuilder;
+use crate::protocol::jsonrpc::error::JsonRpcError;
+use crate::protocol::jsonrpc::handler::MethodContext;
+use crate::protocol::jsonrpc::methods::initialize::server_info;
+use crate::protocol::jsonrpc::openrpc::{Info, OpenRpcDocument, DISCOVER};
+use crate::protocol::jsonrpc::table::MethodTable;
+use crate::protocol::jsonrpc::typed::EmptyParams;
+use crate::tools::ToolRegistry;
+
+/// Extension listing the MCP tool catalog.
+pub const TOOLS_EXTENSION: &str = "x-mcp-tools";
+
+/// Registers the rpc.discover method handler with the JSON-RPC handler builder.
+pub fn register_discover_method(builder: &mut JsonRpcHandlerBuilder, tools: Arc<ToolRegistry>) {
+    builder.register_typed(DISCOVER, move |params, context| {
+        let tools = tools.clone();
+        async move { handle_discover(tools, params, context).await }
+    });
+}
+
+/// Describes `methods` and the tools in `tools` as an OpenRPC document.
+pub fn discover_document(methods: &MethodTable, tools: &ToolRegistry) -> OpenRpcDocument {
+    let server = server_info();
+    let info = Info::new(server.title.unwrap_or(server.name), server.version);
+    let tools = serde_json::to_value(tools.definitions()).unwrap_or_default();
+    OpenRpcDocument::describe(methods, info).with_extension(TOOLS_EXTENSION, tools)
+}
+
+/// Handles the rpc.discover method call.
+async fn handle_discover(
+    tools: Arc<ToolRegistry>,
+    _params: EmptyParams,
+    context: MethodContext,
+) -> Result<OpenRpcDocument, JsonRpcError> {
+    let methods = context
+        .methods
+        .ok_or_else(|| JsonRpcError::internal_error("Method table unavailable"))?;
+    Ok(discover_document(&methods, &tools))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::jsonrpc::handler::JsonRpcHandler;
+    use crate::protocol::jsonrpc::types::Response;
+    use crate::tools::ToolDefinition;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_discover_describes_methods_and_tools() {
+        let tools = Arc::new(ToolRegistry::new());
+        tools.register_fn(ToolDefinition::new("echo", json!({"type": "object"})), |arguments, _ctx| async move {
+            Ok(crate::tools::CallToolResult::structured(arguments))
+        });
+        let mut builder = JsonRpcHandler::builder();
+        register_discover_method(&mut builder, tools);
+        builder.register_method("untyped", |_params, _ctx| async { Ok(json!(1)) });
+        let handler = builder.build();
+
+        let output = handler.handle_request(r#"{"jsonrpc":"2.0","method":"rpc.discover","id":1}"#, None).await;
+        let response: Response = serde_json::from_str(&output).unwrap();
+        let document: OpenRpcDocument = serde_json::from_value(response.result.unwrap()).unwrap();
+        assert!(document.method(DISCOVER).is_some());
+        assert!(document.method("untyped").is_some());
+        assert_eq!(document.extensions[TOOLS_EXTENSION][0]["name"], "echo");
+        assert_eq!(document.info.version, env!("CARGO_PKG_VERSION"));
+
+        assert!(handle_discover(Arc::new(ToolRegistry::new()), EmptyParams {}, MethodContext::default())
+            .await
+            .is_err());
+    }
+}
@@ -9,9 +9,31 @@
 //! This module contains implementations of standard and custom method handlers
 //! for the JSON-RPC 2.0 protocol used by Mauka MCP.
 
+pub mod cancelled;
+pub mod completion_complete;
+pub mod discover;
 pub mod initialize;
+pub mod ping;
+pub mod prompts_get;
+pub mod prompts_list;
+pub mod resources_list;
+pub mod resources_read;
+pub mod resources_subscribe;
+pub mod shutdown;
+pub mod tools_call;
 pub mod tools_list;
 
 // Re-exports
+pub use cancelled::register_cancelled_notification;
+pub use completion_complete::register_completion_complete_method;
+pub use discover::register_discover_method;
 pub use initialize::register_initialize_method;
+pub use ping::register_ping_method;
+pub use prompts_get::register_prompts_get_method;
+pub use prompts_list::register_prompts_list_method;
+pub use resources_list::register_resources_list_methods;
+pub use resources_read::register_resources_read_method;
+pub use resources_subscribe::register_resources_subscribe_methods;
+pub use shutdown::register_shutdown_method;
+pub use tools_call::register_tools_call_method;
 pub use tools_list::register_tools_list_method;
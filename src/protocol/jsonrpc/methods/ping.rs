@@ -0,0 +1,37 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Implementation of the MCP "ping" method handler.
+//!
+//! Either side may ping the other to check that the connection is alive; the
+//! server answers with an empty result in every lifecycle phase.
+
+use crate::protocol::jsonrpc::error::JsonRpcError;
+use crate::protocol::jsonrpc::builder::JsonRpcHandlerBuilder;
+use crate::protocol::jsonrpc::handler::MethodContext;
+use crate::protocol::jsonrpc::typed::{EmptyParams, EmptyResult};
+use crate::protocol::lifecycle::PING;
+
+/// Registers the ping method handler with the JSON-RPC handler builder.
+pub fn register_ping_method(builder: &mut JsonRpcHandlerBuilder) {
+    builder.register_typed(PING, handle_ping);
+}
+
+/// Handles the ping method call.
+async fn handle_ping(_params: EmptyParams, _context: MethodContext) -> Result<EmptyResult, JsonRpcError> {
+    Ok(EmptyResult {})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_ping_returns_empty_result() {
+        let result = handle_ping(EmptyParams {}, MethodContext::default()).await.unwrap();
+        assert_eq!(serde_json::to_value(result).unwrap(), serde_json::json!({}));
+    }
+}
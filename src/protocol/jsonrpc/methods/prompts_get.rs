@@ -0,0 +1,88 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Implementation of the JSON-RPC "prompts/get" method handler.
+//!
+//! Unknown prompts and argument values that are missing or do not match their
+//! declared type are reported as invalid parameters, as the MCP specification
+//! requires.
+
+use std::sync::Arc;
+
+use crate::prompts::{GetPromptParams, GetPromptResult, PromptRegistry};
+use crate::protocol::jsonrpc::error::{ErrorCode, JsonRpcError};
+use crate::protocol::jsonrpc::builder::JsonRpcHandlerBuilder;
+use crate::protocol::jsonrpc::handler::MethodContext;
+
+/// Registers the prompts/get method handler with the JSON-RPC handler builder.
+pub fn register_prompts_get_method(builder: &mut JsonRpcHandlerBuilder, registry: Arc<PromptRegistry>) {
+    builder.register_typed("prompts/get", move |params, context| {
+        let registry = registry.clone();
+        async move { handle_prompts_get(&registry, params, context).await }
+    });
+}
+
+/// Handles the prompts/get method call.
+async fn handle_prompts_get(
+    registry: &PromptRegistry,
+    params: GetPromptParams,
+    context: MethodContext,
+) -> Result<GetPromptResult, JsonRpcError> {
+    let result = registry
+        .get(&params.name, &params.arguments)
+        .map_err(|e| JsonRpcError::new(ErrorCode::InvalidParams, e.to_string()))?;
+    if let Some(session) = &context.session {
+        registry.observe(session);
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prompts::PromptTemplate;
+    use crate::protocol::jsonrpc::handler::MethodResult;
+    use crate::protocol::jsonrpc::typed::{decode_params, encode_result};
+    use serde_json::{json, Value};
+
+    fn registry() -> PromptRegistry {
+        let registry = PromptRegistry::new();
+        let source = "[[arguments]]\nname = \"topic\"\nrequired = true\n[[messages]]\nrole = \"user\"\ntext = \"Research {{topic}}\"";
+        registry.insert(PromptTemplate::parse("research", source).unwrap());
+        registry
+    }
+
+    /// Calls prompts/get the way the typed registration does.
+    async fn get(params: Option<Value>) -> MethodResult {
+        let params = decode_params("prompts/get", params)?;
+        let result = handle_prompts_get(&registry(), params, MethodContext::default()).await?;
+        encode_result("prompts/get", &result)
+    }
+
+    #[tokio::test]
+    async fn test_prompts_get_renders() {
+        let params = json!({"name": "research", "arguments": {"topic": "tides"}});
+        let result = get(Some(params)).await.unwrap();
+        assert_eq!(
+            result,
+            json!({"messages": [{"role": "user", "content": {"type": "text", "text": "Research tides"}}]})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prompts_get_rejects_bad_requests() {
+        for params in [
+            None,
+            Some(json!({"name": "nope"})),
+            Some(json!({"name": "research"})),
+            Some(json!({"name": "research", "arguments": {"topic": 1}})),
+        ] {
+            let error = get(params).await.unwrap_err();
+            assert_eq!(error.code, ErrorCode::InvalidParams.code());
+        }
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Implementation of the JSON-RPC "prompts/list" method handler.
+//!
+//! Prompts are listed one page at a time from the server's [`PromptRegistry`];
+//! a response carries a `nextCursor` while more prompts remain. The calling
+//! session is notified when the list changes afterwards.
+
+use std::sync::Arc;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::prompts::{Prompt, PromptRegistry};
+use crate::protocol::jsonrpc::error::{ErrorCode, JsonRpcError};
+use crate::protocol::jsonrpc::builder::JsonRpcHandlerBuilder;
+use crate::protocol::jsonrpc::handler::MethodContext;
+
+/// Request parameters for the prompts/list method.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PromptsListParams {
+    /// Opaque pagination cursor from a previous response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// Response for the prompts/list method.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptsListResult {
+    /// Prompts on this page
+    pub prompts: Vec<Prompt>,
+
+    /// Cursor for the next page, if there are more prompts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Registers the prompts/list method handler with the JSON-RPC handler builder.
+pub fn register_prompts_list_method(builder: &mut JsonRpcHandlerBuilder, registry: Arc<PromptRegistry>) {
+    builder.register_typed("prompts/list", move |params, context| {
+        let registry = registry.clone();
+        async move { handle_prompts_list(&registry, params, context).await }
+    });
+}
+
+/// Handles the prompts/list method call.
+async fn handle_prompts_list(
+    registry: &PromptRegistry,
+    params: PromptsListParams,
+    context: MethodContext,
+) -> Result<PromptsListResult, JsonRpcError> {
+    let page = registry.list(params.cursor.as_deref()).ok_or_else(|| {
+        JsonRpcError::new(
+            ErrorCode::InvalidParams,
+            format!("Invalid prompts/list cursor: {}", params.cursor.as_deref().unwrap_or_default()),
+        )
+    })?;
+    if let Some(session) = &context.session {
+        registry.observe(session);
+    }
+
+    Ok(PromptsListResult {
+        prompts: page.items,
+        next_cursor: page.next_cursor,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prompts::PromptTemplate;
+    use crate::protocol::Session;
+    use serde_json::json;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn test_prompts_list_observes_session() {
+        let registry = PromptRegistry::new().with_page_size(1);
+        for name in ["b", "a"] {
+            registry.insert(PromptTemplate::parse(name, "[[messages]]\nrole = \"user\"\ntext = \"hi\"").unwrap());
+        }
+        let session = Arc::new(Session::new("test"));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        session.attach_outbound(tx);
+
+        let result = handle_prompts_list(&registry, PromptsListParams::default(), MethodContext::for_session(session.clone()))
+            .await
+            .unwrap();
+        assert_eq!(serde_json::to_value(&result.prompts).unwrap(), json!([{"name": "a"}]));
+        assert!(result.next_cursor.is_some());
+
+        assert_eq!(registry.notify_list_changed(), 1);
+        assert!(rx.try_recv().unwrap().contains("notifications/prompts/list_changed"));
+
+        let params = PromptsListParams { cursor: Some("%".to_string()) };
+        let error = handle_prompts_list(&registry, params, MethodContext::default())
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidParams.code());
+    }
+}
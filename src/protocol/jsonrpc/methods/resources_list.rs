@@ -0,0 +1,155 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Implementation of the JSON-RPC "resources/list" and
+//! "resources/templates/list" method handlers.
+//!
+//! Both listings are served from the server's [`ResourceRegistry`] one page at
+//! a time; a response carries a `nextCursor` while more entries remain.
+
+use std::sync::Arc;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::protocol::jsonrpc::error::{ErrorCode, JsonRpcError};
+use crate::protocol::jsonrpc::builder::JsonRpcHandlerBuilder;
+use crate::protocol::jsonrpc::handler::MethodContext;
+use crate::resources::{Resource, ResourceRegistry, ResourceTemplate};
+
+/// Request parameters for the listing methods.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ResourcesListParams {
+    /// Opaque pagination cursor from a previous response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// Response for the resources/list method.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourcesListResult {
+    /// Resources on this page
+    pub resources: Vec<Resource>,
+
+    /// Cursor for the next page, if there are more resources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Response for the resources/templates/list method.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceTemplatesListResult {
+    /// Templates on this page
+    pub resource_templates: Vec<ResourceTemplate>,
+
+    /// Cursor for the next page, if there are more templates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Registers the resources/list and resources/templates/list method handlers.
+pub fn register_resources_list_methods(builder: &mut JsonRpcHandlerBuilder, registry: Arc<ResourceRegistry>) {
+    let resources = registry.clone();
+    builder.register_typed("resources/list", move |params, context| {
+        let registry = resources.clone();
+        async move { handle_resources_list(&registry, params, context).await }
+    });
+    builder.register_typed("resources/templates/list", move |params, context| {
+        let registry = registry.clone();
+        async move { handle_resource_templates_list(&registry, params, context).await }
+    });
+}
+
+/// Handles the resources/list method call.
+async fn handle_resources_list(
+    registry: &ResourceRegistry,
+    params: ResourcesListParams,
+    _context: MethodContext,
+) -> Result<ResourcesListResult, JsonRpcError> {
+    let page = registry
+        .list(params.cursor.as_deref())
+        .ok_or_else(|| invalid_cursor("resources/list", params.cursor.as_deref()))?;
+
+    Ok(ResourcesListResult {
+        resources: page.items,
+        next_cursor: page.next_cursor,
+    })
+}
+
+/// Handles the resources/templates/list method call.
+async fn handle_resource_templates_list(
+    registry: &ResourceRegistry,
+    params: ResourcesListParams,
+    _context: MethodContext,
+) -> Result<ResourceTemplatesListResult, JsonRpcError> {
+    let page = registry
+        .templates(params.cursor.as_deref())
+        .ok_or_else(|| invalid_cursor("resources/templates/list", params.cursor.as_deref()))?;
+
+    Ok(ResourceTemplatesListResult {
+        resource_templates: page.items,
+        next_cursor: page.next_cursor,
+    })
+}
+
+fn invalid_cursor(method: &str, cursor: Option<&str>) -> JsonRpcError {
+    JsonRpcError::new(
+        ErrorCode::InvalidParams,
+        format!("Invalid {method} cursor: {}", cursor.unwrap_or_default()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::ResourceContents;
+
+    fn registry() -> ResourceRegistry {
+        let registry = ResourceRegistry::new().with_page_size(1);
+        for uri in ["test://b", "test://a"] {
+            registry.register_fn(Resource::new(uri, "test"), move |_ctx| async move {
+                Ok(vec![ResourceContents::text(uri, "text/plain", "x")])
+            });
+        }
+        registry
+            .register_template_fn(ResourceTemplate::new("test://{name}", "named"), |_, _, _| async { Ok(None) })
+            .unwrap();
+        registry
+    }
+
+    #[tokio::test]
+    async fn test_resources_list_pages() {
+        let registry = registry();
+        let first = handle_resources_list(&registry, ResourcesListParams::default(), MethodContext::default())
+            .await
+            .unwrap();
+        assert_eq!(first.resources[0].uri, "test://a");
+
+        let params = ResourcesListParams { cursor: first.next_cursor };
+        let second = handle_resources_list(&registry, params, MethodContext::default()).await.unwrap();
+        assert_eq!(second.resources[0].uri, "test://b");
+        assert!(second.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resource_templates_list() {
+        let result = handle_resource_templates_list(&registry(), ResourcesListParams::default(), MethodContext::default())
+            .await
+            .unwrap();
+        assert_eq!(result.resource_templates[0].uri_template, "test://{name}");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_cursor_is_rejected() {
+        let params = ResourcesListParams { cursor: Some("%%%".to_string()) };
+        let error = handle_resources_list(&registry(), params, MethodContext::default())
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidParams.code());
+    }
+}
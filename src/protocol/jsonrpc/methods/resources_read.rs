@@ -0,0 +1,107 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Implementation of the JSON-RPC "resources/read" method handler.
+//!
+//! Unknown URIs are reported with the MCP "resource not found" error code
+//! (-32002); a provider that fails to read its resource produces an internal
+//! error.
+
+use std::sync::Arc;
+
+use crate::protocol::jsonrpc::error::{ErrorCode, JsonRpcError};
+use crate::protocol::jsonrpc::builder::JsonRpcHandlerBuilder;
+use crate::protocol::jsonrpc::handler::MethodContext;
+use crate::resources::{ReadResourceResult, ResourceReadOutcome, ResourceRegistry, ResourceUriParams};
+
+/// Registers the resources/read method handler with the JSON-RPC handler builder.
+pub fn register_resources_read_method(builder: &mut JsonRpcHandlerBuilder, registry: Arc<ResourceRegistry>) {
+    builder.register_typed("resources/read", move |params, context| {
+        let registry = registry.clone();
+        async move { handle_resources_read(&registry, params, context).await }
+    });
+}
+
+/// Handles the resources/read method call.
+async fn handle_resources_read(
+    registry: &ResourceRegistry,
+    params: ResourceUriParams,
+    context: MethodContext,
+) -> Result<ReadResourceResult, JsonRpcError> {
+    match registry.read(&params.uri, context).await {
+        ResourceReadOutcome::Read(contents) => Ok(ReadResourceResult { contents }),
+        ResourceReadOutcome::NotFound => Err(resource_not_found(&params.uri)),
+        ResourceReadOutcome::Failed(e) => {
+            tracing::debug!(uri = %params.uri, error = %e, "Resource read failed");
+            Err(JsonRpcError::internal_error(format!("Failed to read {}: {}", params.uri, e)))
+        }
+    }
+}
+
+/// Builds the error for a URI no resource is served at.
+pub(crate) fn resource_not_found(uri: &str) -> JsonRpcError {
+    JsonRpcError::with_data(
+        ErrorCode::ResourceNotFound,
+        "Resource not found",
+        serde_json::json!({ "uri": uri }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::MaukaError;
+    use crate::protocol::jsonrpc::handler::MethodResult;
+    use crate::protocol::jsonrpc::typed::{decode_params, encode_result};
+    use crate::resources::{Resource, ResourceContents};
+    use serde_json::{json, Value};
+
+    fn registry() -> ResourceRegistry {
+        let registry = ResourceRegistry::new();
+        registry.register_fn(Resource::new("test://hello", "hello"), |_ctx| async {
+            Ok(vec![ResourceContents::text("test://hello", "text/plain", "hi")])
+        });
+        registry.register_fn(Resource::new("test://broken", "broken"), |_ctx| async {
+            Err(MaukaError::Custom("disk on fire".to_string()))
+        });
+        registry
+    }
+
+    /// Calls resources/read the way the typed registration does.
+    async fn read(params: Option<Value>) -> MethodResult {
+        let params = decode_params("resources/read", params)?;
+        let result = handle_resources_read(&registry(), params, MethodContext::default()).await?;
+        encode_result("resources/read", &result)
+    }
+
+    #[tokio::test]
+    async fn test_read_returns_contents() {
+        let result = read(Some(json!({"uri": "test://hello"}))).await.unwrap();
+        assert_eq!(
+            result,
+            json!({"contents": [{"uri": "test://hello", "mimeType": "text/plain", "text": "hi"}]})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_uri_is_resource_not_found() {
+        let error = read(Some(json!({"uri": "test://nope"}))).await.unwrap_err();
+        assert_eq!(error.code, -32002);
+        assert_eq!(error.data, Some(json!({"uri": "test://nope"})));
+    }
+
+    #[tokio::test]
+    async fn test_failures_and_bad_params() {
+        let error = read(Some(json!({"uri": "test://broken"}))).await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::InternalError.code());
+        assert!(error.message.contains("disk on fire"));
+
+        for params in [None, Some(json!({})), Some(json!({"uri": 1}))] {
+            let error = read(params).await.unwrap_err();
+            assert_eq!(error.code, ErrorCode::InvalidParams.code());
+        }
+    }
+}
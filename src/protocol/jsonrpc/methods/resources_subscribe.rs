@@ -0,0 +1,127 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Implementation of the JSON-RPC "resources/subscribe" and
+//! "resources/unsubscribe" method handlers.
+//!
+//! Subscriptions belong to the session the request arrived on; once subscribed,
+//! the session receives `notifications/resources/updated` whenever the
+//! resource changes. Requests without a session cannot subscribe.
+
+use std::sync::Arc;
+
+use super::resources_read::resource_not_found;
+use crate::protocol::jsonrpc::error::{ErrorCode, JsonRpcError};
+use crate::protocol::jsonrpc::builder::JsonRpcHandlerBuilder;
+use crate::protocol::jsonrpc::handler::MethodContext;
+use crate::protocol::jsonrpc::typed::EmptyResult;
+use crate::resources::{ResourceRegistry, ResourceUriParams};
+
+/// Registers the resources/subscribe and resources/unsubscribe method handlers.
+pub fn register_resources_subscribe_methods(builder: &mut JsonRpcHandlerBuilder, registry: Arc<ResourceRegistry>) {
+    let subscriptions = registry.clone();
+    builder.register_typed("resources/subscribe", move |params, context| {
+        let registry = subscriptions.clone();
+        async move { handle_resources_subscribe(&registry, params, context).await }
+    });
+    builder.register_typed("resources/unsubscribe", move |params, context| {
+        let registry = registry.clone();
+        async move { handle_resources_unsubscribe(&registry, params, context).await }
+    });
+}
+
+/// Handles the resources/subscribe method call.
+async fn handle_resources_subscribe(
+    registry: &ResourceRegistry,
+    params: ResourceUriParams,
+    context: MethodContext,
+) -> Result<EmptyResult, JsonRpcError> {
+    let Some(session) = context.session else {
+        return Err(no_session("resources/subscribe"));
+    };
+    if !registry.contains(&params.uri) {
+        return Err(resource_not_found(&params.uri));
+    }
+
+    registry.subscribe(&params.uri, &session);
+    tracing::debug!(uri = %params.uri, session_id = session.id(), "Resource subscribed");
+    Ok(EmptyResult {})
+}
+
+/// Handles the resources/unsubscribe method call.
+///
+/// Unsubscribing from a resource the session is not subscribed to succeeds.
+async fn handle_resources_unsubscribe(
+    registry: &ResourceRegistry,
+    params: ResourceUriParams,
+    context: MethodContext,
+) -> Result<EmptyResult, JsonRpcError> {
+    let Some(session) = context.session else {
+        return Err(no_session("resources/unsubscribe"));
+    };
+
+    registry.unsubscribe(&params.uri, session.id());
+    Ok(EmptyResult {})
+}
+
+fn no_session(method: &str) -> JsonRpcError {
+    JsonRpcError::new(
+        ErrorCode::InvalidRequest,
+        format!("Invalid Request: {method} requires a session"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::Session;
+    use crate::resources::{Resource, ResourceContents};
+    use tokio::sync::mpsc;
+
+    fn uri(uri: &str) -> ResourceUriParams {
+        ResourceUriParams { uri: uri.to_string() }
+    }
+
+    fn registry() -> ResourceRegistry {
+        let registry = ResourceRegistry::new();
+        registry.register_fn(Resource::new("test://hello", "hello"), |_ctx| async {
+            Ok(vec![ResourceContents::text("test://hello", "text/plain", "hi")])
+        });
+        registry
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_then_unsubscribe() {
+        let registry = registry();
+        let session = Arc::new(Session::new("test"));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        session.attach_outbound(tx);
+        let context = MethodContext::for_session(session.clone());
+        let params = uri("test://hello");
+
+        handle_resources_subscribe(&registry, params.clone(), context.clone()).await.unwrap();
+        assert_eq!(registry.notify_updated("test://hello"), 1);
+        assert!(rx.try_recv().unwrap().contains("notifications/resources/updated"));
+
+        handle_resources_unsubscribe(&registry, params, context).await.unwrap();
+        assert_eq!(registry.notify_updated("test://hello"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_requires_known_resource_and_session() {
+        let registry = registry();
+        let context = MethodContext::for_session(Arc::new(Session::new("test")));
+        let error = handle_resources_subscribe(&registry, uri("test://nope"), context)
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::ResourceNotFound.code());
+
+        let error = handle_resources_subscribe(&registry, uri("test://hello"), MethodContext::default())
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidRequest.code());
+    }
+}
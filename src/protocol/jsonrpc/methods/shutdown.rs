@@ -0,0 +1,65 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Implementation of the "shutdown" method handler.
+//!
+//! `shutdown` ends a session gracefully: the session stops admitting new
+//! requests, and the response is sent only once every request already in
+//! flight on it has finished. The client can then close the connection
+//! knowing that no work is lost.
+
+use tracing::info;
+
+use crate::protocol::jsonrpc::error::JsonRpcError;
+use crate::protocol::jsonrpc::builder::JsonRpcHandlerBuilder;
+use crate::protocol::jsonrpc::handler::MethodContext;
+use crate::protocol::jsonrpc::typed::{EmptyParams, EmptyResult};
+use crate::protocol::lifecycle::{LifecyclePhase, SHUTDOWN};
+
+/// Registers the shutdown method handler with the JSON-RPC handler builder.
+pub fn register_shutdown_method(builder: &mut JsonRpcHandlerBuilder) {
+    builder.register_typed(SHUTDOWN, handle_shutdown);
+}
+
+/// Handles the shutdown method call.
+async fn handle_shutdown(_params: EmptyParams, context: MethodContext) -> Result<EmptyResult, JsonRpcError> {
+    if let Some(session) = &context.session {
+        session.update(|state| state.phase = LifecyclePhase::ShuttingDown);
+        info!(
+            session_id = session.id(),
+            in_flight = session.in_flight(),
+            "Session shutting down, draining in-flight requests"
+        );
+        session.drain().await;
+    }
+
+    Ok(EmptyResult {})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::session::Session;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_in_flight_requests() {
+        let session = Arc::new(Session::new("test"));
+        session.update(|state| state.phase = LifecyclePhase::Initialized);
+
+        let in_flight = session.track();
+        let shutdown = handle_shutdown(EmptyParams {}, MethodContext::for_session(session.clone()));
+        tokio::pin!(shutdown);
+
+        assert!(tokio::time::timeout(Duration::from_millis(50), &mut shutdown).await.is_err());
+        assert_eq!(session.phase(), LifecyclePhase::ShuttingDown);
+
+        drop(in_flight);
+        let result = tokio::time::timeout(Duration::from_secs(5), shutdown).await.unwrap();
+        assert_eq!(result.unwrap(), EmptyResult {});
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Implementation of the JSON-RPC "tools/call" method handler.
+//!
+//! The handler dispatches to a tool in the server's [`ToolRegistry`] by name.
+//! As the MCP specification requires, failures of the tool itself are returned
+//! as a successful response whose result has `isError` set; only malformed
+//! requests and unknown tools produce JSON-RPC errors.
+
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::protocol::jsonrpc::error::{ErrorCode, JsonRpcError};
+use crate::protocol::jsonrpc::builder::JsonRpcHandlerBuilder;
+use crate::protocol::jsonrpc::handler::MethodContext;
+use crate::tools::{CallToolParams, CallToolResult, ToolCallOutcome, ToolRegistry};
+
+/// Registers the tools/call method handler with the JSON-RPC handler builder.
+pub fn register_tools_call_method(builder: &mut JsonRpcHandlerBuilder, registry: Arc<ToolRegistry>) {
+    builder.register_typed("tools/call", move |params, context| {
+        let registry = registry.clone();
+        async move { handle_tools_call(&registry, params, context).await }
+    });
+}
+
+/// Handles the tools/call method call.
+async fn handle_tools_call(
+    registry: &ToolRegistry,
+    params: CallToolParams,
+    context: MethodContext,
+) -> Result<CallToolResult, JsonRpcError> {
+    let arguments = params.arguments.map(Value::Object);
+    match registry.call(&params.name, arguments, context).await {
+        ToolCallOutcome::Completed(result) => Ok(result),
+        ToolCallOutcome::UnknownTool => Err(JsonRpcError::with_data(
+            ErrorCode::InvalidParams,
+            format!("Unknown tool: {}", params.name),
+            serde_json::json!({ "tool": params.name }),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::http::HttpError;
+    use crate::protocol::jsonrpc::handler::MethodResult;
+    use crate::protocol::jsonrpc::typed::{decode_params, encode_result};
+    use crate::tools::ToolDefinition;
+    use serde_json::json;
+
+    fn registry() -> ToolRegistry {
+        let registry = ToolRegistry::new();
+        registry.register_fn(
+            ToolDefinition::new("add", json!({"type": "object"})),
+            |arguments, _ctx| async move {
+                let sum = arguments["a"].as_i64().unwrap_or(0) + arguments["b"].as_i64().unwrap_or(0);
+                Ok(CallToolResult::structured(json!({ "sum": sum })))
+            },
+        );
+        registry.register_fn(
+            ToolDefinition::new("fail", json!({"type": "object"})),
+            |_arguments, _ctx| async move {
+                Err(HttpError::RequestTimeout(std::time::Duration::from_secs(1)).into())
+            },
+        );
+        registry
+    }
+
+    /// Calls tools/call the way the typed registration does.
+    async fn call(params: Option<Value>) -> MethodResult {
+        let params = decode_params("tools/call", params)?;
+        let result = handle_tools_call(&registry(), params, MethodContext::default()).await?;
+        encode_result("tools/call", &result)
+    }
+
+    #[tokio::test]
+    async fn test_tools_call_returns_content() {
+        let params = json!({"name": "add", "arguments": {"a": 2, "b": 3}});
+        let result = call(Some(params)).await.unwrap();
+
+        assert_eq!(result["isError"], json!(false));
+        assert_eq!(result["content"][0]["type"], "text");
+        assert_eq!(result["structuredContent"], json!({"sum": 5}));
+    }
+
+    #[tokio::test]
+    async fn test_tool_failure_is_a_result_not_an_error() {
+        let params = json!({"name": "fail"});
+        let result = call(Some(params)).await.unwrap();
+
+        assert_eq!(result["isError"], json!(true));
+        assert!(result["content"][0]["text"].as_str().unwrap().contains("timed out"));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_tool_is_invalid_params() {
+        let params = json!({"name": "missing"});
+        let error = call(Some(params)).await.unwrap_err();
+
+        assert_eq!(error.code, ErrorCode::InvalidParams.code());
+        assert_eq!(error.data, Some(json!({"tool": "missing"})));
+    }
+
+    #[tokio::test]
+    async fn test_malformed_params_are_rejected() {
+        for params in [None, Some(json!({"arguments": {}})), Some(json!({"name": "add", "arguments": [1]}))] {
+            let error = call(params).await.unwrap_err();
+            assert_eq!(error.code, ErrorCode::InvalidParams.code());
+        }
+
+        let error = call(Some(json!({"name": "add", "arguments": [1]}))).await.unwrap_err();
+        assert_eq!(error.data, Some(json!({"path": "$.arguments"})));
+    }
+}
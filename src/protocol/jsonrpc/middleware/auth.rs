@@ -0,0 +1,183 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Bearer token authentication.
+//!
+//! Network transports copy the client's `Authorization` header into the
+//! context under [`AUTHORIZATION_METADATA`]. [`AuthMiddleware`] refuses calls
+//! that do not carry one of the configured bearer tokens with
+//! `-32401 Unauthorized`, and records which token was used under
+//! [`PRINCIPAL_METADATA`] for the calls it lets through. Calls on trusted
+//! transports, such as stdio where the client launched the server itself, and
+//! calls to exempt methods are let through without a token.
+
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+
+use super::{MethodCall, Middleware, Next, AUTHORIZATION_METADATA, PRINCIPAL_METADATA};
+use crate::config::security::AuthConfig;
+use crate::protocol::jsonrpc::error::{ErrorCode, JsonRpcError};
+use crate::protocol::jsonrpc::handler::MethodResult;
+
+/// Authentication scheme expected in the `Authorization` header.
+const BEARER_SCHEME: &str = "bearer";
+
+/// Refuses calls that do not present a known bearer token.
+#[derive(Debug, Clone, Default)]
+pub struct AuthMiddleware {
+    /// Accepted tokens
+    tokens: Vec<String>,
+
+    /// Transports whose calls need no token
+    trusted_transports: HashSet<String>,
+
+    /// Methods that can be called without a token
+    exempt_methods: HashSet<String>,
+}
+
+impl AuthMiddleware {
+    /// Creates a middleware accepting any of `tokens`.
+    pub fn new<I, T>(tokens: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        Self {
+            tokens: tokens.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a middleware from the authentication configuration.
+    pub fn from_config(config: &AuthConfig) -> Self {
+        config
+            .trusted_transports
+            .iter()
+            .fold(Self::new(config.bearer_tokens.iter().cloned()), |auth, transport| {
+                auth.with_trusted_transport(transport.clone())
+            })
+    }
+
+    /// Lets calls arriving on `transport` through without a token.
+    pub fn with_trusted_transport(mut self, transport: impl Into<String>) -> Self {
+        self.trusted_transports.insert(transport.into());
+        self
+    }
+
+    /// Lets calls to `method` through without a token.
+    pub fn with_exempt_method(mut self, method: impl Into<String>) -> Self {
+        self.exempt_methods.insert(method.into());
+        self
+    }
+
+    /// Returns the index of the token `authorization` presents, if it is accepted.
+    fn authenticate(&self, authorization: &str) -> Option<usize> {
+        let (scheme, token) = authorization.trim().split_once(' ')?;
+        if !scheme.eq_ignore_ascii_case(BEARER_SCHEME) {
+            return None;
+        }
+        let token = token.trim();
+        self.tokens
+            .iter()
+            .position(|known| constant_time_eq(known.as_bytes(), token.as_bytes()))
+    }
+}
+
+#[async_trait]
+impl Middleware for AuthMiddleware {
+    async fn handle(&self, mut call: MethodCall<'_>, next: Next<'_>) -> MethodResult {
+        let trusted = call
+            .context
+            .session
+            .as_ref()
+            .is_some_and(|session| self.trusted_transports.contains(session.transport()));
+        if trusted || self.exempt_methods.contains(&call.method) {
+            return next.run(call).await;
+        }
+
+        let authenticated = call
+            .context
+            .metadata
+            .get(AUTHORIZATION_METADATA)
+            .and_then(|authorization| self.authenticate(authorization));
+        let Some(index) = authenticated else {
+            tracing::debug!(method = %call.method, "Refusing unauthenticated call");
+            return Err(JsonRpcError::new(
+                ErrorCode::Unauthorized,
+                "Unauthorized: a valid bearer token is required",
+            ));
+        };
+
+        call.context.metadata.remove(AUTHORIZATION_METADATA);
+        call.context
+            .metadata
+            .insert(PRINCIPAL_METADATA.to_string(), format!("token-{index}"));
+        next.run(call).await
+    }
+}
+
+/// Compares two byte strings in time independent of where they differ.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::jsonrpc::handler::MethodContext;
+    use crate::protocol::jsonrpc::JsonRpcHandler;
+    use crate::protocol::Session;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn handler() -> JsonRpcHandler {
+        let mut builder = JsonRpcHandler::builder();
+        builder.register_method("whoami", |_params, ctx: MethodContext| async move {
+            Ok(json!(ctx.metadata.get(PRINCIPAL_METADATA)))
+        });
+        builder.register_method("ping", |_params, _ctx| async { Ok(json!({})) });
+        builder.add_middleware(
+            AuthMiddleware::new(["alpha", "beta"])
+                .with_trusted_transport("stdio")
+                .with_exempt_method("ping"),
+        );
+        builder.build()
+    }
+
+    async fn call(handler: &JsonRpcHandler, method: &str, context: MethodContext) -> serde_json::Value {
+        let request = json!({"jsonrpc": "2.0", "method": method, "id": 1}).to_string();
+        serde_json::from_str(&handler.handle_request(request, Some(context)).await).unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_requires_known_bearer_token() {
+        let handler = handler();
+        let remote = || MethodContext::for_session(Arc::new(Session::new("http")));
+
+        let response = call(&handler, "whoami", remote().with_metadata(AUTHORIZATION_METADATA, "Bearer beta")).await;
+        assert_eq!(response["result"], json!("token-1"));
+
+        for authorization in [None, Some("Bearer gamma"), Some("Basic alpha"), Some("Bearer alph")] {
+            let context = match authorization {
+                Some(authorization) => remote().with_metadata(AUTHORIZATION_METADATA, authorization),
+                None => remote(),
+            };
+            let response = call(&handler, "whoami", context).await;
+            assert_eq!(response["error"]["code"], json!(ErrorCode::Unauthorized.code()), "{authorization:?}");
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_trusted_transports_and_exempt_methods() {
+        let handler = handler();
+        let local = MethodContext::for_session(Arc::new(Session::new("stdio")));
+        assert_eq!(call(&handler, "whoami", local).await["result"], json!(null));
+
+        let response = call(&handler, "ping", MethodContext::default()).await;
+        assert_eq!(response["result"], json!({}));
+    }
+}
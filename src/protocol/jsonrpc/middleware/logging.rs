@@ -0,0 +1,101 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Request logging.
+//!
+//! [`LoggingMiddleware`] logs each call when it arrives, with its params, and
+//! again when it finishes, with its outcome and latency. Params are logged
+//! with the values of fields that may hold credentials redacted, using the
+//! same rules as the `mauka://config` resource, and are cut off after
+//! [`MAX_LOGGED_PARAMS_LEN`] bytes.
+
+use std::time::Instant;
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::{MethodCall, Middleware, Next};
+use crate::protocol::jsonrpc::handler::MethodResult;
+use crate::resources::config::redact;
+
+/// Longest params string that is logged in full.
+pub const MAX_LOGGED_PARAMS_LEN: usize = 1024;
+
+/// Logs every call and its outcome.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoggingMiddleware;
+
+#[async_trait]
+impl Middleware for LoggingMiddleware {
+    async fn handle(&self, call: MethodCall<'_>, next: Next<'_>) -> MethodResult {
+        let method = call.method.clone();
+        let id = call.id.clone();
+        tracing::info!(
+            method = %method,
+            id = ?id,
+            params = %loggable_params(call.params_value().as_ref()),
+            "Request received"
+        );
+
+        let started = Instant::now();
+        let result = next.run(call).await;
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+        match &result {
+            Ok(_) => tracing::info!(method = %method, id = ?id, elapsed_ms, "Request completed"),
+            Err(error) => tracing::info!(
+                method = %method,
+                id = ?id,
+                elapsed_ms,
+                code = error.code,
+                message = %error.message,
+                "Request failed"
+            ),
+        }
+        result
+    }
+}
+
+/// Renders params for the log, with sensitive values redacted.
+fn loggable_params(params: Option<&Value>) -> String {
+    let Some(params) = params else {
+        return String::new();
+    };
+    let mut params = params.clone();
+    redact(&mut params);
+
+    let mut rendered = params.to_string();
+    if rendered.len() > MAX_LOGGED_PARAMS_LEN {
+        let mut end = MAX_LOGGED_PARAMS_LEN;
+        while !rendered.is_char_boundary(end) {
+            end -= 1;
+        }
+        rendered.truncate(end);
+        rendered.push_str("...");
+    }
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::config::REDACTED;
+    use serde_json::json;
+
+    #[test]
+    fn test_params_are_redacted_and_truncated() {
+        let params = json!({"name": "fetch_url", "arguments": {"url": "https://example.com", "api_key": "s3cret"}});
+        let rendered = loggable_params(Some(&params));
+        assert!(rendered.contains("https://example.com"));
+        assert!(rendered.contains(REDACTED));
+        assert!(!rendered.contains("s3cret"));
+
+        let long = json!({"text": "é".repeat(MAX_LOGGED_PARAMS_LEN)});
+        let rendered = loggable_params(Some(&long));
+        assert!(rendered.ends_with("..."));
+        assert!(rendered.len() <= MAX_LOGGED_PARAMS_LEN + 3);
+        assert_eq!(loggable_params(None), "");
+    }
+}
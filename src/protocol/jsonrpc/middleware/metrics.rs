@@ -0,0 +1,165 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Per-method call metrics.
+//!
+//! [`MetricsMiddleware`] counts the calls and errors of each method and how
+//! long they took, in a [`MethodMetrics`] that can be shared, for example
+//! with the `mauka://metrics` resource. Besides the time a whole call took,
+//! the time it waited for a dispatch permit and the time its method ran are
+//! counted separately. Calls cancelled by the client never finish and are not
+//! counted.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use super::{MethodCall, Middleware, Next};
+use crate::protocol::jsonrpc::dispatch::CallTimings;
+use crate::protocol::jsonrpc::handler::MethodResult;
+
+/// Counters of one method.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MethodStats {
+    /// Calls that finished
+    pub calls: u64,
+
+    /// Calls that finished with an error
+    pub errors: u64,
+
+    /// Total time spent in the method, in microseconds
+    pub total_latency_us: u64,
+
+    /// Longest call, in microseconds
+    pub max_latency_us: u64,
+
+    /// Total time spent waiting for a dispatch permit, in microseconds
+    pub total_queued_us: u64,
+
+    /// Longest wait for a dispatch permit, in microseconds
+    pub max_queued_us: u64,
+
+    /// Total time the method ran, in microseconds
+    pub total_executing_us: u64,
+
+    /// Longest time the method ran, in microseconds
+    pub max_executing_us: u64,
+}
+
+impl MethodStats {
+    /// Adds a finished call.
+    fn record(&mut self, latency: Duration, timings: &CallTimings, failed: bool) {
+        let latency = micros(latency);
+        let queued = micros(timings.queued());
+        let executing = micros(timings.executing());
+        self.calls += 1;
+        self.errors += u64::from(failed);
+        self.total_latency_us = self.total_latency_us.saturating_add(latency);
+        self.max_latency_us = self.max_latency_us.max(latency);
+        self.total_queued_us = self.total_queued_us.saturating_add(queued);
+        self.max_queued_us = self.max_queued_us.max(queued);
+        self.total_executing_us = self.total_executing_us.saturating_add(executing);
+        self.max_executing_us = self.max_executing_us.max(executing);
+    }
+}
+
+fn micros(duration: Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
+
+/// Call counters of every method called so far.
+#[derive(Debug, Default)]
+pub struct MethodMetrics {
+    methods: Mutex<BTreeMap<String, MethodStats>>,
+}
+
+impl MethodMetrics {
+    /// Creates empty metrics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a finished call of `method` that took `latency` in all.
+    pub fn record(&self, method: &str, latency: Duration, timings: &CallTimings, failed: bool) {
+        let mut methods = self.methods.lock();
+        match methods.get_mut(method) {
+            Some(stats) => stats.record(latency, timings, failed),
+            None => methods.entry(method.to_string()).or_default().record(latency, timings, failed),
+        }
+    }
+
+    /// Returns the counters of every method, by method name.
+    pub fn snapshot(&self) -> BTreeMap<String, MethodStats> {
+        self.methods.lock().clone()
+    }
+}
+
+/// Records every call in a [`MethodMetrics`].
+#[derive(Debug, Clone, Default)]
+pub struct MetricsMiddleware {
+    metrics: Arc<MethodMetrics>,
+}
+
+impl MetricsMiddleware {
+    /// Creates a middleware recording into `metrics`.
+    pub fn new(metrics: Arc<MethodMetrics>) -> Self {
+        Self { metrics }
+    }
+
+    /// Returns the metrics calls are recorded in.
+    pub fn metrics(&self) -> &Arc<MethodMetrics> {
+        &self.metrics
+    }
+}
+
+#[async_trait]
+impl Middleware for MetricsMiddleware {
+    async fn handle(&self, call: MethodCall<'_>, next: Next<'_>) -> MethodResult {
+        let method = call.method.clone();
+        let timings = call.context.timings.clone();
+        let started = Instant::now();
+        let result = next.run(call).await;
+        self.metrics.record(&method, started.elapsed(), &timings, result.is_err());
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_per_method() {
+        let metrics = MethodMetrics::new();
+        let timings = CallTimings::default();
+        timings.record_queued(Duration::from_micros(20));
+        timings.record_executing(Duration::from_micros(70));
+        metrics.record("tools/call", Duration::from_micros(300), &timings, false);
+        metrics.record("tools/call", Duration::from_micros(100), &timings, true);
+        metrics.record("ping", Duration::from_micros(5), &CallTimings::default(), false);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(
+            snapshot["tools/call"],
+            MethodStats {
+                calls: 2,
+                errors: 1,
+                total_latency_us: 400,
+                max_latency_us: 300,
+                total_queued_us: 40,
+                max_queued_us: 20,
+                total_executing_us: 140,
+                max_executing_us: 70,
+            }
+        );
+        assert_eq!(snapshot["ping"].calls, 1);
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Middleware wrapping method dispatch.
+//!
+//! Every validated call passes through the middlewares added with
+//! [`JsonRpcHandlerBuilder::add_middleware`](super::JsonRpcHandlerBuilder::add_middleware) before it reaches its method, in the
+//! order they were added. A middleware receives the [`MethodCall`] and a
+//! [`Next`] continuing the chain. It can inspect or rewrite the call and its
+//! [`MethodContext`], answer on its own without calling [`Next::run`], and
+//! observe the result and how long the rest of the chain took.
+//!
+//! Notifications go through the chain as well, with no id; whatever the
+//! chain returns for them is discarded.
+
+pub mod auth;
+pub mod logging;
+pub mod metrics;
+pub mod rate_limit;
+pub mod trace;
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::value::{to_raw_value, RawValue};
+use serde_json::Value;
+
+use super::handler::{JsonRpcHandler, MethodContext, MethodResult};
+use super::message::RawRequest;
+use super::types::{Id, Request};
+
+pub use auth::AuthMiddleware;
+pub use logging::LoggingMiddleware;
+pub use metrics::{MethodMetrics, MethodStats, MetricsMiddleware};
+pub use rate_limit::RateLimitMiddleware;
+pub use trace::TracingMiddleware;
+
+/// Context metadata key holding the client's socket address, set by network transports.
+pub const REMOTE_ADDR_METADATA: &str = "remote_addr";
+
+/// Context metadata key holding the client's `Authorization` header, set by network transports.
+pub const AUTHORIZATION_METADATA: &str = "authorization";
+
+/// Context metadata key holding the identity [`AuthMiddleware`] authenticated.
+pub const PRINCIPAL_METADATA: &str = "principal";
+
+/// A call on its way to its method.
+///
+/// Params stay JSON text, usually borrowed from the message the call arrived
+/// in, until the method decodes them.
+#[derive(Debug, Clone)]
+pub struct MethodCall<'a> {
+    /// Name of the method
+    pub method: String,
+
+    /// Params of the call, not yet decoded
+    pub params: Option<Cow<'a, RawValue>>,
+
+    /// Id of the request, or `None` for a notification
+    pub id: Option<Id>,
+
+    /// Context the method will run with
+    pub context: MethodContext,
+}
+
+impl MethodCall<'static> {
+    /// Creates the call for `request`, to run with `context`.
+    pub fn from_request(request: Request, context: MethodContext) -> Self {
+        Self {
+            method: request.method,
+            params: request.params.and_then(|params| to_raw_value(&params).ok()).map(Cow::Owned),
+            id: request.id,
+            context,
+        }
+    }
+}
+
+impl<'a> MethodCall<'a> {
+    /// Creates the call for a request still borrowing from its message, to run with `context`.
+    pub fn from_raw(request: RawRequest<'a>, context: MethodContext) -> Self {
+        Self {
+            method: request.method.into_owned(),
+            params: request.params.map(Cow::Borrowed),
+            id: request.id,
+            context,
+        }
+    }
+
+    /// Decodes the params into a [`Value`], for middlewares that look into them.
+    ///
+    /// Returns `None` if the call has no params.
+    pub fn params_value(&self) -> Option<Value> {
+        self.params.as_deref().and_then(|params| serde_json::from_str(params.get()).ok())
+    }
+}
+
+/// Wraps the dispatch of every call.
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// Handles `call`, usually by passing it on with [`Next::run`].
+    async fn handle(&self, call: MethodCall<'_>, next: Next<'_>) -> MethodResult;
+}
+
+/// The rest of the middleware chain, ending at the method itself.
+pub struct Next<'a> {
+    handler: &'a JsonRpcHandler,
+    chain: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    /// Creates the chain `handler` dispatches calls through.
+    pub(super) fn new(handler: &'a JsonRpcHandler, chain: &'a [Arc<dyn Middleware>]) -> Self {
+        Self { handler, chain }
+    }
+
+    /// Passes `call` to the next middleware, or to its method at the end of the chain.
+    pub async fn run(self, call: MethodCall<'_>) -> MethodResult {
+        match self.chain.split_first() {
+            Some((middleware, chain)) => {
+                let next = Next {
+                    handler: self.handler,
+                    chain,
+                };
+                middleware.handle(call, next).await
+            }
+            None => {
+                self.handler
+                    .process_method_call(&call.method, call.params.as_deref(), call.context, call.id.as_ref())
+                    .await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::jsonrpc::error::{ErrorCode, JsonRpcError};
+    use crate::protocol::jsonrpc::types::Response;
+    use parking_lot::Mutex;
+    use serde_json::json;
+
+    /// Records the order calls pass through it and tags the context.
+    struct Recorder {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Middleware for Recorder {
+        async fn handle(&self, mut call: MethodCall<'_>, next: Next<'_>) -> MethodResult {
+            self.log.lock().push(format!("{} before {}", self.name, call.method));
+            call.context.metadata.insert("seen_by".to_string(), self.name.to_string());
+            let result = next.run(call).await;
+            self.log.lock().push(format!("{} after", self.name));
+            result
+        }
+    }
+
+    /// Refuses every call to `secret`.
+    struct Guard;
+
+    #[async_trait]
+    impl Middleware for Guard {
+        async fn handle(&self, call: MethodCall<'_>, next: Next<'_>) -> MethodResult {
+            if call.method == "secret" {
+                return Err(JsonRpcError::new(ErrorCode::Unauthorized, "Unauthorized"));
+            }
+            next.run(call).await
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_chain_order_context_and_short_circuit() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut builder = JsonRpcHandler::builder();
+        builder.register_method("echo", |_params, ctx: MethodContext| async move {
+            Ok(json!(ctx.metadata.get("seen_by")))
+        });
+        builder.register_method("secret", |_params, _ctx| async { Ok(json!("leaked")) });
+        builder.add_middleware(Recorder { name: "outer", log: log.clone() });
+        builder.add_middleware(Recorder { name: "inner", log: log.clone() });
+        builder.add_middleware(Guard);
+        let handler = builder.build();
+
+        let output = handler.handle_request(r#"{"jsonrpc":"2.0","method":"echo","id":1}"#, None).await;
+        let response: Response = serde_json::from_str(&output).unwrap();
+        assert_eq!(response.result, Some(json!("inner")));
+        assert_eq!(*log.lock(), ["outer before echo", "inner before echo", "inner after", "outer after"]);
+
+        let output = handler.handle_request(r#"{"jsonrpc":"2.0","method":"secret","id":2}"#, None).await;
+        let response: Response = serde_json::from_str(&output).unwrap();
+        assert_eq!(response.error.unwrap().code, ErrorCode::Unauthorized.code());
+    }
+}
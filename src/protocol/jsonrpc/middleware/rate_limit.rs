@@ -0,0 +1,204 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Request rate limiting.
+//!
+//! [`RateLimitMiddleware`] keeps a token bucket for the whole server and one
+//! per client IP address, taken from [`REMOTE_ADDR_METADATA`]. Each bucket
+//! refills at its rate and holds up to the rate times the burst factor, so
+//! short bursts above the rate are absorbed. Requests over the limit are
+//! refused with `-32429 Rate limit exceeded`, whose data says after how many
+//! milliseconds a retry can succeed. Notifications are never limited, since
+//! refusing them cannot be reported to the client.
+
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use serde_json::json;
+
+use super::{MethodCall, Middleware, Next, REMOTE_ADDR_METADATA};
+use crate::config::limits::RequestRateLimits;
+use crate::protocol::jsonrpc::error::{ErrorCode, JsonRpcError};
+use crate::protocol::jsonrpc::handler::MethodResult;
+
+/// Number of per-client buckets above which idle ones are dropped.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// A token bucket.
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    /// Tokens available
+    tokens: f64,
+
+    /// When `tokens` was last brought up to date
+    updated: Instant,
+}
+
+/// Refill rate and capacity of a kind of bucket.
+#[derive(Debug, Clone, Copy)]
+struct Limit {
+    /// Tokens added per second
+    rate: f64,
+
+    /// Most tokens a bucket holds
+    capacity: f64,
+}
+
+impl Limit {
+    fn new(rate: f64, burst_factor: f64) -> Self {
+        Self {
+            rate,
+            capacity: (rate * burst_factor).max(1.0),
+        }
+    }
+
+    /// Returns a full bucket.
+    fn bucket(&self, now: Instant) -> Bucket {
+        Bucket {
+            tokens: self.capacity,
+            updated: now,
+        }
+    }
+
+    /// Takes a token from `bucket`, or returns how long until one is available.
+    fn take(&self, bucket: &mut Bucket, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.capacity);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate))
+        }
+    }
+
+    /// Returns true if `bucket` would be full by `now`.
+    fn is_full(&self, bucket: &Bucket, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens + elapsed * self.rate >= self.capacity
+    }
+}
+
+/// Refuses requests above the configured rates.
+#[derive(Debug)]
+pub struct RateLimitMiddleware {
+    /// Limit of the whole server
+    global_limit: Limit,
+
+    /// Bucket of the whole server
+    global: Mutex<Bucket>,
+
+    /// Limit of each client, if any
+    client_limit: Option<Limit>,
+
+    /// Buckets of each client IP address
+    clients: DashMap<String, Bucket>,
+}
+
+impl RateLimitMiddleware {
+    /// Creates a middleware admitting `max_rps` requests per second, with
+    /// bursts of up to `burst_factor` times that.
+    pub fn new(max_rps: f64, burst_factor: f64) -> Self {
+        let global_limit = Limit::new(max_rps, burst_factor);
+        Self {
+            global: Mutex::new(global_limit.bucket(Instant::now())),
+            global_limit,
+            client_limit: None,
+            clients: DashMap::new(),
+        }
+    }
+
+    /// Also limits each client IP address to `max_rps` requests per second,
+    /// with the same burst factor.
+    pub fn with_client_limit(mut self, max_rps: f64) -> Self {
+        let burst_factor = self.global_limit.capacity / self.global_limit.rate;
+        self.client_limit = Some(Limit::new(max_rps, burst_factor));
+        self
+    }
+
+    /// Creates a middleware enforcing the configured request rates.
+    ///
+    /// `window_ms` and `enable_adaptive` do not apply to token buckets and
+    /// are ignored.
+    pub fn from_config(config: &RequestRateLimits) -> Self {
+        Self::new(config.max_rps, config.burst_factor).with_client_limit(config.max_rps_per_ip)
+    }
+
+    /// Takes a token for a request from `client`, or returns how long until
+    /// the request would be admitted.
+    fn admit(&self, client: Option<&str>, now: Instant) -> Result<(), Duration> {
+        if let (Some(limit), Some(client)) = (&self.client_limit, client) {
+            if self.clients.len() > MAX_TRACKED_CLIENTS {
+                self.clients.retain(|_, bucket| !limit.is_full(bucket, now));
+            }
+            let mut bucket = self
+                .clients
+                .entry(client.to_string())
+                .or_insert_with(|| limit.bucket(now));
+            limit.take(&mut bucket, now)?;
+        }
+        self.global_limit.take(&mut self.global.lock(), now)
+    }
+}
+
+#[async_trait]
+impl Middleware for RateLimitMiddleware {
+    async fn handle(&self, call: MethodCall<'_>, next: Next<'_>) -> MethodResult {
+        if call.id.is_none() {
+            return next.run(call).await;
+        }
+
+        let client = call
+            .context
+            .metadata
+            .get(REMOTE_ADDR_METADATA)
+            .map(|addr| addr.parse::<SocketAddr>().map_or_else(|_| addr.clone(), |addr| addr.ip().to_string()));
+        if let Err(retry_after) = self.admit(client.as_deref(), Instant::now()) {
+            tracing::debug!(method = %call.method, client = ?client, "Rate limit exceeded");
+            return Err(JsonRpcError::with_data(
+                ErrorCode::RateLimitExceeded,
+                "Rate limit exceeded",
+                json!({ "retryAfterMs": retry_after.as_millis().max(1) as u64 }),
+            ));
+        }
+        next.run(call).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_absorbs_burst_then_refills() {
+        let limiter = RateLimitMiddleware::new(10.0, 2.0);
+        let start = Instant::now();
+        for _ in 0..20 {
+            assert!(limiter.admit(None, start).is_ok());
+        }
+        let retry_after = limiter.admit(None, start).unwrap_err();
+        assert_eq!(retry_after, Duration::from_millis(100));
+
+        assert!(limiter.admit(None, start + Duration::from_millis(100)).is_ok());
+        assert!(limiter.admit(None, start + Duration::from_millis(100)).is_err());
+    }
+
+    #[test]
+    fn test_clients_are_limited_separately() {
+        let limiter = RateLimitMiddleware::new(1000.0, 2.0).with_client_limit(1.0);
+        let now = Instant::now();
+        assert!(limiter.admit(Some("10.0.0.1"), now).is_ok());
+        assert!(limiter.admit(Some("10.0.0.1"), now).is_ok());
+        assert!(limiter.admit(Some("10.0.0.1"), now).is_err());
+
+        assert!(limiter.admit(Some("10.0.0.2"), now).is_ok());
+        assert!(limiter.admit(None, now).is_ok());
+    }
+}
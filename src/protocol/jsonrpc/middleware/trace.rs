@@ -0,0 +1,53 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Tracing spans around calls.
+//!
+//! Each call runs inside an `rpc` span carrying the method, the request id
+//! and the session, so everything a method logs can be attributed to the call
+//! that caused it. The span records the error code, if any, and the elapsed
+//! time once the call finishes, split into the time spent waiting for a
+//! dispatch permit and the time the method ran.
+
+use std::time::Instant;
+
+use async_trait::async_trait;
+use tracing::field::Empty;
+use tracing::Instrument;
+
+use super::{MethodCall, Middleware, Next};
+use crate::protocol::jsonrpc::handler::MethodResult;
+
+/// Runs every call inside an `rpc` tracing span.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingMiddleware;
+
+#[async_trait]
+impl Middleware for TracingMiddleware {
+    async fn handle(&self, call: MethodCall<'_>, next: Next<'_>) -> MethodResult {
+        let span = tracing::info_span!(
+            "rpc",
+            method = %call.method,
+            id = call.id.as_ref().map(tracing::field::display),
+            session = call.context.session.as_ref().map(|session| session.id().to_string()),
+            error_code = Empty,
+            elapsed_us = Empty,
+            queued_us = Empty,
+            executing_us = Empty,
+        );
+
+        let timings = call.context.timings.clone();
+        let started = Instant::now();
+        let result = next.run(call).instrument(span.clone()).await;
+        span.record("elapsed_us", started.elapsed().as_micros() as u64);
+        span.record("queued_us", timings.queued().as_micros() as u64);
+        span.record("executing_us", timings.executing().as_micros() as u64);
+        if let Err(error) = &result {
+            span.record("error_code", error.code);
+        }
+        result
+    }
+}
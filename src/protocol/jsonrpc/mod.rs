@@ -26,11 +26,9 @@
 //! use mauka_mcp::protocol::jsonrpc::{JsonRpcHandler, Request, Response, ErrorCode};
 //! use std::collections::HashMap;
 //!
-//! // Create a new handler
-//! let mut handler = JsonRpcHandler::new();
-//!
 //! // Register a method handler
-//! handler.register_method("echo", |params, _ctx| async move {
+//! let mut builder = JsonRpcHandler::builder();
+//! builder.register_method("echo", |params, _ctx| async move {
 //!     // Extract parameters
 //!     let message = params.get("message").and_then(|v| v.as_str())
 //!         .unwrap_or("No message provided");
@@ -41,6 +39,9 @@
 //!     }))
 //! });
 //!
+//! // Freeze the methods into a handler
+//! let handler = builder.build();
+//!
 //! // Parse a JSON-RPC request
 //! let request_json = r#"{
 //!     "jsonrpc": "2.0",
@@ -57,18 +58,26 @@
 //! assert!(response.contains(r#""id":1"#));
 //! ```
 
+pub mod builder;
+pub mod dispatch;
 pub mod error;
 pub mod handler;
+pub mod message;
+pub mod middleware;
 pub mod methods;
+pub mod openrpc;
 pub mod setup;
+pub mod table;
+pub mod typed;
 pub mod types;
 pub mod validation;
 pub mod correlation;
 
 // Re-exports
+pub use dispatch::DispatchLimits;
 pub use error::{Error, ErrorCode, JsonRpcError, Result};
 pub use handler::JsonRpcHandler;
-pub use setup::{create_handler, register_standard_methods};
+pub use setup::{create_handler, register_standard_methods, register_standard_middlewares};
 pub use types::{BatchRequest, BatchResponse, Id, Notification, Request, Response};
 pub use validation::validate_request;
 pub use correlation::{CorrelationError, RequestResponseCorrelator};
@@ -0,0 +1,303 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! OpenRPC descriptions of method tables.
+//!
+//! [`OpenRpcDocument::describe`] turns the methods of a [`MethodTable`] into an
+//! [OpenRPC](https://spec.open-rpc.org) document. Methods registered with
+//! types are described with their params, one content descriptor per field,
+//! and their result; other methods are listed with no params and a result of
+//! any shape. Definitions the schemas share are moved to
+//! `components.schemas`, and every [`ErrorCode`] is listed in
+//! `components.errors`. Fields outside the specification can be added as
+//! `x-` extensions.
+
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::error::ErrorCode;
+use super::table::MethodTable;
+
+/// Version of the OpenRPC specification documents follow.
+pub const OPENRPC_VERSION: &str = "1.3.2";
+
+/// Method answering with the server's OpenRPC document, reserved by the OpenRPC specification.
+pub const DISCOVER: &str = "rpc.discover";
+
+/// Prefix of the references to definitions moved to `components.schemas`.
+const COMPONENT_SCHEMAS: &str = "#/components/schemas/";
+
+/// An OpenRPC document.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct OpenRpcDocument {
+    /// Version of the OpenRPC specification the document follows
+    pub openrpc: String,
+
+    /// Metadata about the API
+    pub info: Info,
+
+    /// Methods of the API, ordered by name
+    pub methods: Vec<MethodObject>,
+
+    /// Definitions the methods refer to
+    pub components: Components,
+
+    /// Specification extensions, whose names start with `x-`
+    #[serde(flatten)]
+    pub extensions: BTreeMap<String, Value>,
+}
+
+/// Metadata about an API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Info {
+    /// Title of the API
+    pub title: String,
+
+    /// Version of the API
+    pub version: String,
+
+    /// Description of the API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl Info {
+    /// Creates the metadata of an API.
+    pub fn new(title: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            version: version.into(),
+            description: None,
+        }
+    }
+}
+
+/// A method of an API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct MethodObject {
+    /// Name of the method
+    pub name: String,
+
+    /// Params of the method, by name
+    pub params: Vec<ContentDescriptor>,
+
+    /// Result of the method
+    pub result: ContentDescriptor,
+}
+
+/// A named value and its schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ContentDescriptor {
+    /// Name of the value
+    pub name: String,
+
+    /// Whether the value must be given
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub required: bool,
+
+    /// JSON Schema of the value
+    pub schema: Value,
+}
+
+/// Definitions shared by the methods of a document.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Components {
+    /// Schemas by name
+    pub schemas: BTreeMap<String, Value>,
+
+    /// Errors by name
+    pub errors: BTreeMap<String, ErrorObject>,
+}
+
+/// An error a method may answer with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ErrorObject {
+    /// Error code
+    pub code: i32,
+
+    /// Short description of the error
+    pub message: String,
+}
+
+impl OpenRpcDocument {
+    /// Describes the request methods of `methods`.
+    pub fn describe(methods: &MethodTable, info: Info) -> Self {
+        let mut components = Components::default();
+        let mut names: Vec<_> = methods.names().collect();
+        names.sort_unstable();
+
+        let methods = names
+            .into_iter()
+            .map(|name| match methods.schema(name) {
+                Some(schema) => MethodObject {
+                    name: name.to_string(),
+                    params: params_of(hoist(schema.params.clone(), &mut components.schemas)),
+                    result: ContentDescriptor {
+                        name: "result".to_string(),
+                        required: false,
+                        schema: hoist(schema.result.clone(), &mut components.schemas),
+                    },
+                },
+                None => MethodObject {
+                    name: name.to_string(),
+                    params: Vec::new(),
+                    result: ContentDescriptor {
+                        name: "result".to_string(),
+                        required: false,
+                        schema: json!({}),
+                    },
+                },
+            })
+            .collect();
+
+        components.errors = ErrorCode::ALL
+            .iter()
+            .map(|code| {
+                let error = ErrorObject {
+                    code: code.code(),
+                    message: code.description().to_string(),
+                };
+                (format!("{code:?}"), error)
+            })
+            .collect();
+
+        Self {
+            openrpc: OPENRPC_VERSION.to_string(),
+            info,
+            methods,
+            components,
+            extensions: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a specification extension; `name` should start with `x-`.
+    pub fn with_extension(mut self, name: impl Into<String>, value: Value) -> Self {
+        self.extensions.insert(name.into(), value);
+        self
+    }
+
+    /// Returns the method of this name.
+    pub fn method(&self, name: &str) -> Option<&MethodObject> {
+        self.methods.iter().find(|method| method.name == name)
+    }
+}
+
+/// Splits the schema of a params object into one descriptor per field.
+///
+/// A schema that is not of an object with properties is described as a
+/// single value named `params`.
+fn params_of(mut schema: Value) -> Vec<ContentDescriptor> {
+    let Some(Value::Object(properties)) = schema.get_mut("properties").map(Value::take) else {
+        if schema.get("type").and_then(Value::as_str) == Some("object") {
+            return Vec::new();
+        }
+        return vec![ContentDescriptor {
+            name: "params".to_string(),
+            required: false,
+            schema,
+        }];
+    };
+
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    properties
+        .into_iter()
+        .map(|(name, schema)| ContentDescriptor {
+            required: required.contains(&name.as_str()),
+            name,
+            schema,
+        })
+        .collect()
+}
+
+/// Moves the definitions of `schema` to `schemas`, pointing its references at
+/// their new place. A definition already in `schemas` is kept.
+fn hoist(mut schema: Value, schemas: &mut BTreeMap<String, Value>) -> Value {
+    if let Value::Object(object) = &mut schema {
+        object.remove("$schema");
+        if let Some(Value::Object(definitions)) = object.remove("$defs") {
+            for (name, mut definition) in definitions {
+                retarget(&mut definition);
+                schemas.entry(name).or_insert(definition);
+            }
+        }
+    }
+    retarget(&mut schema);
+    schema
+}
+
+/// Points the `#/$defs/` references of `schema` at `components.schemas`.
+fn retarget(schema: &mut Value) {
+    match schema {
+        Value::Object(object) => {
+            if let Some(Value::String(reference)) = object.get_mut("$ref") {
+                if let Some(name) = reference.strip_prefix("#/$defs/") {
+                    *reference = format!("{COMPONENT_SCHEMAS}{name}");
+                }
+            }
+            object.values_mut().for_each(retarget);
+        }
+        Value::Array(items) => items.iter_mut().for_each(retarget),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::jsonrpc::typed::EmptyParams;
+
+    #[derive(Deserialize, JsonSchema)]
+    #[allow(dead_code)]
+    struct Params {
+        name: String,
+        inner: Option<Inner>,
+    }
+
+    #[derive(Deserialize, JsonSchema)]
+    #[allow(dead_code)]
+    struct Inner {
+        depth: u32,
+    }
+
+    #[test]
+    fn test_describe() {
+        let mut table = MethodTable::new();
+        table.register_typed("typed", |_: Params, _| async { Ok(true) });
+        table.register_typed("empty", |_: EmptyParams, _| async { Ok(1) });
+        table.register_method("untyped", |_, _| async { Ok(json!(null)) });
+
+        let document = OpenRpcDocument::describe(&table, Info::new("test", "1.0"))
+            .with_extension("x-extra", json!([1]));
+        let names: Vec<_> = document.methods.iter().map(|method| method.name.as_str()).collect();
+        assert_eq!(names, ["empty", "typed", "untyped"]);
+
+        let typed = document.method("typed").unwrap();
+        let param = |name: &str| typed.params.iter().find(|param| param.name == name).unwrap();
+        assert_eq!(typed.params.len(), 2);
+        assert!(param("name").required);
+        assert!(!param("inner").required);
+        assert!(serde_json::to_string(&param("inner").schema).unwrap().contains("#/components/schemas/Inner"));
+        assert!(document.components.schemas.contains_key("Inner"));
+        assert_eq!(typed.result.schema["type"], "boolean");
+        assert!(document.method("empty").unwrap().params.is_empty());
+        assert_eq!(document.method("untyped").unwrap().result.schema, json!({}));
+
+        assert_eq!(document.components.errors["InvalidParams"].code, -32602);
+        assert_eq!(document.components.errors.len(), ErrorCode::ALL.len());
+
+        let value = serde_json::to_value(&document).unwrap();
+        assert_eq!(value["openrpc"], OPENRPC_VERSION);
+        assert_eq!(value["x-extra"], json!([1]));
+        assert_eq!(serde_json::from_value::<OpenRpcDocument>(value).unwrap(), document);
+    }
+}
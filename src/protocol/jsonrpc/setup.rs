@@ -9,22 +9,95 @@
 //! This module provides functions to register method handlers and configure
 //! the JSON-RPC handler for use in the Mauka MCP server.
 
+use std::sync::Arc;
+
+use crate::config::MaukaConfig;
+use crate::prompts::PromptRegistry;
+use crate::protocol::jsonrpc::builder::JsonRpcHandlerBuilder;
 use crate::protocol::jsonrpc::handler::JsonRpcHandler;
+use crate::protocol::jsonrpc::methods::initialize::{
+    CompletionsCapability, PromptsCapability, ResourcesCapability, ServerCapabilities,
+    ToolsCapability,
+};
 use crate::protocol::jsonrpc::methods::{
-    register_initialize_method, register_tools_list_method,
+    register_cancelled_notification, register_completion_complete_method, register_discover_method,
+    register_initialize_method, register_ping_method, register_prompts_get_method,
+    register_prompts_list_method, register_resources_list_methods, register_resources_read_method,
+    register_resources_subscribe_methods, register_shutdown_method, register_tools_call_method,
+    register_tools_list_method,
+};
+use crate::protocol::jsonrpc::middleware::{
+    AuthMiddleware, LoggingMiddleware, MethodMetrics, MetricsMiddleware, RateLimitMiddleware,
+    TracingMiddleware,
 };
+use crate::resources::ResourceRegistry;
+use crate::tools::ToolRegistry;
 
-/// Registers all standard method handlers with the JSON-RPC handler.
+/// Registers all standard method handlers with the JSON-RPC handler builder.
 ///
 /// This function should be called once during server initialization to
-/// set up all the standard JSON-RPC method handlers.
-pub fn register_standard_methods(handler: &mut JsonRpcHandler) {
+/// set up all the standard JSON-RPC method handlers. The tool methods are
+/// served from `tools` and the resource methods from `resources`. The prompt
+/// and completion methods are only registered, and only advertised, when
+/// `prompts` is given. `rpc.discover` describes all of them, and the tools,
+/// as an OpenRPC document. Sessions must complete the MCP handshake before any
+/// other method is served to them.
+pub fn register_standard_methods(
+    builder: &mut JsonRpcHandlerBuilder,
+    tools: Arc<ToolRegistry>,
+    resources: Arc<ResourceRegistry>,
+    prompts: Option<Arc<PromptRegistry>>,
+) {
+    let capabilities = ServerCapabilities {
+        tools: Some(ToolsCapability::default()),
+        resources: Some(ResourcesCapability {
+            subscribe: true,
+            list_changed: false,
+        }),
+        prompts: prompts.as_ref().map(|_| PromptsCapability { list_changed: true }),
+        completions: prompts.as_ref().map(|_| CompletionsCapability::default()),
+        ..ServerCapabilities::default()
+    };
+
     // Register core protocol methods
-    register_initialize_method(handler);
-    register_tools_list_method(handler);
-    
-    // Future method handlers will be registered here
-    // register_shutdown_method(handler);
+    register_initialize_method(builder, capabilities);
+    register_ping_method(builder);
+    register_shutdown_method(builder);
+    register_cancelled_notification(builder);
+    register_discover_method(builder, tools.clone());
+    builder.enforce_lifecycle();
+
+    // Register tool methods
+    register_tools_list_method(builder, tools.clone());
+    register_tools_call_method(builder, tools);
+
+    // Register resource methods
+    register_resources_list_methods(builder, resources.clone());
+    register_resources_read_method(builder, resources.clone());
+    register_resources_subscribe_methods(builder, resources);
+
+    // Register prompt methods
+    if let Some(prompts) = prompts {
+        register_prompts_list_method(builder, prompts.clone());
+        register_prompts_get_method(builder, prompts.clone());
+        register_completion_complete_method(builder, prompts);
+    }
+}
+
+/// Adds the standard middlewares to the JSON-RPC handler builder.
+///
+/// Every call is traced, logged and counted in `metrics`. Calls are then
+/// rate limited as `config.limits.request_rate` says and, if bearer tokens
+/// are configured, must be authenticated. Rate limiting comes first so that
+/// guessing tokens is throttled as well.
+pub fn register_standard_middlewares(builder: &mut JsonRpcHandlerBuilder, config: &MaukaConfig, metrics: Arc<MethodMetrics>) {
+    builder.add_middleware(TracingMiddleware);
+    builder.add_middleware(LoggingMiddleware);
+    builder.add_middleware(MetricsMiddleware::new(metrics));
+    builder.add_middleware(RateLimitMiddleware::from_config(&config.limits.request_rate));
+    if config.security.auth.is_enabled() {
+        builder.add_middleware(AuthMiddleware::from_config(&config.security.auth));
+    }
 }
 
 /// Creates a fully configured JSON-RPC handler with all standard methods.
@@ -32,15 +105,32 @@ pub fn register_standard_methods(handler: &mut JsonRpcHandler) {
 /// This is a convenience function for creating a handler with all methods
 /// pre-registered, ready for use in the Mauka MCP server.
 pub fn create_handler() -> JsonRpcHandler {
-    let mut handler = JsonRpcHandler::new();
-    register_standard_methods(&mut handler);
-    handler
+    create_handler_with_tools(Arc::new(ToolRegistry::new()))
+}
+
+/// Creates a fully configured JSON-RPC handler serving the given tools.
+pub fn create_handler_with_tools(tools: Arc<ToolRegistry>) -> JsonRpcHandler {
+    create_handler_with_registries(tools, Arc::new(ResourceRegistry::new()), None)
+}
+
+/// Creates a fully configured JSON-RPC handler serving the given tools,
+/// resources and, if configured, prompts.
+pub fn create_handler_with_registries(
+    tools: Arc<ToolRegistry>,
+    resources: Arc<ResourceRegistry>,
+    prompts: Option<Arc<PromptRegistry>>,
+) -> JsonRpcHandler {
+    let mut builder = JsonRpcHandler::builder();
+    register_standard_methods(&mut builder, tools, resources, prompts);
+    builder.build()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::protocol::jsonrpc::methods::discover::discover_document;
     use crate::protocol::jsonrpc::types::{Id, Request, Response};
+    use crate::tools::{CallToolResult, ToolDefinition};
     use serde_json::{json, Value};
     
     #[tokio::test(flavor = "multi_thread")]
@@ -54,8 +144,9 @@ mod tests {
             id: Some(Id::Number(1)),
             method: "initialize".to_string(),
             params: Some(json!({
-                "client_name": "Test Client",
-                "client_version": "1.0.0"
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": {"name": "Test Client", "version": "1.0.0"}
             })),
         };
         
@@ -78,6 +169,7 @@ mod tests {
         assert!(result.is_object());
         let result_obj = result.as_object().unwrap();
         assert!(result_obj.contains_key("capabilities"));
+        assert_eq!(result["protocolVersion"], json!("2025-06-18"));
     }
     
     #[tokio::test(flavor = "multi_thread")]
@@ -90,9 +182,7 @@ mod tests {
             jsonrpc: "2.0".to_string(),
             id: Some(Id::Number(2)),
             method: "tools/list".to_string(),
-            params: Some(json!({
-                "include_details": true
-            })),
+            params: None,
         };
         
         // Serialize request to string
@@ -114,7 +204,78 @@ mod tests {
         assert!(result.is_object());
         let result_obj = result.as_object().unwrap();
         assert!(result_obj.contains_key("tools"));
-        assert!(result_obj.contains_key("total_count"));
+    }
+    
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_tools_call_method_registered() {
+        // Create handler serving a single tool
+        let tools = Arc::new(ToolRegistry::new());
+        tools.register_fn(
+            ToolDefinition::new("echo", json!({"type": "object"})),
+            |arguments, _ctx| async move { Ok(CallToolResult::structured(arguments)) },
+        );
+        let handler = create_handler_with_tools(tools);
+        
+        // Create tools/call request
+        let request = Request {
+            jsonrpc: "2.0".to_string(),
+            id: Some(Id::Number(4)),
+            method: "tools/call".to_string(),
+            params: Some(json!({
+                "name": "echo",
+                "arguments": {"message": "hi"}
+            })),
+        };
+        
+        // Serialize request to string
+        let request_str = serde_json::to_string(&request).unwrap();
+        
+        // Handle request
+        let response_str = handler.handle_request(request_str, None).await;
+        
+        // Deserialize response
+        let response: Response = serde_json::from_str(&response_str).unwrap();
+        
+        // Check that the tool ran and its output came back as content
+        assert!(response.error.is_none());
+        let result = response.result.unwrap();
+        assert_eq!(result["isError"], json!(false));
+        assert_eq!(result["structuredContent"], json!({"message": "hi"}));
+    }
+    
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_session_lifecycle_is_enforced() {
+        use crate::protocol::{LifecyclePhase, MethodContext, Session};
+
+        let handler = create_handler();
+        let session = Arc::new(Session::new("test"));
+        let call = |message: &'static str| {
+            let context = MethodContext::for_session(session.clone());
+            let handler = handler.clone();
+            async move {
+                let output = handler.handle_message(message, Some(context)).await?;
+                Some(serde_json::from_str::<Response>(&output).unwrap())
+            }
+        };
+        let list = r#"{"jsonrpc":"2.0","method":"tools/list","id":1}"#;
+
+        // Only the handshake and pings are served before initialize
+        let response = call(list).await.unwrap();
+        assert_eq!(response.error.unwrap().code, -32600);
+        assert!(call(r#"{"jsonrpc":"2.0","method":"ping","id":2}"#).await.unwrap().error.is_none());
+
+        let initialize = r#"{"jsonrpc":"2.0","method":"initialize","id":3,"params":{"protocolVersion":"2024-11-05","capabilities":{},"clientInfo":{"name":"c","version":"1"}}}"#;
+        let response = call(initialize).await.unwrap();
+        assert_eq!(response.result.unwrap()["protocolVersion"], json!("2024-11-05"));
+        assert_eq!(session.phase(), LifecyclePhase::Initializing);
+
+        assert!(call(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#).await.is_none());
+        assert_eq!(session.phase(), LifecyclePhase::Initialized);
+        assert!(call(list).await.unwrap().error.is_none());
+
+        let response = call(r#"{"jsonrpc":"2.0","method":"shutdown","id":4}"#).await.unwrap();
+        assert_eq!(response.result, Some(json!({})));
+        assert_eq!(call(list).await.unwrap().error.unwrap().code, -32600);
     }
     
     #[tokio::test(flavor = "multi_thread")]
@@ -145,4 +306,179 @@ mod tests {
         assert_eq!(response.id, Id::Number(3));
         assert_eq!(response.error.unwrap().code, -32601); // Method not found
     }
+    
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_typed_methods_report_schemas_and_param_paths() {
+        let handler = create_handler();
+        
+        // Schemas of the standard methods can be introspected
+        let schema = handler.method_schema("tools/call").unwrap();
+        assert_eq!(schema.params["required"], json!(["name"]));
+        assert!(schema.result["properties"]["content"].is_object());
+        assert!(handler.method_schemas().contains_key("initialize"));
+        let document = discover_document(&handler.methods(), &ToolRegistry::new());
+        let params = &document.method("tools/call").unwrap().params;
+        assert!(params.iter().any(|param| param.name == "name" && param.required));
+        
+        // A badly typed field is reported with its JSON path
+        let request = r#"{"jsonrpc":"2.0","method":"tools/call","id":4,"params":{"name":"x","arguments":"oops"}}"#;
+        let response: Response = serde_json::from_str(&handler.handle_request(request, None).await).unwrap();
+        let error = response.error.unwrap();
+        assert_eq!(error.code, -32602);
+        assert_eq!(error.data, Some(json!({"path": "$.arguments"})));
+        
+        // Positional params are accepted
+        let request = r#"{"jsonrpc":"2.0","method":"tools/call","id":5,"params":["missing"]}"#;
+        let response: Response = serde_json::from_str(&handler.handle_request(request, None).await).unwrap();
+        assert_eq!(response.error.unwrap().data, Some(json!({"tool": "missing"})));
+    }
+    
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_resources_read_method_registered() {
+        use crate::resources::{Resource, ResourceContents};
+
+        // Create handler serving a single resource
+        let resources = Arc::new(ResourceRegistry::new());
+        resources.register_fn(Resource::new("test://hello", "hello"), |_ctx| async {
+            Ok(vec![ResourceContents::text("test://hello", "text/plain", "hi")])
+        });
+        let handler = create_handler_with_registries(Arc::new(ToolRegistry::new()), resources, None);
+        
+        // Read the resource, then an unknown one
+        let read = |uri: &str| {
+            let request = Request {
+                jsonrpc: "2.0".to_string(),
+                id: Some(Id::Number(5)),
+                method: "resources/read".to_string(),
+                params: Some(json!({ "uri": uri })),
+            };
+            let handler = handler.clone();
+            async move {
+                let response_str = handler.handle_request(serde_json::to_string(&request).unwrap(), None).await;
+                serde_json::from_str::<Response>(&response_str).unwrap()
+            }
+        };
+        
+        let response = read("test://hello").await;
+        assert_eq!(response.result.unwrap()["contents"][0]["text"], json!("hi"));
+        
+        let response = read("test://nope").await;
+        assert_eq!(response.error.unwrap().code, -32002); // Resource not found
+    }
+    
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_prompts_capability_only_when_configured() {
+        use crate::prompts::PromptTemplate;
+
+        let initialize = r#"{"jsonrpc":"2.0","method":"initialize","id":1,"params":{"protocolVersion":"2025-06-18","capabilities":{},"clientInfo":{"name":"c","version":"1"}}}"#;
+        let list = r#"{"jsonrpc":"2.0","method":"prompts/list","id":2}"#;
+        let call = |handler: JsonRpcHandler, message: &'static str| async move {
+            let output = handler.handle_message(message, None).await.unwrap();
+            serde_json::from_str::<Response>(&output).unwrap()
+        };
+        
+        // Without prompts, neither the capability nor the methods exist
+        let handler = create_handler();
+        let capabilities = call(handler.clone(), initialize).await.result.unwrap()["capabilities"].clone();
+        assert!(capabilities.get("prompts").is_none());
+        assert!(capabilities.get("completions").is_none());
+        assert_eq!(call(handler, list).await.error.unwrap().code, -32601);
+        
+        // With prompts, both are offered
+        let prompts = Arc::new(PromptRegistry::new());
+        prompts.insert(PromptTemplate::parse("hello", "[[messages]]\nrole = \"user\"\ntext = \"hi\"").unwrap());
+        let handler = create_handler_with_registries(
+            Arc::new(ToolRegistry::new()),
+            Arc::new(ResourceRegistry::new()),
+            Some(prompts),
+        );
+        let capabilities = call(handler.clone(), initialize).await.result.unwrap()["capabilities"].clone();
+        assert_eq!(capabilities["prompts"], json!({"listChanged": true}));
+        assert_eq!(capabilities["completions"], json!({}));
+        assert_eq!(call(handler, list).await.result.unwrap()["prompts"][0]["name"], json!("hello"));
+    }
+    
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cancelled_request_gets_no_response() {
+        use crate::protocol::{MethodContext, Session};
+        use tokio::sync::mpsc;
+        
+        // A tool that reports progress, then runs until cancelled
+        let tools = Arc::new(ToolRegistry::new());
+        tools.register_fn(ToolDefinition::new("crawl", json!({"type": "object"})), |_arguments, ctx| async move {
+            ctx.report_progress(1.0, None, Some("started"));
+            std::future::pending::<()>().await;
+            Ok(CallToolResult::structured(json!({})))
+        });
+        let handler = create_handler_with_tools(tools);
+        
+        let session = Arc::new(Session::new("test"));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        session.attach_outbound(tx);
+        let context = || Some(MethodContext::for_session(session.clone()));
+        
+        let initialize = r#"{"jsonrpc":"2.0","method":"initialize","id":1,"params":{"protocolVersion":"2025-06-18","capabilities":{},"clientInfo":{"name":"c","version":"1"}}}"#;
+        assert!(handler.handle_message(initialize, context()).await.is_some());
+        
+        let call = r#"{"jsonrpc":"2.0","method":"tools/call","id":5,"params":{"name":"crawl","_meta":{"progressToken":"p"}}}"#;
+        let running = tokio::spawn({
+            let handler = handler.clone();
+            let context = context();
+            async move { handler.handle_message(call, context).await }
+        });
+        
+        // Progress arrives while the call runs
+        let progress: Value = serde_json::from_str(&rx.recv().await.unwrap()).unwrap();
+        assert_eq!(progress["method"], json!("notifications/progress"));
+        assert_eq!(progress["params"]["progressToken"], json!("p"));
+        assert_eq!(session.in_flight(), 1);
+        
+        let cancel = r#"{"jsonrpc":"2.0","method":"notifications/cancelled","params":{"requestId":5}}"#;
+        assert!(handler.handle_message(cancel, context()).await.is_none());
+        
+        let output = tokio::time::timeout(std::time::Duration::from_secs(5), running).await.unwrap().unwrap();
+        assert!(output.is_none());
+        assert_eq!(session.in_flight(), 0);
+    }
+    
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_tool_requests_roots_from_client() {
+        use crate::protocol::{MethodContext, Session};
+        use tokio::sync::mpsc;
+        
+        // A tool that lists the client's roots
+        let tools = Arc::new(ToolRegistry::new());
+        tools.register_fn(ToolDefinition::new("roots", json!({"type": "object"})), |_arguments, ctx| async move {
+            let roots = ctx.client().expect("session").list_roots().await?;
+            Ok(CallToolResult::structured(json!({"count": roots.len()})))
+        });
+        let handler = create_handler_with_tools(tools);
+        
+        let session = Arc::new(Session::new("test"));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        session.attach_outbound(tx);
+        let context = || Some(MethodContext::for_session(session.clone()));
+        
+        let initialize = r#"{"jsonrpc":"2.0","method":"initialize","id":1,"params":{"protocolVersion":"2025-06-18","capabilities":{"roots":{}},"clientInfo":{"name":"c","version":"1"}}}"#;
+        assert!(handler.handle_message(initialize, context()).await.is_some());
+        let initialized = r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#;
+        assert!(handler.handle_message(initialized, context()).await.is_none());
+        
+        let call = r#"{"jsonrpc":"2.0","method":"tools/call","id":2,"params":{"name":"roots"}}"#;
+        let running = tokio::spawn({
+            let handler = handler.clone();
+            let context = context();
+            async move { handler.handle_message(call, context).await }
+        });
+        
+        // The client answers the server's request like any other message
+        let request: Value = serde_json::from_str(&rx.recv().await.unwrap()).unwrap();
+        assert_eq!(request["method"], json!("roots/list"));
+        let response = json!({"jsonrpc": "2.0", "id": request["id"], "result": {"roots": [{"uri": "file:///a"}]}});
+        assert!(handler.handle_message(response.to_string(), context()).await.is_none());
+        
+        let output = running.await.unwrap().unwrap();
+        let response: Response = serde_json::from_str(&output).unwrap();
+        assert_eq!(response.result.unwrap()["structuredContent"], json!({"count": 1}));
+    }
 }
@@ -0,0 +1,314 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! The table of methods a handler dispatches to.
+//!
+//! A [`MethodTable`] is filled in once, usually through a
+//! [`JsonRpcHandlerBuilder`](super::JsonRpcHandlerBuilder), and never changes
+//! while a handler serves from it. Changing the methods of a running handler
+//! means building a new table and swapping it in whole with
+//! [`JsonRpcHandler::replace_methods`](super::JsonRpcHandler::replace_methods)
+//! or [`JsonRpcHandler::update_methods`](super::JsonRpcHandler::update_methods);
+//! calls already dispatched keep running against the table they started with.
+//!
+//! Notification handlers are kept apart from request methods. A notification
+//! only goes to the handler registered for it with
+//! [`register_notification`](MethodTable::register_notification), and a
+//! request is only ever served by a request method.
+
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::value::RawValue;
+use serde_json::Value;
+
+use super::error::JsonRpcError;
+use super::handler::{
+    MethodContext, MethodHandler, MethodHandlerFn, MethodHandlerFuture, MethodResult, NotificationResult,
+};
+use super::typed::{self, MethodSchema};
+
+/// Methods by name, with the schemas of those registered with types.
+#[derive(Clone, Default)]
+pub struct MethodTable {
+    /// Handlers by method name
+    handlers: HashMap<String, MethodHandlerFn>,
+
+    /// Params and result schemas of the methods registered with types
+    schemas: BTreeMap<String, MethodSchema>,
+
+    /// Notification handlers by method name
+    notifications: HashMap<String, MethodHandlerFn>,
+}
+
+impl MethodTable {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a method handler function, replacing any method of that name.
+    pub fn register_method<F, Fut>(&mut self, method: impl Into<String>, handler: F)
+    where
+        F: Send + Sync + 'static + Fn(Option<Value>, MethodContext) -> Fut,
+        Fut: Future<Output = MethodResult> + Send + 'static,
+    {
+        self.insert(method, Arc::new(handler));
+    }
+
+    /// Registers a method whose params and result are Rust types.
+    ///
+    /// Params are decoded into `P` before `handler` runs, and a decoding
+    /// failure is answered with `-32602 Invalid params` naming the JSON path of
+    /// the bad field; see [`typed`](super::typed) for the accepted forms. The
+    /// JSON Schemas of `P` and `R` are recorded and available from
+    /// [`schema`](Self::schema).
+    pub fn register_typed<P, R, F, Fut>(&mut self, method: impl Into<String>, handler: F)
+    where
+        P: DeserializeOwned + JsonSchema + Send + 'static,
+        R: Serialize + JsonSchema + 'static,
+        F: Send + Sync + 'static + Fn(P, MethodContext) -> Fut,
+        Fut: Future<Output = std::result::Result<R, JsonRpcError>> + Send + 'static,
+    {
+        let method = method.into();
+        let typed = TypedMethod {
+            name: Arc::from(method.as_str()),
+            handler,
+            _types: PhantomData,
+        };
+        self.insert(method.clone(), Arc::new(typed));
+        self.schemas.insert(method, MethodSchema::of::<P, R>());
+    }
+
+    /// Registers a notification handler function, replacing any notification
+    /// handler of that name.
+    ///
+    /// An error the handler returns is logged, as notifications get no response.
+    pub fn register_notification<F, Fut>(&mut self, method: impl Into<String>, handler: F)
+    where
+        F: Send + Sync + 'static + Fn(Option<Value>, MethodContext) -> Fut,
+        Fut: Future<Output = NotificationResult> + Send + 'static,
+    {
+        let handler = move |params, context| {
+            let notified = handler(params, context);
+            async move { notified.await.map(|()| Value::Null) }
+        };
+        self.insert_notification(method, Arc::new(handler));
+    }
+
+    /// Registers a notification handler whose params are a Rust type.
+    ///
+    /// Params are decoded as for [`register_typed`](Self::register_typed);
+    /// notifications whose params fail to decode are logged and dropped.
+    pub fn register_typed_notification<P, F, Fut>(&mut self, method: impl Into<String>, handler: F)
+    where
+        P: DeserializeOwned + Send + 'static,
+        F: Send + Sync + 'static + Fn(P, MethodContext) -> Fut,
+        Fut: Future<Output = NotificationResult> + Send + 'static,
+    {
+        let method = method.into();
+        let typed = TypedMethod {
+            name: Arc::from(method.as_str()),
+            handler,
+            _types: PhantomData,
+        };
+        self.insert_notification(method, Arc::new(typed));
+    }
+
+    /// Adds an already boxed notification handler, replacing any notification
+    /// handler of that name. Its result is discarded.
+    pub fn insert_notification(&mut self, method: impl Into<String>, handler: MethodHandlerFn) {
+        self.notifications.insert(method.into(), handler);
+    }
+
+    /// Removes a notification handler, returning whether it was registered.
+    pub fn remove_notification(&mut self, method: &str) -> bool {
+        self.notifications.remove(method).is_some()
+    }
+
+    /// Returns the handler of a notification registered with
+    /// [`register_notification`](Self::register_notification).
+    pub fn notification(&self, method: &str) -> Option<&MethodHandlerFn> {
+        self.notifications.get(method)
+    }
+
+    /// Returns the names of the registered notification handlers, in no particular order.
+    pub fn notification_names(&self) -> impl Iterator<Item = &str> {
+        self.notifications.keys().map(String::as_str)
+    }
+
+    /// Adds an already boxed handler, replacing any method of that name.
+    pub fn insert(&mut self, method: impl Into<String>, handler: MethodHandlerFn) {
+        let method = method.into();
+        self.schemas.remove(&method);
+        self.handlers.insert(method, handler);
+    }
+
+    /// Adds every method of `other`, replacing methods of the same names.
+    pub fn extend(&mut self, other: &MethodTable) {
+        for (method, handler) in &other.handlers {
+            self.insert(method.clone(), handler.clone());
+        }
+        self.schemas
+            .extend(other.schemas.iter().map(|(method, schema)| (method.clone(), schema.clone())));
+        self.notifications
+            .extend(other.notifications.iter().map(|(method, handler)| (method.clone(), handler.clone())));
+    }
+
+    /// Removes a method, returning whether it was registered.
+    pub fn remove(&mut self, method: &str) -> bool {
+        self.schemas.remove(method);
+        self.handlers.remove(method).is_some()
+    }
+
+    /// Returns the handler of a method.
+    pub fn get(&self, method: &str) -> Option<&MethodHandlerFn> {
+        self.handlers.get(method)
+    }
+
+    /// Returns true if a request method of this name is registered.
+    pub fn contains(&self, method: &str) -> bool {
+        self.handlers.contains_key(method)
+    }
+
+    /// Returns the names of the registered request methods, in no particular order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.handlers.keys().map(String::as_str)
+    }
+
+    /// Returns the number of registered request methods.
+    pub fn len(&self) -> usize {
+        self.handlers.len()
+    }
+
+    /// Returns true if no request method is registered.
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    /// Returns the params and result schemas of a method registered with
+    /// [`register_typed`](Self::register_typed).
+    pub fn schema(&self, method: &str) -> Option<&MethodSchema> {
+        self.schemas.get(method)
+    }
+
+    /// Returns the schemas of all methods registered with types, by method name.
+    pub fn schemas(&self) -> &BTreeMap<String, MethodSchema> {
+        &self.schemas
+    }
+}
+
+/// A method registered with [`MethodTable::register_typed`], or a
+/// notification handler registered with
+/// [`MethodTable::register_typed_notification`].
+struct TypedMethod<P, R, F> {
+    name: Arc<str>,
+    handler: F,
+    _types: PhantomData<fn(P) -> R>,
+}
+
+impl<P, R, F, Fut> TypedMethod<P, R, F>
+where
+    R: Serialize + 'static,
+    F: Fn(P, MethodContext) -> Fut,
+    Fut: Future<Output = std::result::Result<R, JsonRpcError>> + Send + 'static,
+{
+    fn call(&self, params: std::result::Result<P, JsonRpcError>, context: MethodContext) -> MethodHandlerFuture {
+        let name = self.name.clone();
+        let future = params.map(|params| (self.handler)(params, context));
+        Box::pin(async move {
+            let result = future?.await?;
+            typed::encode_result(&name, &result)
+        })
+    }
+}
+
+impl<P, R, F, Fut> MethodHandler for TypedMethod<P, R, F>
+where
+    P: DeserializeOwned,
+    R: Serialize + 'static,
+    F: Fn(P, MethodContext) -> Fut,
+    Fut: Future<Output = std::result::Result<R, JsonRpcError>> + Send + 'static,
+{
+    fn handle(&self, params: Option<Value>, context: MethodContext) -> MethodHandlerFuture {
+        self.call(typed::decode_params(&self.name, params), context)
+    }
+
+    fn handle_raw(&self, params: Option<&RawValue>, context: MethodContext) -> MethodHandlerFuture {
+        self.call(typed::decode_raw_params(&self.name, params), context)
+    }
+}
+
+impl std::fmt::Debug for MethodTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<_> = self.names().collect();
+        names.sort_unstable();
+        let mut notifications: Vec<_> = self.notification_names().collect();
+        notifications.sort_unstable();
+        f.debug_struct("MethodTable")
+            .field("methods", &names)
+            .field("notifications", &notifications)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::jsonrpc::typed::{EmptyParams, EmptyResult};
+    use serde_json::json;
+
+    #[test]
+    fn test_replacing_untyped_method_drops_schema() {
+        let mut table = MethodTable::new();
+        table.register_typed("ping", |_: EmptyParams, _| async { Ok(EmptyResult {}) });
+        assert!(table.schema("ping").is_some());
+
+        table.register_method("ping", |_, _| async { Ok(json!("pong")) });
+        assert!(table.contains("ping"));
+        assert!(table.schema("ping").is_none());
+
+        assert!(table.remove("ping"));
+        assert!(!table.remove("ping"));
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut table = MethodTable::new();
+        table.register_method("a", |_, _| async { Ok(json!(1)) });
+        let mut other = MethodTable::new();
+        other.register_typed("b", |_: EmptyParams, _| async { Ok(EmptyResult {}) });
+
+        table.extend(&other);
+        let mut names: Vec<_> = table.names().collect();
+        names.sort_unstable();
+        assert_eq!(names, ["a", "b"]);
+        assert!(table.schema("b").is_some());
+    }
+
+    #[test]
+    fn test_notifications_are_kept_apart() {
+        let mut table = MethodTable::new();
+        table.register_method("a", |_, _| async { Ok(json!(1)) });
+        table.register_notification("n", |_, _| async { Ok(()) });
+
+        assert!(!table.contains("n"));
+        assert!(table.notification("a").is_none());
+        assert!(table.notification("n").is_some());
+        assert_eq!(table.len(), 1);
+
+        let mut other = MethodTable::new();
+        other.extend(&table);
+        assert!(other.remove_notification("n"));
+        assert!(other.notification("n").is_none());
+    }
+}
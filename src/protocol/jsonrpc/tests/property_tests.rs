@@ -329,7 +329,7 @@ proptest! {
             // Create a context with the test value
             let mut metadata = HashMap::new();
             metadata.insert(key.clone(), value.clone());
-            let context = MethodContext { metadata };
+            let context = MethodContext { metadata, ..Default::default() };
             
             // Create and send request
             let request = json!({
@@ -233,21 +233,22 @@ async fn test_handle_invalid_request() {
 
 #[tokio::test]
 async fn test_context_provider() {
-    let mut handler = JsonRpcHandler::new();
+    let mut builder = JsonRpcHandler::builder();
     
     // Register a context-aware method
-    handler.register_method("get_context", |_, context| async move {
+    builder.register_method("get_context", |_, context| async move {
         let user_id = context.metadata.get("user_id").cloned().unwrap_or_default();
         Ok(json!({ "user_id": user_id }))
     });
     
     // Register a context provider that adds a user_id to the context
-    handler.register_context_provider(|| {
+    builder.register_context_provider(|| {
         let mut metadata = HashMap::new();
         metadata.insert("user_id".to_string(), "test-user-123".to_string());
         
-        MethodContext { metadata }
+        MethodContext { metadata, ..Default::default() }
     });
+    let handler = builder.build();
     
     // Test with a request that uses the context
     let request = r#"{"jsonrpc": "2.0", "method": "get_context", "id": 1}"#;
@@ -259,7 +260,7 @@ async fn test_context_provider() {
     // Test with an explicitly provided context that overrides the provider
     let mut metadata = HashMap::new();
     metadata.insert("user_id".to_string(), "override-user-456".to_string());
-    let context = MethodContext { metadata };
+    let context = MethodContext { metadata, ..Default::default() };
     
     let response = handler.handle_request(request, Some(context)).await;
     let response_obj: Response = serde_json::from_str(&response).unwrap();
@@ -0,0 +1,163 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Typed method parameters and results.
+//!
+//! [`JsonRpcHandler::register_typed`](super::JsonRpcHandler::register_typed)
+//! uses these helpers to decode params into a Rust type before calling the
+//! method and to encode what it returns. Params may be given by name (an
+//! object) or by position (an array, in field declaration order); missing
+//! params decode as an empty object. A decoding failure is reported as
+//! `-32602 Invalid params` whose data holds the JSON path of the offending
+//! field, such as `$.arguments.topic`.
+
+use schemars::{JsonSchema, SchemaGenerator};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use serde_json::{json, Map, Value};
+use serde_path_to_error::{Path, Segment};
+
+use super::error::{ErrorCode, JsonRpcError};
+
+/// Params of a method that takes none.
+///
+/// Any object is accepted, so that clients may still send `_meta`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct EmptyParams {}
+
+/// Result of a method that returns nothing but success, serialized as `{}`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct EmptyResult {}
+
+/// JSON Schemas of a typed method's params and result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MethodSchema {
+    /// Schema of the params
+    pub params: Value,
+
+    /// Schema of the result
+    pub result: Value,
+}
+
+impl MethodSchema {
+    /// Generates the schemas of `P` and `R`.
+    pub fn of<P: JsonSchema, R: JsonSchema>() -> Self {
+        Self {
+            params: schema_of::<P>(),
+            result: schema_of::<R>(),
+        }
+    }
+}
+
+/// Generates the JSON Schema of `T`.
+pub fn schema_of<T: JsonSchema>() -> Value {
+    SchemaGenerator::default().into_root_schema_for::<T>().to_value()
+}
+
+/// Decodes the params of a `method` call into `P`.
+pub fn decode_params<P: DeserializeOwned>(method: &str, params: Option<Value>) -> Result<P, JsonRpcError> {
+    let params = params.unwrap_or_else(|| Value::Object(Map::new()));
+    serde_path_to_error::deserialize(params).map_err(|err| invalid_params(method, err))
+}
+
+/// Decodes the params of a `method` call into `P` straight from their JSON text.
+pub fn decode_raw_params<P: DeserializeOwned>(method: &str, params: Option<&RawValue>) -> Result<P, JsonRpcError> {
+    let params = params.map_or("{}", RawValue::get);
+    let mut deserializer = serde_json::Deserializer::from_str(params);
+    serde_path_to_error::deserialize(&mut deserializer).map_err(|err| invalid_params(method, err))
+}
+
+/// Reports a decoding failure, with the path of the bad field.
+fn invalid_params<E: std::fmt::Display>(method: &str, err: serde_path_to_error::Error<E>) -> JsonRpcError {
+    let path = json_path(err.path());
+    JsonRpcError::with_data(
+        ErrorCode::InvalidParams,
+        format!("Invalid {method} parameters at {path}: {}", err.inner()),
+        json!({ "path": path }),
+    )
+}
+
+/// Encodes the result of a `method` call.
+pub fn encode_result<R: Serialize>(method: &str, result: &R) -> Result<Value, JsonRpcError> {
+    serde_json::to_value(result)
+        .map_err(|err| JsonRpcError::internal_error(format!("Error serializing {method} result: {err}")))
+}
+
+/// Formats `path` as a JSON path rooted at `$`.
+fn json_path(path: &Path) -> String {
+    let mut formatted = String::from("$");
+    for segment in path.iter() {
+        match segment {
+            Segment::Seq { index } => formatted.push_str(&format!("[{index}]")),
+            Segment::Map { key } => formatted.push_str(&format!(".{key}")),
+            Segment::Enum { variant } => formatted.push_str(&format!(".{variant}")),
+            Segment::Unknown => formatted.push_str(".?"),
+        }
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize, JsonSchema)]
+    struct Inner {
+        #[allow(dead_code)]
+        depth: u32,
+    }
+
+    #[derive(Debug, Deserialize, JsonSchema)]
+    struct Params {
+        name: String,
+        #[serde(default)]
+        pages: Vec<Inner>,
+    }
+
+    #[test]
+    fn test_named_and_positional_params() {
+        let params: Params = decode_params("m", Some(json!({"name": "a", "pages": [{"depth": 1}]}))).unwrap();
+        assert_eq!(params.name, "a");
+        assert_eq!(params.pages.len(), 1);
+
+        let params: Params = decode_params("m", Some(json!(["b"]))).unwrap();
+        assert_eq!(params.name, "b");
+        assert!(params.pages.is_empty());
+    }
+
+    #[test]
+    fn test_errors_carry_path() {
+        let error = decode_params::<Params>("m", Some(json!({"name": "a", "pages": [{"depth": 1}, {"depth": "x"}]})))
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidParams.code());
+        assert_eq!(error.data, Some(json!({"path": "$.pages[1].depth"})));
+        assert!(error.message.starts_with("Invalid m parameters at $.pages[1].depth"));
+
+        let error = decode_params::<Params>("m", None).unwrap_err();
+        assert_eq!(error.data, Some(json!({"path": "$"})));
+        assert!(error.message.contains("missing field `name`"));
+    }
+
+    #[test]
+    fn test_raw_params_decode_like_values() {
+        let raw = RawValue::from_string(r#"{"name": "a", "pages": [{"depth": "x"}]}"#.to_string()).unwrap();
+        let error = decode_raw_params::<Params>("m", Some(&raw)).unwrap_err();
+        assert_eq!(error.data, Some(json!({"path": "$.pages[0].depth"})));
+
+        let raw = RawValue::from_string(r#"["b"]"#.to_string()).unwrap();
+        assert_eq!(decode_raw_params::<Params>("m", Some(&raw)).unwrap().name, "b");
+        assert!(decode_raw_params::<EmptyParams>("m", None).is_ok());
+    }
+
+    #[test]
+    fn test_schema() {
+        let schema = MethodSchema::of::<Params, String>();
+        assert_eq!(schema.params["type"], "object");
+        assert_eq!(schema.params["required"], json!(["name"]));
+        assert_eq!(schema.result["type"], "string");
+    }
+}
@@ -9,7 +9,8 @@
 //! This module defines the core data structures for JSON-RPC 2.0 requests, responses, and
 //! related types according to the [specification](https://www.jsonrpc.org/specification).
 
-use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
 use std::fmt;
 
 use super::error::JsonRpcError;
@@ -17,7 +18,7 @@ use super::error::JsonRpcError;
 /// JSON-RPC request identifier.
 ///
 /// Can be a string, number, or null as per the JSON-RPC 2.0 specification.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema)]
 #[serde(untagged)]
 pub enum Id {
     /// String identifier
@@ -57,10 +58,18 @@ pub struct Request {
     pub params: Option<serde_json::Value>,
     
     /// Request identifier, if None then the request is a notification
-    #[serde(skip_serializing_if = "Option::is_none")]
+    ///
+    /// An `"id": null` member is `Some(Id::Null)`: a request that is answered
+    /// with a null id, unlike a notification, which has no `id` member at all.
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "present_id")]
     pub id: Option<Id>,
 }
 
+/// Deserializes an `id` member that is present, even as `null`, to `Some`.
+fn present_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Id>, D::Error> {
+    Id::deserialize(deserializer).map(Some)
+}
+
 impl Request {
     /// Creates a new JSON-RPC 2.0 request.
     pub fn new(method: impl Into<String>, params: Option<serde_json::Value>, id: Option<Id>) -> Self {
@@ -205,6 +214,17 @@ mod tests {
         assert!(notification.is_notification());
     }
     
+    #[test]
+    fn test_null_id_is_not_a_notification() {
+        let request: Request = serde_json::from_str(r#"{"jsonrpc":"2.0","method":"a","id":null}"#).unwrap();
+        assert_eq!(request.id, Some(Id::Null));
+        assert!(!request.is_notification());
+        assert_eq!(serde_json::to_string(&request).unwrap(), r#"{"jsonrpc":"2.0","method":"a","id":null}"#);
+        
+        let notification: Request = serde_json::from_str(r#"{"jsonrpc":"2.0","method":"a"}"#).unwrap();
+        assert!(notification.is_notification());
+    }
+    
     #[test]
     fn test_response_serialization() {
         // Success response
@@ -0,0 +1,120 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! MCP session lifecycle.
+//!
+//! A session starts [`Uninitialized`](LifecyclePhase::Uninitialized). A
+//! successful `initialize` moves it to
+//! [`Initializing`](LifecyclePhase::Initializing), and the client's
+//! `notifications/initialized` confirms it as
+//! [`Initialized`](LifecyclePhase::Initialized). `shutdown` moves it to
+//! [`ShuttingDown`](LifecyclePhase::ShuttingDown), after which no new work is
+//! accepted.
+//!
+//! Requests are admitted once `initialize` has been answered; the initialized
+//! notification is tracked but not waited for, since transports that handle
+//! messages concurrently may dispatch it after the client's next request.
+//! `ping` and `notifications/cancelled` are allowed in every phase.
+
+use crate::protocol::jsonrpc::error::{ErrorCode, JsonRpcError};
+
+/// Method that opens a session.
+pub const INITIALIZE: &str = "initialize";
+
+/// Notification with which the client confirms initialization.
+pub const INITIALIZED: &str = "notifications/initialized";
+
+/// Liveness check, allowed in every phase.
+pub const PING: &str = "ping";
+
+/// Notification cancelling an in-flight request, allowed in every phase.
+pub const CANCELLED: &str = "notifications/cancelled";
+
+/// Method that ends a session once its in-flight work has finished.
+pub const SHUTDOWN: &str = "shutdown";
+
+/// Stage of the MCP lifecycle a session is in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LifecyclePhase {
+    /// `initialize` has not completed
+    #[default]
+    Uninitialized,
+
+    /// `initialize` was answered; the initialized notification is outstanding
+    Initializing,
+
+    /// The client confirmed initialization
+    Initialized,
+
+    /// `shutdown` was requested; no new work is accepted
+    ShuttingDown,
+}
+
+impl LifecyclePhase {
+    /// Returns true once `initialize` has been answered.
+    pub fn is_initialized(self) -> bool {
+        matches!(self, Self::Initializing | Self::Initialized)
+    }
+}
+
+/// Checks that `method` may be called on a session in `phase`.
+///
+/// `initialize` is always admitted so that its handler can report a repeated
+/// handshake itself.
+pub fn admit(phase: LifecyclePhase, method: &str) -> Result<(), JsonRpcError> {
+    if method == PING || method == INITIALIZE || method == CANCELLED {
+        return Ok(());
+    }
+
+    match phase {
+        LifecyclePhase::Uninitialized => Err(JsonRpcError::with_data(
+            ErrorCode::InvalidRequest,
+            "Invalid Request: Session not initialized",
+            serde_json::json!({ "method": method }),
+        )),
+        LifecyclePhase::ShuttingDown => Err(JsonRpcError::with_data(
+            ErrorCode::InvalidRequest,
+            "Invalid Request: Session is shutting down",
+            serde_json::json!({ "method": method }),
+        )),
+        LifecyclePhase::Initializing | LifecyclePhase::Initialized => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_handshake_and_ping_before_initialize() {
+        let phase = LifecyclePhase::Uninitialized;
+        assert!(admit(phase, INITIALIZE).is_ok());
+        assert!(admit(phase, PING).is_ok());
+
+        let error = admit(phase, "tools/list").unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidRequest.code());
+        assert_eq!(error.data, Some(serde_json::json!({"method": "tools/list"})));
+        assert!(admit(phase, INITIALIZED).is_err());
+    }
+
+    #[test]
+    fn test_requests_admitted_after_initialize() {
+        for phase in [LifecyclePhase::Initializing, LifecyclePhase::Initialized] {
+            assert!(phase.is_initialized());
+            assert!(admit(phase, "tools/call").is_ok());
+            assert!(admit(phase, SHUTDOWN).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_nothing_new_after_shutdown() {
+        let phase = LifecyclePhase::ShuttingDown;
+        assert!(!phase.is_initialized());
+        assert!(admit(phase, "tools/call").is_err());
+        assert!(admit(phase, SHUTDOWN).is_err());
+        assert!(admit(phase, PING).is_ok());
+    }
+}
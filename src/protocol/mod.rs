@@ -6,5 +6,21 @@
 // JSON-RPC 2.0 implementation
 pub mod jsonrpc;
 
+// MCP session lifecycle
+pub mod lifecycle;
+
+// Requests from the server to the client
+pub mod peer;
+
+// Progress notifications for long-running requests
+pub mod progress;
+
+// Per-connection session state
+pub mod session;
+
 // Re-export common protocol components
 pub use self::jsonrpc::handler::{JsonRpcHandler, MethodContext, MethodHandler, MethodResult};
+pub use self::lifecycle::LifecyclePhase;
+pub use self::peer::ClientPeer;
+pub use self::progress::ProgressReporter;
+pub use self::session::{Session, SessionState};
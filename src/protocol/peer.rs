@@ -0,0 +1,312 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Requests from the server to the client.
+//!
+//! MCP lets the server ask the client for its filesystem roots, for a
+//! completion from the client's language model, and for input from the user.
+//! A [`ClientPeer`], obtained from
+//! [`MethodContext::client`](crate::protocol::MethodContext::client), sends
+//! these requests on the session a handler is serving. Each request is only
+//! sent if the client announced the matching capability during `initialize`,
+//! and fails if the client does not answer within the peer's timeout.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::protocol::ClientRequestError;
+use crate::prompts::Role;
+use crate::protocol::jsonrpc::methods::initialize::ClientCapabilities;
+use crate::protocol::session::Session;
+use crate::tools::ToolContent;
+
+/// Method asking the client for its filesystem roots.
+pub const ROOTS_LIST: &str = "roots/list";
+
+/// Method asking the client to sample from its language model.
+pub const SAMPLING_CREATE_MESSAGE: &str = "sampling/createMessage";
+
+/// Method asking the client to collect input from its user.
+pub const ELICITATION_CREATE: &str = "elicitation/create";
+
+/// Default time to wait for the client to answer.
+///
+/// Sampling and elicitation usually involve the user, so this is generous.
+pub const DEFAULT_CLIENT_REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// A filesystem root exposed by the client.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Root {
+    /// `file://` URI of the root
+    pub uri: String,
+
+    /// Display name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Result of a `roots/list` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListRootsResult {
+    /// The client's roots
+    pub roots: Vec<Root>,
+}
+
+/// One message of a sampling conversation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SamplingMessage {
+    /// Speaker of the message
+    pub role: Role,
+
+    /// Content of the message
+    pub content: ToolContent,
+}
+
+/// Parameters of a `sampling/createMessage` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMessageParams {
+    /// Conversation to continue
+    pub messages: Vec<SamplingMessage>,
+
+    /// Most tokens to generate
+    pub max_tokens: u32,
+
+    /// System prompt the server asks for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+
+    /// Sampling temperature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+
+    /// Sequences that end generation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop_sequences: Vec<String>,
+
+    /// Hints for the client's model selection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_preferences: Option<Value>,
+}
+
+impl CreateMessageParams {
+    /// Creates parameters asking for a reply to a single user message.
+    pub fn user(text: impl Into<String>, max_tokens: u32) -> Self {
+        Self {
+            messages: vec![SamplingMessage {
+                role: Role::User,
+                content: ToolContent::text(text),
+            }],
+            max_tokens,
+            system_prompt: None,
+            temperature: None,
+            stop_sequences: Vec::new(),
+            model_preferences: None,
+        }
+    }
+}
+
+/// Result of a `sampling/createMessage` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMessageResult {
+    /// Speaker of the generated message
+    pub role: Role,
+
+    /// Generated content
+    pub content: ToolContent,
+
+    /// Model that generated the message
+    pub model: String,
+
+    /// Why generation stopped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_reason: Option<String>,
+}
+
+/// Parameters of an `elicitation/create` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElicitParams {
+    /// What to ask the user
+    pub message: String,
+
+    /// Flat object schema of the requested input
+    pub requested_schema: Value,
+}
+
+/// How the user responded to an elicitation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ElicitAction {
+    /// The user submitted the requested input
+    Accept,
+
+    /// The user explicitly declined
+    Decline,
+
+    /// The user dismissed the request
+    Cancel,
+}
+
+/// Result of an `elicitation/create` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElicitResult {
+    /// How the user responded
+    pub action: ElicitAction,
+
+    /// The submitted input, when accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<HashMap<String, Value>>,
+}
+
+/// Sends requests to the client of one session.
+#[derive(Debug, Clone)]
+pub struct ClientPeer {
+    /// Session the client is connected through
+    session: Arc<Session>,
+
+    /// How long to wait for each response
+    timeout: Duration,
+}
+
+impl ClientPeer {
+    /// Creates a peer for the client of `session`.
+    pub fn new(session: Arc<Session>) -> Self {
+        Self {
+            session,
+            timeout: DEFAULT_CLIENT_REQUEST_TIMEOUT,
+        }
+    }
+
+    /// Sets how long to wait for each response.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Asks the client for its filesystem roots.
+    pub async fn list_roots(&self) -> Result<Vec<Root>, ClientRequestError> {
+        self.require("roots", |capabilities| capabilities.roots.is_some())?;
+        let result: ListRootsResult = self.request(ROOTS_LIST, None).await?;
+        Ok(result.roots)
+    }
+
+    /// Asks the client to sample a message from its language model.
+    pub async fn create_message(&self, params: CreateMessageParams) -> Result<CreateMessageResult, ClientRequestError> {
+        self.require("sampling", |capabilities| capabilities.sampling.is_some())?;
+        self.request(SAMPLING_CREATE_MESSAGE, Some(to_value(&params)?)).await
+    }
+
+    /// Asks the client to collect input from its user.
+    pub async fn elicit(&self, params: ElicitParams) -> Result<ElicitResult, ClientRequestError> {
+        self.require("elicitation", |capabilities| capabilities.elicitation.is_some())?;
+        self.request(ELICITATION_CREATE, Some(to_value(&params)?)).await
+    }
+
+    /// Sends an arbitrary request and decodes its result.
+    pub async fn request<T: DeserializeOwned>(&self, method: &str, params: Option<Value>) -> Result<T, ClientRequestError> {
+        let result = self.session.request(method, params, self.timeout).await?;
+        serde_json::from_value(result).map_err(|e| ClientRequestError::InvalidResponse(e.to_string()))
+    }
+
+    /// Fails unless the client announced `capability`.
+    fn require(
+        &self,
+        capability: &'static str,
+        announced: impl Fn(&ClientCapabilities) -> bool,
+    ) -> Result<(), ClientRequestError> {
+        match self.session.state().client_capabilities {
+            Some(capabilities) if announced(&capabilities) => Ok(()),
+            _ => Err(ClientRequestError::Unsupported(capability)),
+        }
+    }
+}
+
+fn to_value<T: Serialize>(params: &T) -> Result<Value, ClientRequestError> {
+    serde_json::to_value(params).map_err(|e| ClientRequestError::InvalidResponse(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::jsonrpc::types::{Request, Response};
+    use serde_json::json;
+    use tokio::sync::mpsc;
+
+    fn session(capabilities: Value) -> (Arc<Session>, mpsc::UnboundedReceiver<String>) {
+        let session = Arc::new(Session::new("test"));
+        session.update(|state| state.client_capabilities = Some(serde_json::from_value(capabilities).unwrap()));
+        let (tx, rx) = mpsc::unbounded_channel();
+        session.attach_outbound(tx);
+        (session, rx)
+    }
+
+    /// Answers the next request the client receives with `result`.
+    fn answer(session: Arc<Session>, mut rx: mpsc::UnboundedReceiver<String>, result: Value) -> tokio::task::JoinHandle<Request> {
+        tokio::spawn(async move {
+            let request: Request = serde_json::from_str(&rx.recv().await.unwrap()).unwrap();
+            session.correlate(Response::success(request.id.clone().unwrap(), result)).await;
+            request
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_message() {
+        let (session, rx) = session(json!({"sampling": {}}));
+        let client = answer(
+            session.clone(),
+            rx,
+            json!({"role": "assistant", "content": {"type": "text", "text": "A summary"}, "model": "m", "stopReason": "endTurn"}),
+        );
+
+        let result = ClientPeer::new(session)
+            .create_message(CreateMessageParams::user("Summarize this", 100))
+            .await
+            .unwrap();
+        assert_eq!(result.content, ToolContent::text("A summary"));
+
+        let request = client.await.unwrap();
+        assert_eq!(request.method, SAMPLING_CREATE_MESSAGE);
+        assert_eq!(request.params.unwrap()["maxTokens"], json!(100));
+    }
+
+    #[tokio::test]
+    async fn test_list_roots_and_elicit() {
+        let (session, rx) = session(json!({"roots": {}, "elicitation": {}}));
+        let client = answer(session.clone(), rx, json!({"roots": [{"uri": "file:///work", "name": "work"}]}));
+        let roots = ClientPeer::new(session.clone()).list_roots().await.unwrap();
+        assert_eq!(roots, vec![Root { uri: "file:///work".to_string(), name: Some("work".to_string()) }]);
+        assert_eq!(client.await.unwrap().method, ROOTS_LIST);
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        session.attach_outbound(tx);
+        let client = answer(session.clone(), rx, json!({"action": "decline"}));
+        let params = ElicitParams {
+            message: "Which page?".to_string(),
+            requested_schema: json!({"type": "object", "properties": {"url": {"type": "string"}}}),
+        };
+        let result = ClientPeer::new(session).elicit(params).await.unwrap();
+        assert_eq!(result.action, ElicitAction::Decline);
+        assert_eq!(client.await.unwrap().method, ELICITATION_CREATE);
+    }
+
+    #[tokio::test]
+    async fn test_requires_capability_and_timely_answer() {
+        let (session, _rx) = session(json!({"roots": {}}));
+        let peer = ClientPeer::new(session).with_timeout(Duration::from_millis(10));
+        assert!(matches!(
+            peer.create_message(CreateMessageParams::user("hi", 10)).await,
+            Err(ClientRequestError::Unsupported("sampling"))
+        ));
+        assert!(matches!(peer.list_roots().await, Err(ClientRequestError::Timeout { .. })));
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Progress notifications.
+//!
+//! A client asks to be told about the progress of a long-running request by
+//! putting a `progressToken` in the request's `_meta`. The handler then finds
+//! a [`ProgressReporter`] in its
+//! [`MethodContext`](crate::protocol::MethodContext), and each report is sent
+//! to the client as a `notifications/progress` carrying that token.
+
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use serde_json::{json, Map, Value};
+
+use crate::protocol::jsonrpc::types::Request;
+use crate::protocol::session::Session;
+
+/// Method of progress notifications.
+pub const PROGRESS_NOTIFICATION: &str = "notifications/progress";
+
+/// Reports the progress of one request to the client that sent it.
+///
+/// Clones report for the same request. Progress must increase with every
+/// report, as the MCP specification requires; reports that do not are dropped.
+#[derive(Debug, Clone)]
+pub struct ProgressReporter {
+    /// Token the client attached to the request
+    token: Value,
+
+    /// Session the request arrived on
+    session: Arc<Session>,
+
+    /// Progress of the last report sent
+    last: Arc<Mutex<Option<f64>>>,
+}
+
+impl ProgressReporter {
+    /// Creates a reporter sending progress for `token` to `session`.
+    pub fn new(session: Arc<Session>, token: Value) -> Self {
+        Self {
+            token,
+            session,
+            last: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Creates a reporter for a request whose parameters, still JSON text,
+    /// carry `_meta.progressToken`. Only `_meta` is decoded.
+    ///
+    /// Returns `None` if there is no token, or it is neither a string nor an
+    /// integer.
+    pub fn from_raw_params(params: Option<&RawValue>, session: &Arc<Session>) -> Option<Self> {
+        #[derive(Deserialize)]
+        struct Params {
+            #[serde(rename = "_meta")]
+            meta: Option<Meta>,
+        }
+
+        #[derive(Deserialize)]
+        struct Meta {
+            #[serde(rename = "progressToken")]
+            progress_token: Option<Value>,
+        }
+
+        let params = params.filter(|params| params.get().starts_with('{'))?;
+        let params: Params = serde_json::from_str(params.get()).ok()?;
+        let token = params.meta?.progress_token?;
+        let valid = token.is_string() || token.is_i64() || token.is_u64();
+        valid.then(|| Self::new(session.clone(), token))
+    }
+
+    /// Returns the client's progress token.
+    pub fn token(&self) -> &Value {
+        &self.token
+    }
+
+    /// Reports `progress`, out of `total` if known, with an optional message.
+    ///
+    /// Returns whether the notification was sent.
+    pub fn report(&self, progress: f64, total: Option<f64>, message: Option<&str>) -> bool {
+        {
+            let mut last = self.last.lock();
+            if last.is_some_and(|last| progress <= last) {
+                return false;
+            }
+            *last = Some(progress);
+        }
+
+        let mut params = Map::new();
+        params.insert("progressToken".to_string(), self.token.clone());
+        params.insert("progress".to_string(), json!(progress));
+        if let Some(total) = total {
+            params.insert("total".to_string(), json!(total));
+        }
+        if let Some(message) = message {
+            params.insert("message".to_string(), json!(message));
+        }
+
+        let notification = Request::notification(PROGRESS_NOTIFICATION, Some(Value::Object(params)));
+        serde_json::to_string(&notification).is_ok_and(|message| self.session.send(message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    fn raw(params: Value) -> Box<RawValue> {
+        RawValue::from_string(params.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_reports_increasing_progress() {
+        let session = Arc::new(Session::new("test"));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        session.attach_outbound(tx);
+
+        let params = json!({"url": "https://example.com", "_meta": {"progressToken": "abc"}});
+        let reporter = ProgressReporter::from_raw_params(Some(&raw(params)), &session).unwrap();
+        assert!(reporter.report(1.0, Some(2.0), Some("halfway")));
+        assert!(!reporter.clone().report(1.0, Some(2.0), None));
+
+        let message: Value = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert_eq!(message["method"], PROGRESS_NOTIFICATION);
+        assert_eq!(
+            message["params"],
+            json!({"progressToken": "abc", "progress": 1.0, "total": 2.0, "message": "halfway"})
+        );
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_requires_valid_token() {
+        let session = Arc::new(Session::new("test"));
+        for params in [
+            json!({}),
+            json!({"_meta": {}}),
+            json!({"_meta": {"progressToken": 1.5}}),
+            json!([{"_meta": {"progressToken": 1}}]),
+        ] {
+            assert!(ProgressReporter::from_raw_params(Some(&raw(params)), &session).is_none());
+        }
+        assert!(ProgressReporter::from_raw_params(None, &session).is_none());
+
+        let params = raw(json!({"_meta": {"progressToken": 7}}));
+        assert_eq!(ProgressReporter::from_raw_params(Some(&params), &session).unwrap().token(), &json!(7));
+        let params = raw(json!({"url": "x", "_meta": {"progressToken": "abc"}}));
+        assert_eq!(ProgressReporter::from_raw_params(Some(&params), &session).unwrap().token(), &json!("abc"));
+    }
+}
@@ -0,0 +1,613 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Per-connection session state.
+//!
+//! A [`Session`] is created by a transport for each client connection and travels
+//! with every request from that connection inside the
+//! [`MethodContext`](crate::protocol::MethodContext). Method handlers use it to
+//! record and read state negotiated with the client, such as the capabilities
+//! and client information exchanged during `initialize`.
+//!
+//! Transports that can push messages to the client attach an outbound channel to
+//! the session, which lets server-side code send notifications to that client
+//! without knowing which transport it is connected through.
+//!
+//! Sessions are bidirectional: [`Session::request`] sends a request to the
+//! client and waits for the response, which the transport routes back through
+//! [`Session::correlate`].
+//!
+//! The session also counts the requests it has in flight, so that `shutdown` can
+//! wait for them with [`Session::drain`], and keeps the cancellation token of
+//! each so that `notifications/cancelled` can abort it with [`Session::cancel`].
+//! A request reusing the id of one still in flight is refused, as its progress,
+//! cancellation and response could not be told apart from the first one's. With
+//! a [`ReplayWindow`], ids of completed requests stay taken for a while longer;
+//! they are remembered in a [`KonaBloomFilter`], so the memory a session spends
+//! on them is bounded however many requests it serves.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use parking_lot::{Mutex, RwLock};
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, Notify};
+use tokio_util::sync::CancellationToken;
+
+use crate::data_structures::kona_bloom_filter::{KonaBloomFilter, KonaBloomFilterConfig};
+use crate::error::protocol::{ClientRequestError, ProtocolError};
+use crate::protocol::jsonrpc::correlation::RequestResponseCorrelator;
+use crate::protocol::jsonrpc::methods::initialize::{ClientCapabilities, Implementation};
+use crate::protocol::jsonrpc::types::{Id, Request, Response};
+use crate::protocol::lifecycle::CANCELLED;
+use crate::protocol::lifecycle::LifecyclePhase;
+
+/// State negotiated with the client over the lifetime of a session.
+#[derive(Debug, Clone, Default)]
+pub struct SessionState {
+    /// Where the session is in the MCP lifecycle
+    pub phase: LifecyclePhase,
+
+    /// Protocol version agreed during initialization
+    pub protocol_version: Option<String>,
+
+    /// The client's name and version, as reported during initialization
+    pub client_info: Option<Implementation>,
+
+    /// Capabilities announced by the client
+    pub client_capabilities: Option<ClientCapabilities>,
+}
+
+/// Chance that a request with a fresh id is refused as replayed.
+const REPLAY_FALSE_POSITIVE_RATE: f64 = 0.0001;
+
+/// Generations the ids of completed requests are spread over.
+const REPLAY_GENERATIONS: usize = 4;
+
+/// How long the ids of completed requests stay taken on a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayWindow {
+    /// Least time an id stays taken after its request completes
+    duration: Duration,
+
+    /// Number of requests a session is expected to complete within the window
+    expected_ids: usize,
+}
+
+impl ReplayWindow {
+    /// Creates a window of `duration` sized for `expected_ids` completed
+    /// requests; more than that raise the chance of refusing a fresh id.
+    pub fn new(duration: Duration, expected_ids: usize) -> Self {
+        Self {
+            duration,
+            expected_ids: expected_ids.max(1),
+        }
+    }
+
+    /// Returns the least time an id stays taken after its request completes.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns the number of completed requests the window is sized for.
+    pub fn expected_ids(&self) -> usize {
+        self.expected_ids
+    }
+
+    /// Builds the filter remembering the ids of completed requests.
+    ///
+    /// An id lives in one of [`REPLAY_GENERATIONS`] generations, the oldest of
+    /// which is emptied every `duration / (REPLAY_GENERATIONS - 1)`, so it is
+    /// remembered for the whole window and at most a generation longer.
+    fn filter(&self) -> KonaBloomFilter<String> {
+        let config = KonaBloomFilterConfig::new()
+            .with_expected_items(self.expected_ids)
+            .with_false_positive_rate(REPLAY_FALSE_POSITIVE_RATE);
+        // The filter masks its hashes with its size in bits, so that size must
+        // be a power of two. Asking for twice the ids makes the optimal size at
+        // least the rounded up one, which the cap then brings it down to.
+        let bytes = config.calculate_optimal_bit_size().next_power_of_two();
+        let hash_functions = (bytes as f64 * 8.0 / self.expected_ids as f64 * std::f64::consts::LN_2)
+            .round()
+            .clamp(1.0, 20.0) as usize;
+        let config = config
+            .with_expected_items(self.expected_ids.saturating_mul(2))
+            .with_max_size_bytes(bytes)
+            .with_hash_functions(hash_functions)
+            .with_generations(true)
+            .with_generation_count(REPLAY_GENERATIONS)
+            .with_generation_duration(self.duration / (REPLAY_GENERATIONS as u32 - 1));
+        KonaBloomFilter::with_config(config)
+    }
+}
+
+/// Ids of the requests a session completed within its replay window.
+struct CompletedRequests {
+    window: ReplayWindow,
+    ids: KonaBloomFilter<String>,
+}
+
+impl std::fmt::Debug for CompletedRequests {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompletedRequests")
+            .field("window", &self.window)
+            .field("fill_ratio", &self.ids.fill_ratio())
+            .finish()
+    }
+}
+
+/// A client session bound to a single transport connection.
+#[derive(Debug)]
+pub struct Session {
+    /// Unique session identifier
+    id: String,
+
+    /// Name of the transport that owns this session
+    transport: &'static str,
+
+    /// When the session was created
+    created_at: Instant,
+
+    /// Mutable negotiated state
+    state: RwLock<SessionState>,
+
+    /// Channel for server-initiated messages, if the transport supports them
+    outbound: RwLock<Option<mpsc::UnboundedSender<String>>>,
+
+    /// Number of requests currently being handled
+    in_flight: AtomicUsize,
+
+    /// Woken when the last in-flight request finishes
+    idle: Notify,
+
+    /// Cancellation tokens of in-flight requests, keyed by request id
+    requests: Mutex<HashMap<String, (u64, CancellationToken)>>,
+
+    /// Source of the tags telling reuses of a request id apart
+    next_tag: AtomicU64,
+
+    /// Ids of recently completed requests, once replays are detected
+    completed: OnceLock<CompletedRequests>,
+
+    /// Requests sent to the client that await a response
+    correlator: RequestResponseCorrelator,
+}
+
+/// Marks a request as in flight on a session until dropped.
+#[derive(Debug)]
+pub struct InFlight<'a> {
+    session: &'a Session,
+
+    /// Key and tag of the cancellable request, if any
+    request: Option<(String, u64)>,
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        if let Some((key, tag)) = self.request.take() {
+            let mut requests = self.session.requests.lock();
+            if requests.get(&key).is_some_and(|(current, _)| *current == tag) {
+                // Remembered before the id is released, so no reuse slips in between
+                if let Some(completed) = self.session.completed.get() {
+                    completed.ids.insert(key.clone());
+                }
+                requests.remove(&key);
+            }
+        }
+        if self.session.in_flight.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.session.idle.notify_waiters();
+        }
+    }
+}
+
+impl Session {
+    /// Creates a new session with a randomly generated identifier.
+    pub fn new(transport: &'static str) -> Self {
+        Self::with_id(uuid::Uuid::new_v4().to_string(), transport)
+    }
+
+    /// Creates a new session with the given identifier.
+    pub fn with_id(id: impl Into<String>, transport: &'static str) -> Self {
+        Self {
+            id: id.into(),
+            transport,
+            created_at: Instant::now(),
+            state: RwLock::new(SessionState::default()),
+            outbound: RwLock::new(None),
+            in_flight: AtomicUsize::new(0),
+            idle: Notify::new(),
+            requests: Mutex::new(HashMap::new()),
+            next_tag: AtomicU64::new(0),
+            completed: OnceLock::new(),
+            correlator: RequestResponseCorrelator::new(),
+        }
+    }
+
+    /// Returns the session identifier.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the name of the transport that owns this session.
+    pub fn transport(&self) -> &'static str {
+        self.transport
+    }
+
+    /// Returns when the session was created.
+    pub fn created_at(&self) -> Instant {
+        self.created_at
+    }
+
+    /// Returns a snapshot of the session state.
+    pub fn state(&self) -> SessionState {
+        self.state.read().clone()
+    }
+
+    /// Returns where the session is in the MCP lifecycle.
+    pub fn phase(&self) -> LifecyclePhase {
+        self.state.read().phase
+    }
+
+    /// Applies a modification to the session state, returning the closure's result.
+    ///
+    /// The state is locked for the duration of the closure, so checking and
+    /// changing it here is atomic.
+    pub fn update<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut SessionState) -> R,
+    {
+        f(&mut self.state.write())
+    }
+
+    /// Marks a request as in flight until the returned guard is dropped.
+    pub fn track(&self) -> InFlight<'_> {
+        self.in_flight.fetch_add(1, Ordering::AcqRel);
+        InFlight {
+            session: self,
+            request: None,
+        }
+    }
+
+    /// Marks the request `id` as in flight until the returned guard is dropped,
+    /// cancelling `token` if the client cancels the request meanwhile.
+    ///
+    /// Fails with [`ProtocolError::DuplicateId`] if a request with this id is
+    /// already in flight, or completed within the replay window. A `null` id is
+    /// never refused, as it cannot be told apart from others anyway.
+    pub fn track_request(&self, id: &Id, token: CancellationToken) -> Result<InFlight<'_>, ProtocolError> {
+        let key = request_key(id);
+        let tag = self.next_tag.fetch_add(1, Ordering::Relaxed);
+        {
+            let mut requests = self.requests.lock();
+            if !matches!(id, Id::Null) {
+                if requests.contains_key(&key) {
+                    return Err(ProtocolError::DuplicateId(format!("{key} is already in flight")));
+                }
+                if let Some(completed) = self.completed.get().filter(|c| c.ids.check(key.clone())) {
+                    return Err(ProtocolError::DuplicateId(format!(
+                        "{key} was used within the last {}ms",
+                        completed.window.duration().as_millis()
+                    )));
+                }
+            }
+            requests.insert(key.clone(), (tag, token));
+        }
+        self.in_flight.fetch_add(1, Ordering::AcqRel);
+        Ok(InFlight {
+            session: self,
+            request: Some((key, tag)),
+        })
+    }
+
+    /// Keeps the ids of requests completed from now on taken for `window`.
+    ///
+    /// Only the first window a session is given applies; later calls are ignored.
+    pub fn detect_replays(&self, window: &ReplayWindow) {
+        self.completed.get_or_init(|| CompletedRequests {
+            window: *window,
+            ids: window.filter(),
+        });
+    }
+
+    /// Returns the replay window of the session, if replays are detected.
+    pub fn replay_window(&self) -> Option<ReplayWindow> {
+        self.completed.get().map(|completed| completed.window)
+    }
+
+    /// Cancels the in-flight request `id`, returning whether it was in flight.
+    pub fn cancel(&self, id: &Id) -> bool {
+        match self.requests.lock().get(&request_key(id)) {
+            Some((_, token)) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the number of requests in flight.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Acquire)
+    }
+
+    /// Waits until no requests are in flight.
+    pub async fn drain(&self) {
+        loop {
+            // Registered before the check so a wake-up in between is not lost
+            let idle = self.idle.notified();
+            if self.in_flight() == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+
+    /// Attaches the channel that delivers server-initiated messages to the client.
+    pub fn attach_outbound(&self, sender: mpsc::UnboundedSender<String>) {
+        *self.outbound.write() = Some(sender);
+    }
+
+    /// Detaches the outbound channel, closing it once in-flight sends complete.
+    pub fn detach_outbound(&self) {
+        self.outbound.write().take();
+    }
+
+    /// Sends a serialized JSON-RPC message to the client.
+    ///
+    /// Returns `false` if the transport cannot deliver server-initiated messages
+    /// or the client has gone away.
+    pub fn send(&self, message: String) -> bool {
+        match self.outbound.read().as_ref() {
+            Some(sender) => sender.send(message).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Sends a request to the client and waits up to `timeout` for its result.
+    ///
+    /// On timeout the client is told, with `notifications/cancelled`, that the
+    /// server no longer waits for the response.
+    pub async fn request(
+        &self,
+        method: &str,
+        params: Option<Value>,
+        timeout: Duration,
+    ) -> Result<Value, ClientRequestError> {
+        self.correlator.cleanup_timed_out_requests().await;
+
+        let request = self.correlator.prepare_request(Request::new(method, params, Some(Id::Null)));
+        let id = request.id.clone().unwrap_or(Id::Null);
+        let timeout_ms = u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX);
+        // Only notifications are refused, and prepared requests always have an id
+        let receiver = self
+            .correlator
+            .register_request(&request, Some(timeout_ms))
+            .await
+            .map_err(|e| ClientRequestError::InvalidResponse(e.to_string()))?;
+
+        let delivered = serde_json::to_string(&request).is_ok_and(|message| self.send(message));
+        if !delivered {
+            self.correlator.cancel_request(&id).await;
+            return Err(ClientRequestError::NotConnected);
+        }
+
+        match tokio::time::timeout(timeout, receiver).await {
+            Ok(Ok(Response { error: Some(error), .. })) => Err(ClientRequestError::Rejected(error)),
+            Ok(Ok(response)) => Ok(response.result.unwrap_or(Value::Null)),
+            Ok(Err(_)) => Err(ClientRequestError::NotConnected),
+            Err(_) => {
+                self.correlator.cancel_request(&id).await;
+                let cancelled = Request::notification(
+                    CANCELLED,
+                    Some(json!({ "requestId": id, "reason": "Request timed out" })),
+                );
+                if let Ok(message) = serde_json::to_string(&cancelled) {
+                    self.send(message);
+                }
+                Err(ClientRequestError::Timeout {
+                    method: method.to_string(),
+                    timeout_ms,
+                })
+            }
+        }
+    }
+
+    /// Delivers a response from the client to the request it answers.
+    ///
+    /// Returns `false` if no request sent on this session awaits it.
+    pub async fn correlate(&self, response: Response) -> bool {
+        self.correlator.correlate_response(response).await
+    }
+}
+
+/// Returns the key an in-flight request is tracked under.
+///
+/// The serialized form keeps `1` and `"1"` apart, as JSON-RPC requires.
+fn request_key(id: &Id) -> String {
+    serde_json::to_string(id).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sessions_have_unique_ids() {
+        let a = Session::new("test");
+        let b = Session::new("test");
+        assert_ne!(a.id(), b.id());
+        assert_eq!(a.transport(), "test");
+    }
+
+    #[test]
+    fn test_update_state() {
+        let session = Session::with_id("abc", "test");
+        assert_eq!(session.phase(), LifecyclePhase::Uninitialized);
+
+        let previous = session.update(|state| std::mem::replace(&mut state.phase, LifecyclePhase::Initializing));
+        assert_eq!(previous, LifecyclePhase::Uninitialized);
+        assert_eq!(session.phase(), LifecyclePhase::Initializing);
+        assert_eq!(session.id(), "abc");
+    }
+
+    #[tokio::test]
+    async fn test_drain_waits_for_in_flight_requests() {
+        let session = std::sync::Arc::new(Session::new("test"));
+        session.drain().await;
+
+        let (started_tx, started_rx) = tokio::sync::oneshot::channel();
+        let (finish_tx, finish_rx) = tokio::sync::oneshot::channel::<()>();
+        let request = tokio::spawn({
+            let session = session.clone();
+            async move {
+                let _in_flight = session.track();
+                let _ = started_tx.send(());
+                let _ = finish_rx.await;
+            }
+        });
+        started_rx.await.unwrap();
+        assert_eq!(session.in_flight(), 1);
+
+        let drain = tokio::spawn({
+            let session = session.clone();
+            async move { session.drain().await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!drain.is_finished());
+
+        finish_tx.send(()).unwrap();
+        request.await.unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(5), drain)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(session.in_flight(), 0);
+    }
+
+    #[test]
+    fn test_send_requires_outbound_channel() {
+        let session = Session::new("test");
+        assert!(!session.send("{}".to_string()));
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        session.attach_outbound(tx);
+        assert!(session.send("hello".to_string()));
+        assert_eq!(rx.try_recv().unwrap(), "hello");
+
+        session.detach_outbound();
+        assert!(!session.send("bye".to_string()));
+    }
+
+    #[test]
+    fn test_cancel_in_flight_request() {
+        let session = Session::new("test");
+        let token = CancellationToken::new();
+        let guard = session.track_request(&Id::Number(1), token.clone()).unwrap();
+
+        assert!(!session.cancel(&Id::String("1".to_string())));
+        assert!(session.cancel(&Id::Number(1)));
+        assert!(token.is_cancelled());
+
+        drop(guard);
+        assert!(!session.cancel(&Id::Number(1)));
+        assert_eq!(session.in_flight(), 0);
+    }
+
+    #[test]
+    fn test_duplicate_in_flight_id_is_refused() {
+        let session = Session::new("test");
+        let first = session.track_request(&Id::Number(1), CancellationToken::new()).unwrap();
+        let error = session.track_request(&Id::Number(1), CancellationToken::new()).unwrap_err();
+        assert!(matches!(error, ProtocolError::DuplicateId(_)));
+        assert_eq!(session.in_flight(), 1);
+
+        let other = session.track_request(&Id::String("1".to_string()), CancellationToken::new());
+        assert!(other.is_ok());
+        let nulls = [Id::Null, Id::Null].map(|id| session.track_request(&id, CancellationToken::new()));
+        assert!(nulls.iter().all(Result::is_ok));
+
+        // Without a replay window the id is free again once its request completes
+        drop(first);
+        assert!(session.track_request(&Id::Number(1), CancellationToken::new()).is_ok());
+    }
+
+    #[test]
+    fn test_replayed_id_is_refused_within_window() {
+        let session = Session::new("test");
+        let window = ReplayWindow::new(Duration::from_secs(60), 100);
+        session.detect_replays(&window);
+        session.detect_replays(&ReplayWindow::new(Duration::from_secs(1), 1));
+        assert_eq!(session.replay_window(), Some(window));
+
+        for id in 0..50 {
+            drop(session.track_request(&Id::Number(id), CancellationToken::new()).unwrap());
+        }
+        let error = session.track_request(&Id::Number(7), CancellationToken::new()).unwrap_err();
+        assert!(error.to_string().contains("60000ms"));
+        assert!(session.track_request(&Id::Number(50), CancellationToken::new()).is_ok());
+        assert_eq!(session.in_flight(), 0);
+    }
+
+    #[test]
+    fn test_replay_window_keeps_rotating() {
+        let session = Session::new("test");
+        session.detect_replays(&ReplayWindow::new(Duration::from_millis(30), 100));
+
+        // Requests keep coming over many windows, each id refused right after
+        for id in 0..40 {
+            drop(session.track_request(&Id::Number(id), CancellationToken::new()).unwrap());
+            assert!(session.track_request(&Id::Number(id), CancellationToken::new()).is_err());
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(session.in_flight(), 0);
+    }
+
+    #[test]
+    fn test_replayed_id_is_accepted_after_idle_window() {
+        let session = Session::new("test");
+        session.detect_replays(&ReplayWindow::new(Duration::from_millis(30), 100));
+        drop(session.track_request(&Id::Number(1), CancellationToken::new()).unwrap());
+        assert!(session.track_request(&Id::Number(1), CancellationToken::new()).is_err());
+
+        // Nothing happens on the session for longer than all its generations
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(session.track_request(&Id::Number(1), CancellationToken::new()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_request_round_trip() {
+        let session = std::sync::Arc::new(Session::new("test"));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        session.attach_outbound(tx);
+
+        // Answer the first request, then let the second time out
+        let client = tokio::spawn({
+            let session = session.clone();
+            async move {
+                let request: Request = serde_json::from_str(&rx.recv().await.unwrap()).unwrap();
+                assert_eq!(request.method, "roots/list");
+                let id = request.id.unwrap();
+                assert!(session.correlate(Response::success(id, json!({"roots": []}))).await);
+                rx
+            }
+        });
+        let result = session.request("roots/list", None, Duration::from_secs(5)).await.unwrap();
+        assert_eq!(result, json!({"roots": []}));
+
+        let mut rx = client.await.unwrap();
+        let error = session.request("roots/list", None, Duration::from_millis(10)).await.unwrap_err();
+        assert!(matches!(error, ClientRequestError::Timeout { .. }));
+        rx.recv().await.unwrap();
+        let cancelled: Request = serde_json::from_str(&rx.recv().await.unwrap()).unwrap();
+        assert_eq!(cancelled.method, CANCELLED);
+
+        session.detach_outbound();
+        let error = session.request("roots/list", None, Duration::from_secs(5)).await.unwrap_err();
+        assert!(matches!(error, ClientRequestError::NotConnected));
+    }
+}
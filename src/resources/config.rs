@@ -0,0 +1,198 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Resources exposing the server configuration.
+//!
+//! `mauka://config` serves the whole [`MaukaConfig`] the server is running
+//! with, and `mauka://config/{section}` one of its top-level sections. Values
+//! of fields whose names suggest credentials or key material are replaced with
+//! [`REDACTED`] before anything is served.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::registry::{ResourceProvider, TemplateProvider};
+use super::types::{Resource, ResourceContents, ResourceTemplate, JSON_MIME_TYPE};
+use crate::config::MaukaConfig;
+use crate::error::MaukaResult;
+use crate::protocol::MethodContext;
+
+/// URI of the full configuration.
+pub const CONFIG_URI: &str = "mauka://config";
+
+/// URI template of a single configuration section.
+pub const CONFIG_SECTION_TEMPLATE: &str = "mauka://config/{section}";
+
+/// Placeholder for redacted values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Fragments of field names whose values are never served.
+const SENSITIVE_KEY_FRAGMENTS: &[&str] = &[
+    "password",
+    "secret",
+    "token",
+    "api_key",
+    "apikey",
+    "private_key",
+    "key_file",
+    "credential",
+    "authorization",
+    "cookie",
+];
+
+/// Serves the full configuration at [`CONFIG_URI`].
+#[derive(Debug, Clone)]
+pub struct ConfigResource {
+    config: Arc<MaukaConfig>,
+}
+
+impl ConfigResource {
+    /// Creates a resource serving `config`.
+    pub fn new(config: Arc<MaukaConfig>) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl ResourceProvider for ConfigResource {
+    fn resource(&self) -> Resource {
+        Resource::new(CONFIG_URI, "config")
+            .with_title("Server configuration")
+            .with_description("The configuration the server is running with, with credentials redacted")
+            .with_mime_type(JSON_MIME_TYPE)
+    }
+
+    async fn read(&self, _context: MethodContext) -> MaukaResult<Vec<ResourceContents>> {
+        Ok(vec![ResourceContents::json(CONFIG_URI, &redacted(&self.config)?)?])
+    }
+}
+
+/// Serves each top-level configuration section at [`CONFIG_SECTION_TEMPLATE`].
+#[derive(Debug, Clone)]
+pub struct ConfigSectionTemplate {
+    config: Arc<MaukaConfig>,
+}
+
+impl ConfigSectionTemplate {
+    /// Creates a template serving the sections of `config`.
+    pub fn new(config: Arc<MaukaConfig>) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl TemplateProvider for ConfigSectionTemplate {
+    fn template(&self) -> ResourceTemplate {
+        ResourceTemplate::new(CONFIG_SECTION_TEMPLATE, "config-section")
+            .with_description("One section of the server configuration, such as `http` or `security`")
+            .with_mime_type(JSON_MIME_TYPE)
+    }
+
+    async fn read(
+        &self,
+        uri: &str,
+        variables: HashMap<String, String>,
+        _context: MethodContext,
+    ) -> MaukaResult<Option<Vec<ResourceContents>>> {
+        let config = redacted(&self.config)?;
+        let section = variables.get("section").and_then(|name| config.get(name));
+        section
+            .map(|section| Ok(vec![ResourceContents::json(uri, section)?]))
+            .transpose()
+    }
+}
+
+/// Serializes `config` with sensitive values redacted.
+pub fn redacted(config: &MaukaConfig) -> MaukaResult<Value> {
+    let mut value = serde_json::to_value(config)?;
+    redact(&mut value);
+    Ok(value)
+}
+
+/// Replaces the values of sensitive fields anywhere in `value`.
+pub fn redact(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields.iter_mut() {
+                if is_sensitive(key) && !field.is_null() {
+                    *field = Value::String(REDACTED.to_string());
+                } else {
+                    redact(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+/// Returns true if a field with this name may hold a credential.
+fn is_sensitive(key: &str) -> bool {
+    let key = key.to_ascii_lowercase().replace('-', "_");
+    SENSITIVE_KEY_FRAGMENTS.iter().any(|fragment| key.contains(fragment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config() -> Arc<MaukaConfig> {
+        let mut config = MaukaConfig::default();
+        config.security.tls.client_key_file = Some("/etc/mauka/client.key".into());
+        Arc::new(config)
+    }
+
+    #[test]
+    fn test_sensitive_fields_are_redacted() {
+        let mut value = json!({
+            "name": "mauka",
+            "api_key": "abc",
+            "nested": [{ "Authorization": "Bearer x", "password": null, "port": 1 }]
+        });
+        redact(&mut value);
+        assert_eq!(
+            value,
+            json!({
+                "name": "mauka",
+                "api_key": REDACTED,
+                "nested": [{ "Authorization": REDACTED, "password": null, "port": 1 }]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_config_resource_redacts_key_file() {
+        let contents = ConfigResource::new(config()).read(MethodContext::default()).await.unwrap();
+        let value: Value = serde_json::from_str(contents[0].text.as_deref().unwrap()).unwrap();
+        assert_eq!(value["security"]["tls"]["client_key_file"], REDACTED);
+        assert_eq!(value["server"]["name"], json!(MaukaConfig::default().server.name));
+        assert!(!contents[0].text.as_deref().unwrap().contains("client.key"));
+    }
+
+    #[tokio::test]
+    async fn test_config_sections() {
+        let template = ConfigSectionTemplate::new(config());
+        let variables = HashMap::from([("section".to_string(), "http".to_string())]);
+        let contents = template
+            .read("mauka://config/http", variables, MethodContext::default())
+            .await
+            .unwrap()
+            .unwrap();
+        let value: Value = serde_json::from_str(contents[0].text.as_deref().unwrap()).unwrap();
+        assert!(value.get("client").is_some());
+
+        let variables = HashMap::from([("section".to_string(), "nope".to_string())]);
+        assert!(template
+            .read("mauka://config/nope", variables, MethodContext::default())
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
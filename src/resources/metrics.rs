@@ -0,0 +1,132 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Resource exposing runtime metrics.
+//!
+//! `mauka://metrics` serves a snapshot taken at read time: server uptime, the
+//! statistics of the outbound HTTP connection pool and, when the server counts
+//! them, the calls of each JSON-RPC method. Subscribers are
+//! notified by the registry's watcher whenever the snapshot changes.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use serde::Serialize;
+
+use super::registry::ResourceProvider;
+use super::types::{Resource, ResourceContents, JSON_MIME_TYPE};
+use crate::client::{HttpClient, PoolStats};
+use crate::error::MaukaResult;
+use crate::protocol::jsonrpc::middleware::{MethodMetrics, MethodStats};
+use crate::protocol::MethodContext;
+
+/// URI of the metrics snapshot.
+pub const METRICS_URI: &str = "mauka://metrics";
+
+/// Snapshot served by [`MetricsResource`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsSnapshot {
+    /// Whole seconds since the server started
+    pub uptime_seconds: u64,
+
+    /// Outbound HTTP connection pool statistics
+    pub http_pool: PoolStats,
+
+    /// Call counters of each JSON-RPC method
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub methods: BTreeMap<String, MethodStats>,
+}
+
+/// Serves a [`MetricsSnapshot`] at [`METRICS_URI`].
+#[derive(Debug, Clone)]
+pub struct MetricsResource {
+    /// When the server started
+    started_at: Instant,
+
+    /// Client whose connection pool is reported
+    client: Arc<HttpClient>,
+
+    /// Method call counters, if the server keeps them
+    methods: Option<Arc<MethodMetrics>>,
+}
+
+impl MetricsResource {
+    /// Creates a resource reporting on `client`, counting uptime from now.
+    pub fn new(client: Arc<HttpClient>) -> Self {
+        Self {
+            started_at: Instant::now(),
+            client,
+            methods: None,
+        }
+    }
+
+    /// Also reports the method call counters in `metrics`.
+    pub fn with_method_metrics(mut self, metrics: Arc<MethodMetrics>) -> Self {
+        self.methods = Some(metrics);
+        self
+    }
+
+    /// Takes a snapshot of the current metrics.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            uptime_seconds: self.started_at.elapsed().as_secs(),
+            http_pool: self.client.pool_stats(),
+            methods: self.methods.as_ref().map(|metrics| metrics.snapshot()).unwrap_or_default(),
+        }
+    }
+}
+
+#[async_trait]
+impl ResourceProvider for MetricsResource {
+    fn resource(&self) -> Resource {
+        Resource::new(METRICS_URI, "metrics")
+            .with_title("Runtime metrics")
+            .with_description("Server uptime and outbound HTTP connection pool statistics")
+            .with_mime_type(JSON_MIME_TYPE)
+    }
+
+    async fn read(&self, _context: MethodContext) -> MaukaResult<Vec<ResourceContents>> {
+        Ok(vec![ResourceContents::json(METRICS_URI, &self.snapshot())?])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MaukaConfig;
+    use serde_json::Value;
+
+    #[tokio::test]
+    async fn test_metrics_snapshot() {
+        let config = MaukaConfig::default();
+        let client = Arc::new(HttpClient::new(&config.http, &config.security).unwrap());
+        let contents = MetricsResource::new(client).read(MethodContext::default()).await.unwrap();
+
+        assert_eq!(contents[0].uri, METRICS_URI);
+        let value: Value = serde_json::from_str(contents[0].text.as_deref().unwrap()).unwrap();
+        assert_eq!(value["uptimeSeconds"], 0);
+        assert_eq!(value["httpPool"]["active"], 0);
+        assert_eq!(value["httpPool"]["created"], 0);
+        assert!(value.get("methods").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_include_method_counters() {
+        let config = MaukaConfig::default();
+        let client = Arc::new(HttpClient::new(&config.http, &config.security).unwrap());
+        let metrics = Arc::new(MethodMetrics::new());
+        metrics.record("tools/call", std::time::Duration::from_micros(250), &Default::default(), true);
+
+        let snapshot = MetricsResource::new(client).with_method_metrics(metrics).snapshot();
+        let value = serde_json::to_value(snapshot).unwrap();
+        assert_eq!(value["methods"]["tools/call"]["calls"], 1);
+        assert_eq!(value["methods"]["tools/call"]["errors"], 1);
+        assert_eq!(value["methods"]["tools/call"]["maxLatencyUs"], 250);
+    }
+}
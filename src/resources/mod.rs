@@ -0,0 +1,47 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! MCP resources.
+//!
+//! Resources and resource templates are registered in a [`ResourceRegistry`],
+//! which the `resources/*` methods are served from.
+//! [`register_builtin_resources`] adds the resources that ship with the server:
+//! the running configuration and a metrics snapshot.
+
+pub mod config;
+pub mod metrics;
+pub mod registry;
+pub mod template;
+pub mod types;
+
+use std::sync::Arc;
+
+use crate::client::HttpClient;
+use crate::config::MaukaConfig;
+use crate::error::MaukaResult;
+use crate::protocol::jsonrpc::middleware::MethodMetrics;
+
+// Re-exports
+pub use config::{ConfigResource, ConfigSectionTemplate};
+pub use metrics::{MetricsResource, MetricsSnapshot};
+pub use registry::{
+    Page, ResourceProvider, ResourceReadOutcome, ResourceRegistry, TemplateProvider, DEFAULT_WATCH_INTERVAL,
+};
+pub use types::{ReadResourceResult, Resource, ResourceContents, ResourceTemplate, ResourceUriParams};
+
+/// Registers the built-in resources, serving `config` and reporting on
+/// `client` and the method calls counted in `methods`.
+pub fn register_builtin_resources(
+    registry: &ResourceRegistry,
+    config: Arc<MaukaConfig>,
+    client: Arc<HttpClient>,
+    methods: Arc<MethodMetrics>,
+) -> MaukaResult<()> {
+    registry.register(ConfigResource::new(config.clone()));
+    registry.register_template(ConfigSectionTemplate::new(config))?;
+    registry.register(MetricsResource::new(client).with_method_metrics(methods));
+    Ok(())
+}
@@ -0,0 +1,579 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Resource registry.
+//!
+//! Resources are registered either individually, as a [`ResourceProvider`]
+//! serving one URI, or as a [`TemplateProvider`] serving every URI matching a
+//! [`ResourceTemplate`]. The registry backs `resources/list`,
+//! `resources/templates/list` and `resources/read`.
+//!
+//! Sessions can subscribe to a URI. [`ResourceRegistry::notify_updated`] sends
+//! `notifications/resources/updated` to every subscriber of a URI, and
+//! [`ResourceRegistry::watch`] calls it whenever the contents of a subscribed
+//! resource change.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::ops::Bound;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use parking_lot::{Mutex, RwLock};
+use tokio_util::sync::CancellationToken;
+
+use super::template::UriTemplate;
+use super::types::{Resource, ResourceContents, ResourceTemplate};
+use crate::error::{MaukaError, MaukaResult};
+use crate::protocol::jsonrpc::types::Request;
+use crate::protocol::{MethodContext, Session};
+
+/// Method of the notification sent to subscribers when a resource changes.
+pub const RESOURCE_UPDATED_NOTIFICATION: &str = "notifications/resources/updated";
+
+/// Number of entries returned per page by default.
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// How often [`ResourceRegistry::watch`] rereads subscribed resources by default.
+pub const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A resource served at a single URI.
+#[async_trait]
+pub trait ResourceProvider: Send + Sync {
+    /// Returns the metadata advertised for this resource.
+    fn resource(&self) -> Resource;
+
+    /// Reads the current contents of the resource.
+    async fn read(&self, context: MethodContext) -> MaukaResult<Vec<ResourceContents>>;
+}
+
+/// A family of resources whose URIs match a template.
+#[async_trait]
+pub trait TemplateProvider: Send + Sync {
+    /// Returns the template advertised for these resources.
+    fn template(&self) -> ResourceTemplate;
+
+    /// Reads the resource at `uri`, given the values of the template's variables.
+    ///
+    /// Returns `None` if no resource exists at `uri` even though it matches
+    /// the template.
+    async fn read(
+        &self,
+        uri: &str,
+        variables: HashMap<String, String>,
+        context: MethodContext,
+    ) -> MaukaResult<Option<Vec<ResourceContents>>>;
+}
+
+/// A resource built from metadata and an async closure.
+struct FnResource<F> {
+    resource: Resource,
+    reader: F,
+}
+
+#[async_trait]
+impl<F, Fut> ResourceProvider for FnResource<F>
+where
+    F: Fn(MethodContext) -> Fut + Send + Sync,
+    Fut: Future<Output = MaukaResult<Vec<ResourceContents>>> + Send,
+{
+    fn resource(&self) -> Resource {
+        self.resource.clone()
+    }
+
+    async fn read(&self, context: MethodContext) -> MaukaResult<Vec<ResourceContents>> {
+        (self.reader)(context).await
+    }
+}
+
+/// A template built from metadata and an async closure.
+struct FnTemplate<F> {
+    template: ResourceTemplate,
+    reader: F,
+}
+
+#[async_trait]
+impl<F, Fut> TemplateProvider for FnTemplate<F>
+where
+    F: Fn(String, HashMap<String, String>, MethodContext) -> Fut + Send + Sync,
+    Fut: Future<Output = MaukaResult<Option<Vec<ResourceContents>>>> + Send,
+{
+    fn template(&self) -> ResourceTemplate {
+        self.template.clone()
+    }
+
+    async fn read(
+        &self,
+        uri: &str,
+        variables: HashMap<String, String>,
+        context: MethodContext,
+    ) -> MaukaResult<Option<Vec<ResourceContents>>> {
+        (self.reader)(uri.to_string(), variables, context).await
+    }
+}
+
+/// A registered template with its parsed form.
+struct RegisteredTemplate {
+    parsed: UriTemplate,
+    provider: Arc<dyn TemplateProvider>,
+}
+
+/// One page of a listing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<T> {
+    /// Entries on this page
+    pub items: Vec<T>,
+
+    /// Cursor for the next page, if there are more entries
+    pub next_cursor: Option<String>,
+}
+
+/// Outcome of looking up and reading a resource.
+#[derive(Debug)]
+pub enum ResourceReadOutcome {
+    /// The resource was read
+    Read(Vec<ResourceContents>),
+
+    /// No resource exists at the requested URI
+    NotFound,
+
+    /// The provider failed to read the resource
+    Failed(MaukaError),
+}
+
+/// Registry of the resources exposed by the server.
+///
+/// Resources are listed in URI order and templates in template order. Listings
+/// are paginated with opaque cursors that stay valid when entries are added or
+/// removed between pages. Registering under a URI or template that is already
+/// taken replaces the previous provider.
+pub struct ResourceRegistry {
+    /// Resources served at a single URI, keyed by URI
+    resources: RwLock<BTreeMap<String, Arc<dyn ResourceProvider>>>,
+
+    /// Templates, keyed by URI template
+    templates: RwLock<BTreeMap<String, RegisteredTemplate>>,
+
+    /// Subscribed sessions, keyed by URI and then by session id
+    subscriptions: Mutex<HashMap<String, HashMap<String, Weak<Session>>>>,
+
+    /// Entries returned per page
+    page_size: usize,
+}
+
+impl Default for ResourceRegistry {
+    fn default() -> Self {
+        Self {
+            resources: RwLock::default(),
+            templates: RwLock::default(),
+            subscriptions: Mutex::default(),
+            page_size: DEFAULT_PAGE_SIZE,
+        }
+    }
+}
+
+impl ResourceRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of entries returned per page.
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Registers a resource under the URI in its metadata.
+    pub fn register<P>(&self, provider: P)
+    where
+        P: ResourceProvider + 'static,
+    {
+        let uri = provider.resource().uri;
+        self.resources.write().insert(uri, Arc::new(provider));
+    }
+
+    /// Registers a resource read by an async closure.
+    pub fn register_fn<F, Fut>(&self, resource: Resource, reader: F)
+    where
+        F: Fn(MethodContext) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = MaukaResult<Vec<ResourceContents>>> + Send + 'static,
+    {
+        self.register(FnResource { resource, reader });
+    }
+
+    /// Registers a template provider.
+    ///
+    /// Fails if the URI template cannot be parsed.
+    pub fn register_template<P>(&self, provider: P) -> MaukaResult<()>
+    where
+        P: TemplateProvider + 'static,
+    {
+        let uri_template = provider.template().uri_template;
+        let parsed = UriTemplate::parse(&uri_template)
+            .ok_or_else(|| MaukaError::Custom(format!("Invalid resource URI template: {uri_template}")))?;
+        self.templates.write().insert(
+            uri_template,
+            RegisteredTemplate {
+                parsed,
+                provider: Arc::new(provider),
+            },
+        );
+        Ok(())
+    }
+
+    /// Registers a template whose resources are read by an async closure.
+    pub fn register_template_fn<F, Fut>(&self, template: ResourceTemplate, reader: F) -> MaukaResult<()>
+    where
+        F: Fn(String, HashMap<String, String>, MethodContext) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = MaukaResult<Option<Vec<ResourceContents>>>> + Send + 'static,
+    {
+        self.register_template(FnTemplate { template, reader })
+    }
+
+    /// Removes a resource, returning whether it was registered.
+    pub fn unregister(&self, uri: &str) -> bool {
+        self.resources.write().remove(uri).is_some()
+    }
+
+    /// Returns whether no resources or templates are registered.
+    pub fn is_empty(&self) -> bool {
+        self.resources.read().is_empty() && self.templates.read().is_empty()
+    }
+
+    /// Returns one page of resource metadata.
+    ///
+    /// Returns `None` if `cursor` was not issued by this registry.
+    pub fn list(&self, cursor: Option<&str>) -> Option<Page<Resource>> {
+        let resources = self.resources.read();
+        paginate(&resources, cursor, self.page_size, |provider| provider.resource())
+    }
+
+    /// Returns one page of templates.
+    ///
+    /// Returns `None` if `cursor` was not issued by this registry.
+    pub fn templates(&self, cursor: Option<&str>) -> Option<Page<ResourceTemplate>> {
+        let templates = self.templates.read();
+        paginate(&templates, cursor, self.page_size, |registered| registered.provider.template())
+    }
+
+    /// Returns whether `uri` names a registered resource or matches a template.
+    pub fn contains(&self, uri: &str) -> bool {
+        self.resources.read().contains_key(uri)
+            || self.templates.read().values().any(|t| t.parsed.matches(uri).is_some())
+    }
+
+    /// Reads the resource at `uri`.
+    ///
+    /// A resource registered under the exact URI takes precedence over
+    /// templates, which are tried in template order.
+    pub async fn read(&self, uri: &str, context: MethodContext) -> ResourceReadOutcome {
+        let provider = self.resources.read().get(uri).cloned();
+        if let Some(provider) = provider {
+            return match provider.read(context).await {
+                Ok(contents) => ResourceReadOutcome::Read(contents),
+                Err(e) => ResourceReadOutcome::Failed(e),
+            };
+        }
+
+        let candidates: Vec<_> = self
+            .templates
+            .read()
+            .values()
+            .filter_map(|t| t.parsed.matches(uri).map(|variables| (t.provider.clone(), variables)))
+            .collect();
+        for (provider, variables) in candidates {
+            match provider.read(uri, variables, context.clone()).await {
+                Ok(Some(contents)) => return ResourceReadOutcome::Read(contents),
+                Ok(None) => continue,
+                Err(e) => return ResourceReadOutcome::Failed(e),
+            }
+        }
+
+        ResourceReadOutcome::NotFound
+    }
+
+    /// Subscribes `session` to updates of `uri`.
+    pub fn subscribe(&self, uri: &str, session: &Arc<Session>) {
+        self.subscriptions
+            .lock()
+            .entry(uri.to_string())
+            .or_default()
+            .insert(session.id().to_string(), Arc::downgrade(session));
+    }
+
+    /// Unsubscribes the session from updates of `uri`, returning whether it was subscribed.
+    pub fn unsubscribe(&self, uri: &str, session_id: &str) -> bool {
+        let mut subscriptions = self.subscriptions.lock();
+        let Some(sessions) = subscriptions.get_mut(uri) else {
+            return false;
+        };
+        let removed = sessions.remove(session_id).is_some();
+        if sessions.is_empty() {
+            subscriptions.remove(uri);
+        }
+        removed
+    }
+
+    /// Returns the URIs that have at least one subscriber.
+    pub fn subscribed_uris(&self) -> Vec<String> {
+        self.subscriptions.lock().keys().cloned().collect()
+    }
+
+    /// Sends `notifications/resources/updated` for `uri` to its subscribers.
+    ///
+    /// Subscribers whose session has ended or can no longer be reached are
+    /// dropped. Returns the number of sessions notified.
+    pub fn notify_updated(&self, uri: &str) -> usize {
+        let sessions: Vec<(String, Weak<Session>)> = match self.subscriptions.lock().get(uri) {
+            Some(sessions) => sessions.iter().map(|(id, s)| (id.clone(), s.clone())).collect(),
+            None => return 0,
+        };
+
+        let notification = Request::notification(
+            RESOURCE_UPDATED_NOTIFICATION,
+            Some(serde_json::json!({ "uri": uri })),
+        );
+        let Ok(message) = serde_json::to_string(&notification) else {
+            return 0;
+        };
+
+        let mut notified = 0;
+        for (id, session) in sessions {
+            if session.upgrade().is_some_and(|session| session.send(message.clone())) {
+                notified += 1;
+            } else {
+                tracing::debug!(uri, session_id = %id, "Dropping unreachable resource subscriber");
+                self.unsubscribe(uri, &id);
+            }
+        }
+        notified
+    }
+
+    /// Rereads subscribed resources every `interval` and notifies their
+    /// subscribers when the contents change, until `shutdown` is cancelled.
+    pub async fn watch(self: Arc<Self>, interval: Duration, shutdown: CancellationToken) {
+        let mut digests: HashMap<String, u64> = HashMap::new();
+        let mut ticks = tokio::time::interval(interval);
+        loop {
+            tokio::select! {
+                _ = ticks.tick() => {}
+                _ = shutdown.cancelled() => return,
+            }
+
+            let uris = self.subscribed_uris();
+            digests.retain(|uri, _| uris.contains(uri));
+            for uri in uris {
+                let ResourceReadOutcome::Read(contents) = self.read(&uri, MethodContext::default()).await else {
+                    continue;
+                };
+                let digest = digest(&contents);
+                if digests.insert(uri.clone(), digest).is_some_and(|previous| previous != digest) {
+                    self.notify_updated(&uri);
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for ResourceRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceRegistry")
+            .field("resources", &self.resources.read().keys().collect::<Vec<_>>())
+            .field("templates", &self.templates.read().keys().collect::<Vec<_>>())
+            .field("page_size", &self.page_size)
+            .finish()
+    }
+}
+
+/// Returns the page of `entries` following `cursor`.
+///
+/// A cursor is the key of the last entry on the previous page, so pages stay
+/// consistent when entries change in between.
+pub(crate) fn paginate<V, T>(
+    entries: &BTreeMap<String, V>,
+    cursor: Option<&str>,
+    page_size: usize,
+    describe: impl Fn(&V) -> T,
+) -> Option<Page<T>> {
+    let start = match cursor {
+        Some(cursor) => {
+            let key = String::from_utf8(URL_SAFE_NO_PAD.decode(cursor).ok()?).ok()?;
+            Bound::Excluded(key)
+        }
+        None => Bound::Unbounded,
+    };
+
+    let mut range = entries.range((start, Bound::Unbounded));
+    let page: Vec<(&String, &V)> = range.by_ref().take(page_size).collect();
+    let next_cursor = match (page.last(), range.next()) {
+        (Some((last, _)), Some(_)) => Some(URL_SAFE_NO_PAD.encode(last.as_bytes())),
+        _ => None,
+    };
+
+    Some(Page {
+        items: page.into_iter().map(|(_, value)| describe(value)).collect(),
+        next_cursor,
+    })
+}
+
+/// Hashes resource contents to detect changes.
+fn digest(contents: &[ResourceContents]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for item in contents {
+        item.uri.hash(&mut hasher);
+        item.text.hash(&mut hasher);
+        item.blob.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use tokio::sync::mpsc;
+
+    fn registry(count: usize) -> ResourceRegistry {
+        let registry = ResourceRegistry::new().with_page_size(2);
+        for i in 0..count {
+            let uri = format!("test://item/{i}");
+            registry.register_fn(Resource::new(uri.clone(), format!("item-{i}")), move |_ctx| {
+                let uri = uri.clone();
+                async move { Ok(vec![ResourceContents::text(uri, "text/plain", "hello")]) }
+            });
+        }
+        registry
+    }
+
+    fn subscriber() -> (Arc<Session>, mpsc::UnboundedReceiver<String>) {
+        let session = Arc::new(Session::new("test"));
+        let (tx, rx) = mpsc::unbounded_channel();
+        session.attach_outbound(tx);
+        (session, rx)
+    }
+
+    #[test]
+    fn test_list_is_paginated() {
+        let registry = registry(5);
+        let mut cursor = None;
+        let mut uris = Vec::new();
+        loop {
+            let page = registry.list(cursor.as_deref()).unwrap();
+            assert!(page.items.len() <= 2);
+            uris.extend(page.items.into_iter().map(|r| r.uri));
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(uris, (0..5).map(|i| format!("test://item/{i}")).collect::<Vec<_>>());
+
+        assert!(registry.list(Some("not base64!")).is_none());
+        let full = ResourceRegistry::new().list(None).unwrap();
+        assert!(full.items.is_empty() && full.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_cursor_survives_changes() {
+        let registry = registry(4);
+        let first = registry.list(None).unwrap();
+        assert!(registry.unregister("test://item/1"));
+
+        let second = registry.list(first.next_cursor.as_deref()).unwrap();
+        let uris: Vec<_> = second.items.into_iter().map(|r| r.uri).collect();
+        assert_eq!(uris, vec!["test://item/2", "test://item/3"]);
+        assert!(second.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_exact_uri_and_templates() {
+        let registry = registry(1);
+        registry
+            .register_template_fn(
+                ResourceTemplate::new("test://greeting/{name}", "greeting"),
+                |uri, variables, _ctx| async move {
+                    let name = &variables["name"];
+                    Ok((name != "nobody").then(|| vec![ResourceContents::text(uri, "text/plain", format!("hi {name}"))]))
+                },
+            )
+            .unwrap();
+
+        let ResourceReadOutcome::Read(contents) = registry.read("test://item/0", MethodContext::default()).await else {
+            panic!("expected resource");
+        };
+        assert_eq!(contents[0].text.as_deref(), Some("hello"));
+
+        let ResourceReadOutcome::Read(contents) = registry.read("test://greeting/ana", MethodContext::default()).await else {
+            panic!("expected templated resource");
+        };
+        assert_eq!(contents[0].text.as_deref(), Some("hi ana"));
+        assert!(registry.contains("test://greeting/ana"));
+
+        for uri in ["test://greeting/nobody", "test://missing"] {
+            assert!(matches!(registry.read(uri, MethodContext::default()).await, ResourceReadOutcome::NotFound));
+        }
+        assert_eq!(registry.templates(None).unwrap().items[0].uri_template, "test://greeting/{name}");
+        assert!(registry
+            .register_template_fn(ResourceTemplate::new("test://{", "broken"), |_, _, _| async { Ok(None) })
+            .is_err());
+    }
+
+    #[test]
+    fn test_notify_reaches_subscribers_only() {
+        let registry = registry(2);
+        let (session, mut rx) = subscriber();
+        let (other, mut other_rx) = subscriber();
+        registry.subscribe("test://item/0", &session);
+        registry.subscribe("test://item/1", &other);
+
+        assert_eq!(registry.notify_updated("test://item/0"), 1);
+        let message: serde_json::Value = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert_eq!(message["method"], RESOURCE_UPDATED_NOTIFICATION);
+        assert_eq!(message["params"]["uri"], "test://item/0");
+        assert!(other_rx.try_recv().is_err());
+
+        assert!(registry.unsubscribe("test://item/0", session.id()));
+        assert_eq!(registry.notify_updated("test://item/0"), 0);
+
+        // Ended sessions are dropped on the next notification
+        drop(other);
+        assert_eq!(registry.notify_updated("test://item/1"), 0);
+        assert!(registry.subscribed_uris().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_watch_notifies_on_change() {
+        let registry = Arc::new(ResourceRegistry::new());
+        let version = Arc::new(AtomicU64::new(0));
+        let counter = version.clone();
+        registry.register_fn(Resource::new("test://counter", "counter"), move |_ctx| {
+            let value = counter.load(Ordering::SeqCst);
+            async move { Ok(vec![ResourceContents::text("test://counter", "text/plain", value.to_string())]) }
+        });
+
+        let (session, mut rx) = subscriber();
+        registry.subscribe("test://counter", &session);
+        let shutdown = CancellationToken::new();
+        let watcher = tokio::spawn(registry.clone().watch(Duration::from_millis(10), shutdown.clone()));
+
+        // Unchanged contents produce no notification
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(rx.try_recv().is_err());
+
+        version.store(1, Ordering::SeqCst);
+        let message = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap().unwrap();
+        assert!(message.contains("test://counter"));
+
+        shutdown.cancel();
+        watcher.await.unwrap();
+    }
+}
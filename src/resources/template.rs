@@ -0,0 +1,125 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Matching of URIs against resource templates.
+//!
+//! Templates use RFC 6570 simple string expansion (`{name}`), the only form the
+//! built-in templates need. A variable matches one non-empty path segment, so
+//! `mauka://config/{section}` matches `mauka://config/http` but not
+//! `mauka://config/http/pool`. Matched values are percent-decoded.
+
+use std::collections::HashMap;
+
+use percent_encoding::percent_decode_str;
+
+/// One piece of a parsed template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    /// Text that must appear verbatim
+    Literal(String),
+
+    /// A `{name}` expression
+    Variable(String),
+}
+
+/// A parsed URI template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UriTemplate {
+    parts: Vec<Part>,
+}
+
+impl UriTemplate {
+    /// Parses a template, returning `None` if a brace is unbalanced, a
+    /// variable is unnamed, or two variables are adjacent.
+    pub fn parse(template: &str) -> Option<Self> {
+        let mut parts = Vec::new();
+        let mut rest = template;
+        while !rest.is_empty() {
+            match rest.find('{') {
+                Some(0) => {
+                    let end = rest.find('}')?;
+                    let name = &rest[1..end];
+                    if name.is_empty() || name.contains('{') || matches!(parts.last(), Some(Part::Variable(_))) {
+                        return None;
+                    }
+                    parts.push(Part::Variable(name.to_string()));
+                    rest = &rest[end + 1..];
+                }
+                Some(start) => {
+                    parts.push(Part::Literal(rest[..start].to_string()));
+                    rest = &rest[start..];
+                }
+                None => {
+                    parts.push(Part::Literal(rest.to_string()));
+                    rest = "";
+                }
+            }
+            if matches!(parts.last(), Some(Part::Literal(text)) if text.contains('}')) {
+                return None;
+            }
+        }
+        Some(Self { parts })
+    }
+
+    /// Matches `uri` against the template, returning the variable values.
+    pub fn matches(&self, uri: &str) -> Option<HashMap<String, String>> {
+        let mut variables = HashMap::new();
+        let mut rest = uri;
+        let mut parts = self.parts.iter().peekable();
+        while let Some(part) = parts.next() {
+            match part {
+                Part::Literal(text) => rest = rest.strip_prefix(text.as_str())?,
+                Part::Variable(name) => {
+                    let end = match parts.peek() {
+                        Some(Part::Literal(next)) => rest.find(next.as_str())?,
+                        _ => rest.len(),
+                    };
+                    let raw = &rest[..end];
+                    if raw.is_empty() || raw.contains('/') {
+                        return None;
+                    }
+                    let value = percent_decode_str(raw).decode_utf8().ok()?;
+                    variables.insert(name.clone(), value.into_owned());
+                    rest = &rest[end..];
+                }
+            }
+        }
+        rest.is_empty().then_some(variables)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_single_segment() {
+        let template = UriTemplate::parse("mauka://config/{section}").unwrap();
+        let variables = template.matches("mauka://config/http").unwrap();
+        assert_eq!(variables["section"], "http");
+
+        assert!(template.matches("mauka://config/").is_none());
+        assert!(template.matches("mauka://config/http/pool").is_none());
+        assert!(template.matches("mauka://metrics/http").is_none());
+    }
+
+    #[test]
+    fn test_matches_several_variables() {
+        let template = UriTemplate::parse("mauka://hosts/{host}/paths/{path}.json").unwrap();
+        let variables = template.matches("mauka://hosts/example.com/paths/a%20b.json").unwrap();
+        assert_eq!(variables["host"], "example.com");
+        assert_eq!(variables["path"], "a b");
+        assert!(template.matches("mauka://hosts/example.com/paths/a.txt").is_none());
+    }
+
+    #[test]
+    fn test_rejects_malformed_templates() {
+        for template in ["mauka://{", "mauka://{}", "mauka://}", "mauka://{a}{b}", "mauka://{a{b}}"] {
+            assert!(UriTemplate::parse(template).is_none(), "{template}");
+        }
+        assert!(UriTemplate::parse("mauka://static").unwrap().matches("mauka://static").is_some());
+    }
+}
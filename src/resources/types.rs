@@ -0,0 +1,198 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! MCP resource wire types.
+//!
+//! These types mirror the shapes defined by the MCP specification for
+//! `resources/list`, `resources/templates/list` and `resources/read`, and
+//! serialize with the spec's camelCase field names.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// MIME type of the JSON documents served by the built-in resources.
+pub const JSON_MIME_TYPE: &str = "application/json";
+
+/// Metadata describing a resource, as advertised by `resources/list`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Resource {
+    /// URI identifying the resource
+    pub uri: String,
+
+    /// Programmatic name
+    pub name: String,
+
+    /// Human-readable display name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Description of what the resource holds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// MIME type of the contents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+impl Resource {
+    /// Creates a resource with the given URI and name.
+    pub fn new(uri: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            uri: uri.into(),
+            name: name.into(),
+            title: None,
+            description: None,
+            mime_type: None,
+        }
+    }
+
+    /// Sets the display name.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the MIME type.
+    pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+}
+
+/// A family of resources whose URIs follow an RFC 6570 template, as
+/// advertised by `resources/templates/list`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceTemplate {
+    /// URI template, such as `mauka://config/{section}`
+    pub uri_template: String,
+
+    /// Programmatic name
+    pub name: String,
+
+    /// Human-readable display name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Description of the resources the template expands to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// MIME type of the contents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+impl ResourceTemplate {
+    /// Creates a template with the given URI template and name.
+    pub fn new(uri_template: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            uri_template: uri_template.into(),
+            name: name.into(),
+            title: None,
+            description: None,
+            mime_type: None,
+        }
+    }
+
+    /// Sets the description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the MIME type.
+    pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+}
+
+/// Contents of a resource, as returned by `resources/read`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceContents {
+    /// URI of the resource
+    pub uri: String,
+
+    /// MIME type of the contents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+
+    /// Text contents, for textual resources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    /// Base64-encoded contents, for binary resources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob: Option<String>,
+}
+
+impl ResourceContents {
+    /// Creates textual contents.
+    pub fn text(uri: impl Into<String>, mime_type: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            uri: uri.into(),
+            mime_type: Some(mime_type.into()),
+            text: Some(text.into()),
+            blob: None,
+        }
+    }
+
+    /// Creates JSON contents from a serializable value.
+    pub fn json<T: Serialize>(uri: impl Into<String>, value: &T) -> serde_json::Result<Self> {
+        Ok(Self::text(uri, JSON_MIME_TYPE, serde_json::to_string_pretty(value)?))
+    }
+}
+
+/// Result of a `resources/read` request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ReadResourceResult {
+    /// Contents of the resource
+    pub contents: Vec<ResourceContents>,
+}
+
+/// Parameters of requests naming a single resource: `resources/read`,
+/// `resources/subscribe` and `resources/unsubscribe`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ResourceUriParams {
+    /// URI of the resource
+    pub uri: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_resource_uses_camel_case() {
+        let resource = Resource::new("mauka://config", "config").with_mime_type(JSON_MIME_TYPE);
+        assert_eq!(
+            serde_json::to_value(resource).unwrap(),
+            json!({"uri": "mauka://config", "name": "config", "mimeType": "application/json"})
+        );
+
+        let template = ResourceTemplate::new("mauka://config/{section}", "config-section");
+        assert_eq!(serde_json::to_value(template).unwrap()["uriTemplate"], "mauka://config/{section}");
+    }
+
+    #[test]
+    fn test_json_contents() {
+        let contents = ResourceContents::json("mauka://x", &json!({"a": 1})).unwrap();
+        assert_eq!(contents.mime_type.as_deref(), Some(JSON_MIME_TYPE));
+        assert_eq!(serde_json::from_str::<serde_json::Value>(contents.text.as_deref().unwrap()).unwrap(), json!({"a": 1}));
+        assert!(contents.blob.is_none());
+    }
+}
@@ -5,7 +5,8 @@
 // We only need fs and tempdir here
 use crate::config::{
     cache::CacheConfig, http::HttpConfig, limits::LimitsConfig, security::SecurityConfig,
-    server::TransportType, ConfigLoader, MaukaConfig, Validate,
+    server::{MethodLimits, ServerConfig, TransportType},
+    ConfigLoader, MaukaConfig, Validate,
 };
 use std::fs;
 use tempfile::tempdir;
@@ -162,4 +163,21 @@ fn test_specific_validation_rules() {
     let mut limits_config = LimitsConfig::default();
     limits_config.memory.warning_threshold = 1.5;
     assert!(limits_config.validate().is_err());
+
+    // Test server validation: concurrency caps beyond what a semaphore holds
+    let server_config = ServerConfig {
+        max_concurrent_requests: usize::MAX,
+        ..Default::default()
+    };
+    assert!(server_config.validate().is_err());
+
+    let mut server_config = ServerConfig::default();
+    server_config.methods.insert(
+        "tools/call".to_string(),
+        MethodLimits {
+            max_concurrent: Some(usize::MAX),
+            ..Default::default()
+        },
+    );
+    assert!(server_config.validate().is_err());
 }
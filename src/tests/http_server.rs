@@ -0,0 +1,61 @@
+//! Loopback HTTP server for testing the outbound HTTP client.
+//!
+//! The server listens on an ephemeral port on `127.0.0.1` and answers every
+//! request with the given handler until the test runtime shuts down. Clients
+//! need [`loopback_security`] to be allowed to reach it.
+
+use std::future::Future;
+use std::net::SocketAddr;
+
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::body::Incoming;
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
+
+use crate::config::security::SecurityConfig;
+
+/// Returns security settings that allow fetching from loopback addresses.
+pub fn loopback_security() -> SecurityConfig {
+    let mut security = SecurityConfig::default();
+    security.url_validation.block_loopback = false;
+    security
+}
+
+/// Spawns a loopback HTTP/1.1 and h2c server and returns its address.
+pub async fn spawn_http_server<F, Fut>(handler: F) -> SocketAddr
+where
+    F: Fn(Request<Incoming>) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Response<Full<Bytes>>> + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(serve_connection(stream, handler.clone()));
+        }
+    });
+
+    addr
+}
+
+/// Serves HTTP/1.1 or HTTP/2 on one connection with the given handler.
+pub async fn serve_connection<I, F, Fut>(io: I, handler: F)
+where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    F: Fn(Request<Incoming>) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Response<Full<Bytes>>> + Send + 'static,
+{
+    let service = service_fn(move |request| {
+        let response = handler(request);
+        async move { Ok::<_, std::convert::Infallible>(response.await) }
+    });
+    let _ = auto::Builder::new(TokioExecutor::new())
+        .serve_connection(TokioIo::new(io), service)
+        .await;
+}
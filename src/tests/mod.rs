@@ -16,8 +16,10 @@
 
 pub mod config_tests;
 pub mod error_tests;
+pub mod http_server;
 pub mod kahuna_queue_tests;
 pub mod test_utils;
+pub mod tls_server;
 
 // Re-export commonly used testing tools to simplify imports in test modules
 pub use test_utils::{
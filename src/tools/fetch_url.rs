@@ -0,0 +1,333 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! The `fetch_url` tool.
+//!
+//! Fetches a URL with the shared [`HttpClient`] and returns the status,
+//! headers, decoded body and timing of the response. Failures are reported
+//! with the [`HttpError`] describing them. Callers that pass a progress token
+//! are told when the fetch starts and when it completes.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use hyper::header::{HeaderMap, HeaderName, HeaderValue};
+use hyper::Method;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use super::registry::Tool;
+use super::types::{CallToolResult, ToolAnnotations, ToolDefinition};
+use crate::client::{DecodedBody, FetchRequest, FetchResponse, HttpClient, UrlValidator};
+use crate::error::http::HttpError;
+use crate::error::MaukaResult;
+use crate::protocol::MethodContext;
+
+/// Name under which the tool is registered.
+pub const FETCH_URL_TOOL: &str = "fetch_url";
+
+/// Longest timeout a caller may request, in seconds.
+const MAX_TIMEOUT_SECS: u64 = 300;
+
+/// Arguments of a `fetch_url` call.
+#[derive(Debug, Deserialize)]
+struct FetchUrlArguments {
+    /// URL to fetch
+    url: String,
+
+    /// Request method, `GET` by default
+    #[serde(default)]
+    method: Option<String>,
+
+    /// Request headers
+    #[serde(default)]
+    headers: HashMap<String, String>,
+
+    /// Request body
+    #[serde(default)]
+    body: Option<String>,
+
+    /// Timeout in seconds
+    #[serde(default)]
+    timeout: Option<u64>,
+
+    /// Whether to follow redirects
+    #[serde(default)]
+    follow_redirects: Option<bool>,
+
+    /// Maximum number of redirects to follow
+    #[serde(default)]
+    max_redirects: Option<usize>,
+}
+
+impl FetchUrlArguments {
+    /// Converts the arguments into a client request.
+    fn into_request(self, validator: &UrlValidator) -> Result<FetchRequest, HttpError> {
+        let url = validator.parse(&self.url)?;
+
+        let method = match self.method {
+            Some(method) => Method::from_bytes(method.to_ascii_uppercase().as_bytes())
+                .map_err(|_| HttpError::InvalidRequest(format!("invalid method: {method}")))?,
+            None => Method::GET,
+        };
+
+        let mut headers = HeaderMap::with_capacity(self.headers.len());
+        for (name, value) in self.headers {
+            let header_name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| HttpError::InvalidRequest(format!("invalid header name: {name}")))?;
+            let header_value = HeaderValue::from_str(&value)
+                .map_err(|_| HttpError::InvalidRequest(format!("invalid value for header {name}")))?;
+            headers.append(header_name, header_value);
+        }
+
+        let mut request = FetchRequest::new(method, url).with_headers(headers);
+        if let Some(body) = self.body {
+            request = request.with_body(body);
+        }
+        if let Some(timeout) = self.timeout {
+            if !(1..=MAX_TIMEOUT_SECS).contains(&timeout) {
+                return Err(HttpError::InvalidRequest(format!(
+                    "timeout must be between 1 and {MAX_TIMEOUT_SECS} seconds"
+                )));
+            }
+            request = request.with_timeout(Duration::from_secs(timeout));
+        }
+        request.follow_redirects = self.follow_redirects;
+        request.max_redirects = self.max_redirects;
+
+        Ok(request)
+    }
+}
+
+/// Tool that fetches a URL.
+#[derive(Debug, Clone)]
+pub struct FetchUrlTool {
+    /// Client performing the fetches
+    client: Arc<HttpClient>,
+}
+
+impl FetchUrlTool {
+    /// Creates the tool on top of the given client.
+    pub fn new(client: Arc<HttpClient>) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl Tool for FetchUrlTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition::new(
+            FETCH_URL_TOOL,
+            json!({
+                "type": "object",
+                "properties": {
+                    "url": {"type": "string", "format": "uri", "description": "URL to fetch"},
+                    "method": {
+                        "type": "string",
+                        "enum": ["GET", "POST", "PUT", "DELETE", "HEAD", "OPTIONS", "PATCH"],
+                        "description": "Request method (default GET)"
+                    },
+                    "headers": {
+                        "type": "object",
+                        "additionalProperties": {"type": "string"},
+                        "description": "Request headers"
+                    },
+                    "body": {"type": "string", "description": "Request body"},
+                    "timeout": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": MAX_TIMEOUT_SECS,
+                        "description": "Timeout in seconds"
+                    },
+                    "follow_redirects": {"type": "boolean"},
+                    "max_redirects": {"type": "integer", "minimum": 0, "maximum": 10}
+                },
+                "required": ["url"]
+            }),
+        )
+        .with_title("Fetch URL")
+        .with_description("Fetch content from a URL and return its status, headers, body and timing")
+        .with_output_schema(json!({
+            "type": "object",
+            "properties": {
+                "url": {"type": "string"},
+                "status": {"type": "integer"},
+                "statusText": {"type": "string"},
+                "httpVersion": {"type": "string"},
+                "headers": {"type": "object"},
+                "body": {"type": "string"},
+                "bodyEncoding": {"type": "string", "enum": ["text", "base64"]},
+                "charset": {"type": "string"},
+                "redirects": {"type": "integer"},
+                "timing": {
+                    "type": "object",
+                    "properties": {
+                        "connectMs": {"type": "number"},
+                        "firstByteMs": {"type": "number"},
+                        "totalMs": {"type": "number"}
+                    }
+                }
+            },
+            "required": ["url", "status", "headers", "body", "bodyEncoding", "timing"]
+        }))
+        .with_annotations(ToolAnnotations {
+            read_only_hint: Some(false),
+            open_world_hint: Some(true),
+            ..Default::default()
+        })
+    }
+
+    async fn call(&self, arguments: Value, context: MethodContext) -> MaukaResult<CallToolResult> {
+        let arguments: FetchUrlArguments = serde_json::from_value(arguments)
+            .map_err(|e| HttpError::InvalidRequest(format!("invalid arguments: {e}")))?;
+        let request = arguments.into_request(self.client.validator())?;
+
+        context.report_progress(0.0, Some(1.0), Some(&format!("Fetching {}", request.url)));
+        let response = self.client.fetch(request).await?;
+        context.report_progress(1.0, Some(1.0), Some("Fetched"));
+
+        Ok(CallToolResult::structured(response_json(&response)))
+    }
+}
+
+/// Renders a response as the tool's structured output.
+fn response_json(response: &FetchResponse) -> Value {
+    let mut headers = Map::new();
+    for name in response.headers.keys() {
+        let mut values: Vec<Value> = response
+            .headers
+            .get_all(name)
+            .iter()
+            .map(|value| Value::String(String::from_utf8_lossy(value.as_bytes()).into_owned()))
+            .collect();
+        let value = if values.len() == 1 {
+            values.remove(0)
+        } else {
+            Value::Array(values)
+        };
+        headers.insert(name.to_string(), value);
+    }
+
+    let mut output = json!({
+        "url": response.url.as_str(),
+        "status": response.status.as_u16(),
+        "statusText": response.status.canonical_reason().unwrap_or_default(),
+        "httpVersion": format!("{:?}", response.version),
+        "headers": headers,
+        "redirects": response.redirects,
+        "timing": {
+            "connectMs": millis(response.timing.connect),
+            "firstByteMs": millis(response.timing.first_byte),
+            "totalMs": millis(response.timing.total),
+        },
+    });
+
+    match response.decoded_body() {
+        DecodedBody::Text { text, charset } => {
+            output["body"] = Value::String(text);
+            output["bodyEncoding"] = json!("text");
+            output["charset"] = json!(charset);
+        }
+        DecodedBody::Binary { base64 } => {
+            output["body"] = Value::String(base64);
+            output["bodyEncoding"] = json!("base64");
+        }
+    }
+
+    output
+}
+
+/// Converts a duration to fractional milliseconds.
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::http::HttpConfig;
+    use crate::config::security::SecurityConfig;
+    use crate::tests::http_server::{loopback_security, spawn_http_server};
+    use crate::tools::ToolRegistry;
+    use crate::tools::ToolCallOutcome;
+    use bytes::Bytes;
+    use http_body_util::Full;
+    use hyper::body::Incoming;
+    use hyper::{Request, Response};
+
+    fn registry(security: &SecurityConfig) -> ToolRegistry {
+        let registry = ToolRegistry::new();
+        let client = Arc::new(HttpClient::new(&HttpConfig::default(), security).unwrap());
+        registry.register(FetchUrlTool::new(client));
+        registry
+    }
+
+    async fn call(registry: &ToolRegistry, arguments: Value) -> CallToolResult {
+        match registry.call(FETCH_URL_TOOL, Some(arguments), MethodContext::default()).await {
+            ToolCallOutcome::Completed(result) => result,
+            ToolCallOutcome::UnknownTool => panic!("fetch_url is not registered"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_returns_response() {
+        let addr = spawn_http_server(|request: Request<Incoming>| async move {
+            let greeting = request.headers().get("x-name").unwrap().to_str().unwrap().to_string();
+            Response::builder()
+                .status(201)
+                .header("content-type", "text/plain; charset=utf-8")
+                .header("set-cookie", "a=1")
+                .header("set-cookie", "b=2")
+                .body(Full::new(Bytes::from(format!("hello {greeting}"))))
+                .unwrap()
+        })
+        .await;
+
+        let result = call(
+            &registry(&loopback_security()),
+            json!({
+                "url": format!("http://{addr}/greet"),
+                "method": "post",
+                "headers": {"X-Name": "mauka"},
+                "body": "{}",
+                "timeout": 5
+            }),
+        )
+        .await;
+
+        assert!(!result.is_error);
+        let output = result.structured_content.unwrap();
+        assert_eq!(output["status"], 201);
+        assert_eq!(output["statusText"], "Created");
+        assert_eq!(output["httpVersion"], "HTTP/1.1");
+        assert_eq!(output["body"], "hello mauka");
+        assert_eq!(output["bodyEncoding"], "text");
+        assert_eq!(output["headers"]["set-cookie"], json!(["a=1", "b=2"]));
+        assert!(output["timing"]["totalMs"].as_f64().unwrap() > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_reports_http_errors() {
+        let registry = registry(&SecurityConfig::default());
+
+        let result = call(&registry, json!({"url": "not a url"})).await;
+        assert!(result.is_error);
+
+        let result = call(&registry, json!({"url": "http://169.254.169.254/latest/meta-data/"})).await;
+        assert!(result.is_error);
+        assert!(format!("{:?}", result.content).contains("link_local_address"));
+
+        let result = call(&registry, json!({"url": "http://example.com/", "timeout": 0})).await;
+        assert!(result.is_error);
+
+        let result = call(&registry, json!({"url": "http://does-not-exist.invalid/"})).await;
+        assert!(result.is_error);
+        let CallToolResult { content, .. } = result;
+        assert!(format!("{content:?}").contains("DNS resolution failed"));
+    }
+}
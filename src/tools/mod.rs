@@ -0,0 +1,31 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! MCP tools.
+//!
+//! Tools are registered in a [`ToolRegistry`], which the `tools/list` and
+//! `tools/call` methods are served from. [`register_builtin_tools`] adds the
+//! tools that ship with the server.
+
+pub mod fetch_url;
+pub mod registry;
+pub mod types;
+
+use std::sync::Arc;
+
+use crate::client::HttpClient;
+
+// Re-exports
+pub use fetch_url::FetchUrlTool;
+pub use registry::{Tool, ToolCallOutcome, ToolRegistry};
+pub use types::{
+    CallToolParams, CallToolResult, EmbeddedResource, ToolAnnotations, ToolContent, ToolDefinition,
+};
+
+/// Registers the built-in tools, fetching through `client`.
+pub fn register_builtin_tools(registry: &ToolRegistry, client: Arc<HttpClient>) {
+    registry.register(FetchUrlTool::new(client));
+}
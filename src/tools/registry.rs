@@ -0,0 +1,227 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Tool registry.
+//!
+//! Every tool registers its [`ToolDefinition`] together with an async executor.
+//! The registry backs both `tools/list`, which is generated from the registered
+//! definitions, and `tools/call`, which dispatches to the executor by name.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde_json::Value;
+
+use super::types::{CallToolResult, ToolDefinition};
+use crate::error::MaukaResult;
+use crate::protocol::MethodContext;
+
+/// A tool that can be invoked through `tools/call`.
+#[async_trait]
+pub trait Tool: Send + Sync {
+    /// Returns the metadata advertised for this tool.
+    fn definition(&self) -> ToolDefinition;
+
+    /// Executes the tool with the given arguments.
+    ///
+    /// Errors are reported to the client as a tool result with `isError` set.
+    async fn call(&self, arguments: Value, context: MethodContext) -> MaukaResult<CallToolResult>;
+}
+
+/// A tool built from a definition and an async closure.
+struct FnTool<F> {
+    definition: ToolDefinition,
+    executor: F,
+}
+
+#[async_trait]
+impl<F, Fut> Tool for FnTool<F>
+where
+    F: Fn(Value, MethodContext) -> Fut + Send + Sync,
+    Fut: Future<Output = MaukaResult<CallToolResult>> + Send,
+{
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn call(&self, arguments: Value, context: MethodContext) -> MaukaResult<CallToolResult> {
+        (self.executor)(arguments, context).await
+    }
+}
+
+/// Outcome of looking up and running a tool.
+#[derive(Debug)]
+pub enum ToolCallOutcome {
+    /// The tool ran; its failures are part of the result
+    Completed(CallToolResult),
+
+    /// No tool with the requested name is registered
+    UnknownTool,
+}
+
+/// Registry of the tools exposed by the server.
+///
+/// Tools are listed in name order. Registering a tool under a name that is
+/// already taken replaces the previous tool.
+#[derive(Default)]
+pub struct ToolRegistry {
+    /// Registered tools, keyed by name
+    tools: RwLock<BTreeMap<String, Arc<dyn Tool>>>,
+}
+
+impl ToolRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a tool under the name in its definition.
+    pub fn register<T>(&self, tool: T)
+    where
+        T: Tool + 'static,
+    {
+        let name = tool.definition().name;
+        self.tools.write().insert(name, Arc::new(tool));
+    }
+
+    /// Registers a tool implemented by an async closure.
+    pub fn register_fn<F, Fut>(&self, definition: ToolDefinition, executor: F)
+    where
+        F: Fn(Value, MethodContext) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = MaukaResult<CallToolResult>> + Send + 'static,
+    {
+        self.register(FnTool {
+            definition,
+            executor,
+        });
+    }
+
+    /// Removes a tool, returning whether it was registered.
+    pub fn unregister(&self, name: &str) -> bool {
+        self.tools.write().remove(name).is_some()
+    }
+
+    /// Returns whether a tool with the given name is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.tools.read().contains_key(name)
+    }
+
+    /// Returns the number of registered tools.
+    pub fn len(&self) -> usize {
+        self.tools.read().len()
+    }
+
+    /// Returns whether no tools are registered.
+    pub fn is_empty(&self) -> bool {
+        self.tools.read().is_empty()
+    }
+
+    /// Returns the definitions of all registered tools, ordered by name.
+    pub fn definitions(&self) -> Vec<ToolDefinition> {
+        self.tools.read().values().map(|tool| tool.definition()).collect()
+    }
+
+    /// Invokes the named tool.
+    ///
+    /// Missing arguments are passed to the tool as an empty object. An error
+    /// returned by the tool is converted into a result with `isError` set.
+    pub async fn call(&self, name: &str, arguments: Option<Value>, context: MethodContext) -> ToolCallOutcome {
+        let Some(tool) = self.tools.read().get(name).cloned() else {
+            return ToolCallOutcome::UnknownTool;
+        };
+
+        let arguments = arguments.unwrap_or_else(|| Value::Object(Default::default()));
+        let result = match tool.call(arguments, context).await {
+            Ok(result) => result,
+            Err(e) => {
+                tracing::debug!(tool = name, error = %e, "Tool call failed");
+                CallToolResult::error(e.to_string())
+            }
+        };
+
+        ToolCallOutcome::Completed(result)
+    }
+}
+
+impl std::fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolRegistry")
+            .field("tools", &self.tools.read().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::MaukaError;
+    use crate::tools::types::ToolContent;
+    use serde_json::json;
+
+    fn registry() -> ToolRegistry {
+        let registry = ToolRegistry::new();
+        registry.register_fn(
+            ToolDefinition::new("echo", json!({"type": "object"})),
+            |arguments, _ctx| async move { Ok(CallToolResult::structured(arguments)) },
+        );
+        registry.register_fn(
+            ToolDefinition::new("fail", json!({"type": "object"})),
+            |_arguments, _ctx| async move { Err(MaukaError::Custom("it broke".to_string())) },
+        );
+        registry
+    }
+
+    #[test]
+    fn test_definitions_are_sorted_by_name() {
+        let names: Vec<String> = registry().definitions().into_iter().map(|d| d.name).collect();
+        assert_eq!(names, vec!["echo", "fail"]);
+    }
+
+    #[tokio::test]
+    async fn test_call_dispatches_by_name() {
+        let outcome = registry().call("echo", Some(json!({"a": 1})), MethodContext::default()).await;
+        let ToolCallOutcome::Completed(result) = outcome else {
+            panic!("expected tool to run");
+        };
+        assert!(!result.is_error);
+        assert_eq!(result.structured_content, Some(json!({"a": 1})));
+    }
+
+    #[tokio::test]
+    async fn test_missing_arguments_default_to_empty_object() {
+        let outcome = registry().call("echo", None, MethodContext::default()).await;
+        let ToolCallOutcome::Completed(result) = outcome else {
+            panic!("expected tool to run");
+        };
+        assert_eq!(result.structured_content, Some(json!({})));
+    }
+
+    #[tokio::test]
+    async fn test_tool_errors_become_error_results() {
+        let outcome = registry().call("fail", None, MethodContext::default()).await;
+        let ToolCallOutcome::Completed(result) = outcome else {
+            panic!("expected tool to run");
+        };
+        assert!(result.is_error);
+        assert_eq!(result.content, vec![ToolContent::text("it broke")]);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_tool() {
+        let registry = registry();
+        assert!(matches!(
+            registry.call("missing", None, MethodContext::default()).await,
+            ToolCallOutcome::UnknownTool
+        ));
+
+        assert!(registry.unregister("echo"));
+        assert!(!registry.contains("echo"));
+        assert_eq!(registry.len(), 1);
+    }
+}
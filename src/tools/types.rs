@@ -0,0 +1,267 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! MCP tool wire types.
+//!
+//! These types mirror the shapes defined by the MCP specification for
+//! `tools/list` and `tools/call`, and serialize with the spec's camelCase field
+//! names.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Metadata describing a tool, as advertised by `tools/list`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolDefinition {
+    /// Unique tool name used to invoke the tool
+    pub name: String,
+
+    /// Human-readable display name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Description of what the tool does
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// JSON Schema of the tool's arguments
+    pub input_schema: Value,
+
+    /// JSON Schema of the tool's structured output, if it produces one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_schema: Option<Value>,
+
+    /// Hints about the tool's behavior
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<ToolAnnotations>,
+}
+
+impl ToolDefinition {
+    /// Creates a definition with the given name and argument schema.
+    pub fn new(name: impl Into<String>, input_schema: Value) -> Self {
+        Self {
+            name: name.into(),
+            title: None,
+            description: None,
+            input_schema,
+            output_schema: None,
+            annotations: None,
+        }
+    }
+
+    /// Sets the description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the display name.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the output schema.
+    pub fn with_output_schema(mut self, output_schema: Value) -> Self {
+        self.output_schema = Some(output_schema);
+        self
+    }
+
+    /// Sets the behavior hints.
+    pub fn with_annotations(mut self, annotations: ToolAnnotations) -> Self {
+        self.annotations = Some(annotations);
+        self
+    }
+}
+
+/// Hints describing how a tool behaves. Clients must treat them as untrusted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolAnnotations {
+    /// The tool does not modify its environment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_only_hint: Option<bool>,
+
+    /// The tool may perform destructive updates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destructive_hint: Option<bool>,
+
+    /// Calling the tool repeatedly with the same arguments has no additional effect
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotent_hint: Option<bool>,
+
+    /// The tool interacts with entities outside the server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_world_hint: Option<bool>,
+}
+
+/// A resource embedded in tool output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddedResource {
+    /// URI of the resource
+    pub uri: String,
+
+    /// MIME type of the resource
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+
+    /// Text contents, for textual resources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    /// Base64-encoded contents, for binary resources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob: Option<String>,
+}
+
+/// One item of tool output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ToolContent {
+    /// Plain text
+    Text {
+        /// The text
+        text: String,
+    },
+
+    /// Base64-encoded image
+    Image {
+        /// Base64-encoded image data
+        data: String,
+        /// MIME type of the image
+        #[serde(rename = "mimeType")]
+        mime_type: String,
+    },
+
+    /// Base64-encoded audio
+    Audio {
+        /// Base64-encoded audio data
+        data: String,
+        /// MIME type of the audio
+        #[serde(rename = "mimeType")]
+        mime_type: String,
+    },
+
+    /// Embedded resource contents
+    Resource {
+        /// The resource
+        resource: EmbeddedResource,
+    },
+}
+
+impl ToolContent {
+    /// Creates a text content item.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text { text: text.into() }
+    }
+}
+
+/// Result of a `tools/call` request.
+///
+/// Failures of the tool itself are reported here with `is_error` set, rather
+/// than as JSON-RPC errors, so that the model invoking the tool can see them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CallToolResult {
+    /// Output of the tool
+    pub content: Vec<ToolContent>,
+
+    /// Structured output matching the tool's output schema
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub structured_content: Option<Value>,
+
+    /// Whether the tool call failed
+    #[serde(default)]
+    pub is_error: bool,
+}
+
+impl CallToolResult {
+    /// Creates a successful result with the given content.
+    pub fn success(content: Vec<ToolContent>) -> Self {
+        Self {
+            content,
+            structured_content: None,
+            is_error: false,
+        }
+    }
+
+    /// Creates a successful result carrying structured output.
+    ///
+    /// The structured value is also serialized into a text item for clients
+    /// that only read `content`.
+    pub fn structured(value: Value) -> Self {
+        Self {
+            content: vec![ToolContent::text(value.to_string())],
+            structured_content: Some(value),
+            is_error: false,
+        }
+    }
+
+    /// Creates a failed result with a single text item describing the failure.
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            content: vec![ToolContent::text(message)],
+            structured_content: None,
+            is_error: true,
+        }
+    }
+}
+
+/// Parameters of a `tools/call` request.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CallToolParams {
+    /// Name of the tool to invoke
+    pub name: String,
+
+    /// Arguments for the tool
+    #[serde(default)]
+    pub arguments: Option<Map<String, Value>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_content_serialization() {
+        let content = vec![
+            ToolContent::text("hello"),
+            ToolContent::Image {
+                data: "aGk=".to_string(),
+                mime_type: "image/png".to_string(),
+            },
+        ];
+        assert_eq!(
+            serde_json::to_value(&content).unwrap(),
+            json!([
+                {"type": "text", "text": "hello"},
+                {"type": "image", "data": "aGk=", "mimeType": "image/png"}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_error_result_serialization() {
+        let value = serde_json::to_value(CallToolResult::error("boom")).unwrap();
+        assert_eq!(
+            value,
+            json!({"content": [{"type": "text", "text": "boom"}], "isError": true})
+        );
+    }
+
+    #[test]
+    fn test_definition_uses_camel_case() {
+        let definition = ToolDefinition::new("echo", json!({"type": "object"}))
+            .with_description("Echoes its arguments");
+        let value = serde_json::to_value(definition).unwrap();
+        assert_eq!(value["inputSchema"], json!({"type": "object"}));
+        assert!(value.get("outputSchema").is_none());
+    }
+}
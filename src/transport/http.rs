@@ -0,0 +1,835 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! MCP Streamable HTTP transport.
+//!
+//! Serves a single MCP endpoint ([`MCP_ENDPOINT`]) on [`ServerConfig::address`]:
+//!
+//! - `POST` carries one JSON-RPC message or batch from the client. Requests are
+//!   answered with an `application/json` body; bodies holding only notifications
+//!   are acknowledged with `202 Accepted`.
+//! - `GET` with `Accept: text/event-stream` opens a Server-Sent Events stream for
+//!   server-to-client messages. Every event carries an `id`, and a client that
+//!   reconnects with `Last-Event-ID` is replayed the events it missed, as long as
+//!   they are still in the session's event buffer.
+//! - `DELETE` terminates the session.
+//!
+//! Sessions are created by a successful `initialize` and identified by the
+//! `Mcp-Session-Id` header, which the client must echo on every later request.
+//! At most [`ConnectionLimits::max_concurrent_connections`] sessions are open at
+//! once; `initialize` is answered with `503 Service Unavailable` beyond that. A
+//! session that sees no request for [`ConnectionLimits::idle_timeout_ms`], and
+//! has neither a request in flight nor an open event stream, is closed. Requests
+//! naming a protocol version the server does not speak in `MCP-Protocol-Version`
+//! are rejected with `400 Bad Request`.
+//!
+//! To keep web pages reached through DNS rebinding away from the endpoint, a
+//! request carrying an `Origin` header is rejected with `403 Forbidden` unless
+//! the origin is on the local machine or listed in
+//! [`ServerConfig::allowed_origins`].
+//!
+//! When the shutdown token is cancelled the transport stops accepting connections,
+//! ends every session's event stream and lets outstanding `POST` requests finish
+//! before returning.
+
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use dashmap::DashMap;
+use futures::StreamExt;
+use http_body_util::combinators::BoxBody;
+use http_body_util::{BodyExt, Full, Limited, StreamBody};
+use hyper::body::{Frame, Incoming};
+use hyper::header::{HeaderValue, ACCEPT, ALLOW, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, ORIGIN};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request as HttpRequest, Response as HttpResponse, StatusCode};
+use hyper_util::rt::TokioIo;
+use parking_lot::Mutex;
+use serde_json::Value;
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, mpsc};
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+use tracing::{debug, info, warn};
+
+use super::{is_allowed_origin, Transport, TransportResult};
+use crate::config::limits::ConnectionLimits;
+use crate::config::server::ServerConfig;
+use crate::error::transport::TransportError;
+use crate::protocol::jsonrpc::error::{ErrorCode, JsonRpcError};
+use crate::protocol::jsonrpc::methods::initialize::is_supported_protocol_version;
+use crate::protocol::jsonrpc::middleware::{AUTHORIZATION_METADATA, REMOTE_ADDR_METADATA};
+use crate::protocol::jsonrpc::message::OutgoingMessage;
+use crate::protocol::jsonrpc::types::{Id, Response};
+use crate::protocol::{JsonRpcHandler, MethodContext, Session};
+
+/// Path of the MCP endpoint.
+pub const MCP_ENDPOINT: &str = "/mcp";
+
+/// Header carrying the session identifier.
+pub const SESSION_HEADER: &str = "mcp-session-id";
+
+/// Header carrying the protocol version negotiated during initialization.
+pub const PROTOCOL_VERSION_HEADER: &str = "mcp-protocol-version";
+
+/// Header a reconnecting SSE client uses to resume after the last event it saw.
+const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+/// Name reported by sessions created by this transport.
+const TRANSPORT_NAME: &str = "http";
+
+/// Number of server-to-client events retained per session for resumption.
+const EVENT_BUFFER_SIZE: usize = 1024;
+
+/// Response body type used by this transport.
+type Body = BoxBody<Bytes, Infallible>;
+
+/// A server-to-client message tagged with its SSE event id.
+#[derive(Debug, Clone)]
+struct StoredEvent {
+    /// Monotonic per-session event id
+    id: u64,
+
+    /// Serialized JSON-RPC message
+    data: String,
+}
+
+/// Bounded log of server-to-client events with live fan-out to SSE streams.
+#[derive(Debug)]
+struct EventLog {
+    /// Recent events, oldest first
+    events: Mutex<VecDeque<StoredEvent>>,
+
+    /// Live subscribers
+    live: broadcast::Sender<StoredEvent>,
+}
+
+impl EventLog {
+    fn new() -> Self {
+        let (live, _) = broadcast::channel(EVENT_BUFFER_SIZE);
+        Self {
+            events: Mutex::new(VecDeque::with_capacity(EVENT_BUFFER_SIZE)),
+            live,
+        }
+    }
+
+    /// Appends a message to the log and publishes it to live streams.
+    fn push(&self, data: String) {
+        let mut events = self.events.lock();
+        let id = events.back().map_or(1, |event| event.id + 1);
+        if events.len() == EVENT_BUFFER_SIZE {
+            events.pop_front();
+        }
+        let event = StoredEvent { id, data };
+        events.push_back(event.clone());
+        // Holding the lock keeps replay and live delivery consistent
+        let _ = self.live.send(event);
+    }
+
+    /// Subscribes to live events and returns the retained events after `last_event_id`.
+    fn subscribe(&self, last_event_id: Option<u64>) -> (Vec<StoredEvent>, broadcast::Receiver<StoredEvent>) {
+        let events = self.events.lock();
+        let receiver = self.live.subscribe();
+        let replay = match last_event_id {
+            Some(last) => events.iter().filter(|e| e.id > last).cloned().collect(),
+            None => Vec::new(),
+        };
+        (replay, receiver)
+    }
+}
+
+/// State kept for each open Streamable HTTP session.
+#[derive(Debug)]
+struct HttpSession {
+    /// Protocol-level session shared with method handlers
+    session: Arc<Session>,
+
+    /// Server-to-client events for this session
+    events: Arc<EventLog>,
+
+    /// When the client last sent a request on this session
+    last_seen: Mutex<Instant>,
+}
+
+impl HttpSession {
+    fn new() -> Self {
+        let session = Arc::new(Session::new(TRANSPORT_NAME));
+        let events = Arc::new(EventLog::new());
+
+        // Move messages the server sends to this session into the event log
+        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+        session.attach_outbound(tx);
+        let log = events.clone();
+        tokio::spawn(async move {
+            while let Some(message) = rx.recv().await {
+                log.push(message);
+            }
+        });
+
+        Self {
+            session,
+            events,
+            last_seen: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Records that the client just used the session.
+    fn touch(&self) {
+        *self.last_seen.lock() = Instant::now();
+    }
+
+    /// Returns true if the session has been unused for `idle_timeout`, with no
+    /// request in flight and no event stream open.
+    fn is_idle(&self, idle_timeout: Duration) -> bool {
+        self.last_seen.lock().elapsed() >= idle_timeout
+            && self.session.in_flight() == 0
+            && self.events.live.receiver_count() == 0
+    }
+
+    /// Ends the event pump and any open event streams.
+    fn close(&self) {
+        self.session.detach_outbound();
+    }
+}
+
+/// Shared state of a running transport.
+struct Shared {
+    handler: JsonRpcHandler,
+    sessions: DashMap<String, Arc<HttpSession>>,
+    max_message_size: usize,
+    max_sessions: usize,
+    allowed_origins: Vec<String>,
+}
+
+/// Transport that serves MCP over Streamable HTTP.
+#[derive(Debug, Clone)]
+pub struct StreamableHttpTransport {
+    /// Address to listen on
+    address: SocketAddr,
+
+    /// Maximum size of a request body in bytes
+    max_message_size: usize,
+
+    /// Maximum number of simultaneously open sessions
+    max_sessions: usize,
+
+    /// Time without any request after which an unused session is closed
+    idle_timeout: Duration,
+
+    /// Origins allowed besides those of the local machine
+    allowed_origins: Vec<String>,
+}
+
+impl StreamableHttpTransport {
+    /// Creates a Streamable HTTP transport from the server and connection configuration.
+    pub fn new(server: &ServerConfig, limits: &ConnectionLimits) -> Self {
+        Self {
+            address: server.address,
+            max_message_size: server.max_message_size,
+            max_sessions: limits.max_concurrent_connections,
+            idle_timeout: Duration::from_millis(limits.idle_timeout_ms),
+            allowed_origins: server.allowed_origins.clone(),
+        }
+    }
+
+    /// Returns the address this transport listens on.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Binds the configured address and serves requests until `shutdown` is
+    /// cancelled.
+    pub async fn run(self, handler: JsonRpcHandler, shutdown: CancellationToken) -> TransportResult<()> {
+        let listener = TcpListener::bind(self.address).await.map_err(|e| {
+            TransportError::Other(format!("Failed to bind {}: {}", self.address, e))
+        })?;
+
+        self.serve(listener, handler, shutdown).await
+    }
+
+    /// Serves requests on an already bound listener.
+    ///
+    /// On cancellation of `shutdown`, returns once every connection has finished
+    /// its outstanding requests.
+    pub async fn serve(
+        self,
+        listener: TcpListener,
+        handler: JsonRpcHandler,
+        shutdown: CancellationToken,
+    ) -> TransportResult<()> {
+        info!(address = ?listener.local_addr().ok(), "Streamable HTTP transport listening");
+
+        let shared = Arc::new(Shared {
+            handler,
+            sessions: DashMap::new(),
+            max_message_size: self.max_message_size,
+            max_sessions: self.max_sessions,
+            allowed_origins: self.allowed_origins,
+        });
+
+        let connections = TaskTracker::new();
+        // Eviction stops with the transport, however serving ends
+        let evicting = shutdown.child_token();
+        let _evicting = evicting.clone().drop_guard();
+        tokio::spawn(evict_idle_sessions(shared.clone(), self.idle_timeout, evicting));
+
+        loop {
+            let Some((stream, peer)) = super::accept(&listener, TRANSPORT_NAME, &shutdown).await else {
+                break;
+            };
+
+            let shared = shared.clone();
+            let shutdown = shutdown.clone();
+            connections.spawn(async move {
+                let service = service_fn(move |request| {
+                    let shared = shared.clone();
+                    async move { Ok::<_, Infallible>(route(&shared, request, peer).await) }
+                });
+
+                let connection = http1::Builder::new().serve_connection(TokioIo::new(stream), service);
+                tokio::pin!(connection);
+                let result = tokio::select! {
+                    result = connection.as_mut() => result,
+                    _ = shutdown.cancelled() => {
+                        // Finish the request in progress, then close the connection
+                        connection.as_mut().graceful_shutdown();
+                        connection.await
+                    }
+                };
+
+                if let Err(e) = result {
+                    debug!(%peer, error = %e, "HTTP connection ended with error");
+                }
+            });
+        }
+
+        // Ending the event pumps closes open SSE streams so their connections can finish
+        for entry in shared.sessions.iter() {
+            entry.close();
+        }
+        shared.sessions.clear();
+
+        info!(connections = connections.len(), "Streamable HTTP transport draining connections");
+        connections.close();
+        connections.wait().await;
+
+        info!("Streamable HTTP transport stopped");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Transport for StreamableHttpTransport {
+    fn name(&self) -> &'static str {
+        TRANSPORT_NAME
+    }
+
+    async fn run(self: Box<Self>, handler: JsonRpcHandler, shutdown: CancellationToken) -> TransportResult<()> {
+        StreamableHttpTransport::run(*self, handler, shutdown).await
+    }
+}
+
+/// Closes sessions left idle for `idle_timeout` until `shutdown` is cancelled.
+async fn evict_idle_sessions(shared: Arc<Shared>, idle_timeout: Duration, shutdown: CancellationToken) {
+    let mut ticks = tokio::time::interval((idle_timeout / 2).max(Duration::from_millis(10)));
+    loop {
+        tokio::select! {
+            _ = ticks.tick() => {}
+            _ = shutdown.cancelled() => return,
+        }
+        shared.sessions.retain(|id, http_session| {
+            if !http_session.is_idle(idle_timeout) {
+                return true;
+            }
+            http_session.close();
+            info!(session_id = id.as_str(), "Streamable HTTP session expired");
+            false
+        });
+    }
+}
+
+/// Dispatches a request to the handler for its method.
+async fn route(shared: &Shared, request: HttpRequest<Incoming>, peer: SocketAddr) -> HttpResponse<Body> {
+    if request.uri().path() != MCP_ENDPOINT {
+        return status_response(StatusCode::NOT_FOUND);
+    }
+    if header_str(&request, ORIGIN.as_str()).is_some_and(|origin| !is_allowed_origin(origin, &shared.allowed_origins)) {
+        warn!(%peer, origin = header_str(&request, ORIGIN.as_str()), "Rejecting request from disallowed origin");
+        return status_response(StatusCode::FORBIDDEN);
+    }
+    if header_str(&request, PROTOCOL_VERSION_HEADER).is_some_and(|v| !is_supported_protocol_version(v)) {
+        return status_response(StatusCode::BAD_REQUEST);
+    }
+
+    match *request.method() {
+        Method::POST => handle_post(shared, request, peer).await,
+        Method::GET => handle_get(shared, &request),
+        Method::DELETE => handle_delete(shared, &request),
+        _ => {
+            let mut response = status_response(StatusCode::METHOD_NOT_ALLOWED);
+            response
+                .headers_mut()
+                .insert(ALLOW, HeaderValue::from_static("GET, POST, DELETE"));
+            response
+        }
+    }
+}
+
+/// Handles a JSON-RPC message posted by the client.
+async fn handle_post(shared: &Shared, request: HttpRequest<Incoming>, peer: SocketAddr) -> HttpResponse<Body> {
+    let is_json = request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    if !is_json {
+        return status_response(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    let declared_length = request
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if declared_length.is_some_and(|len| len > shared.max_message_size) {
+        return payload_too_large(shared.max_message_size);
+    }
+
+    let session_id = header_str(&request, SESSION_HEADER).map(str::to_string);
+    let authorization = header_str(&request, AUTHORIZATION.as_str()).map(str::to_string);
+
+    let body = match Limited::new(request.into_body(), shared.max_message_size).collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(_) => return payload_too_large(shared.max_message_size),
+    };
+    let Ok(message) = std::str::from_utf8(&body) else {
+        return rpc_error_response(StatusCode::BAD_REQUEST, JsonRpcError::parse_error());
+    };
+
+    // A new session is only kept once initialize succeeds on it
+    let (http_session, created) = match session_id {
+        Some(id) => match shared.sessions.get(&id) {
+            Some(entry) => {
+                entry.touch();
+                (entry.clone(), false)
+            }
+            None => return status_response(StatusCode::NOT_FOUND),
+        },
+        None if is_initialize(message) => {
+            if shared.sessions.len() >= shared.max_sessions {
+                warn!(%peer, "Session limit reached, rejecting initialize");
+                return status_response(StatusCode::SERVICE_UNAVAILABLE);
+            }
+            (Arc::new(HttpSession::new()), true)
+        }
+        None => {
+            return rpc_error_response(
+                StatusCode::BAD_REQUEST,
+                JsonRpcError::new(
+                    ErrorCode::InvalidRequest,
+                    "Invalid Request: Missing Mcp-Session-Id header",
+                ),
+            )
+        }
+    };
+
+    let session = http_session.session.clone();
+    let mut context = MethodContext::for_session(session.clone()).with_metadata(REMOTE_ADDR_METADATA, peer.to_string());
+    if let Some(authorization) = authorization {
+        context = context.with_metadata(AUTHORIZATION_METADATA, authorization);
+    }
+    let output = shared.handler.handle_bytes(&body, Some(context)).await;
+    let initialized = matches!(&output, Some(OutgoingMessage::Single(response)) if response.error.is_none());
+    let mut response = match output {
+        Some(output) => json_response(StatusCode::OK, output.to_vec()),
+        None => status_response(StatusCode::ACCEPTED),
+    };
+
+    if created {
+        match HeaderValue::from_str(session.id()) {
+            Ok(value) if initialized => {
+                shared.sessions.insert(session.id().to_string(), http_session);
+                info!(session_id = session.id(), "Streamable HTTP session opened");
+                response.headers_mut().insert(SESSION_HEADER, value);
+            }
+            _ => http_session.close(),
+        }
+    }
+    response
+}
+
+/// Opens an SSE stream of server-to-client messages, replaying missed events.
+fn handle_get(shared: &Shared, request: &HttpRequest<Incoming>) -> HttpResponse<Body> {
+    let accepts_sse = header_str(request, ACCEPT.as_str())
+        .is_some_and(|v| v.contains("text/event-stream"));
+    if !accepts_sse {
+        return status_response(StatusCode::NOT_ACCEPTABLE);
+    }
+
+    let Some(http_session) = header_str(request, SESSION_HEADER)
+        .and_then(|id| shared.sessions.get(id).map(|entry| entry.clone()))
+    else {
+        return status_response(StatusCode::NOT_FOUND);
+    };
+    http_session.touch();
+
+    let last_event_id = header_str(request, LAST_EVENT_ID_HEADER).and_then(|v| v.parse::<u64>().ok());
+    let (replay, live) = http_session.events.subscribe(last_event_id);
+    let replayed_up_to = replay.last().map(|e| e.id).or(last_event_id).unwrap_or(0);
+
+    let live = futures::stream::unfold(live, |mut live| async move {
+        loop {
+            match live.recv().await {
+                Ok(event) => return Some((event, live)),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!(skipped, "SSE stream fell behind, events dropped");
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    })
+    .filter(move |event| futures::future::ready(event.id > replayed_up_to));
+
+    let frames = futures::stream::iter(replay)
+        .chain(live)
+        .map(|event| Ok::<_, Infallible>(Frame::data(Bytes::from(format_sse_event(&event)))));
+
+    let mut response = HttpResponse::new(BodyExt::boxed(StreamBody::new(frames)));
+    let headers = response.headers_mut();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/event-stream"));
+    headers.insert("cache-control", HeaderValue::from_static("no-cache"));
+    response
+}
+
+/// Terminates the session named by the request.
+fn handle_delete(shared: &Shared, request: &HttpRequest<Incoming>) -> HttpResponse<Body> {
+    let Some(id) = header_str(request, SESSION_HEADER) else {
+        return status_response(StatusCode::BAD_REQUEST);
+    };
+
+    match shared.sessions.remove(id) {
+        Some((_, http_session)) => {
+            // Closing the outbound channel ends the event pump and any open streams
+            http_session.close();
+            info!(session_id = id, "Streamable HTTP session terminated");
+            status_response(StatusCode::NO_CONTENT)
+        }
+        None => status_response(StatusCode::NOT_FOUND),
+    }
+}
+
+/// Returns true if the message is an `initialize` request.
+fn is_initialize(message: &str) -> bool {
+    serde_json::from_str::<Value>(message)
+        .ok()
+        .and_then(|value| value.get("method").and_then(Value::as_str).map(|m| m == "initialize"))
+        .unwrap_or(false)
+}
+
+/// Formats a stored event in SSE wire format.
+fn format_sse_event(event: &StoredEvent) -> String {
+    let mut out = format!("id: {}\n", event.id);
+    for line in event.data.lines() {
+        out.push_str("data: ");
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.push('\n');
+    out
+}
+
+/// Returns a request header as a string, if present and valid.
+fn header_str<'a, B>(request: &'a HttpRequest<B>, name: &str) -> Option<&'a str> {
+    request.headers().get(name).and_then(|v| v.to_str().ok())
+}
+
+fn status_response(status: StatusCode) -> HttpResponse<Body> {
+    let mut response = HttpResponse::new(Full::new(Bytes::new()).boxed());
+    *response.status_mut() = status;
+    response
+}
+
+fn json_response(status: StatusCode, body: impl Into<Bytes>) -> HttpResponse<Body> {
+    let mut response = HttpResponse::new(Full::new(body.into()).boxed());
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+fn rpc_error_response(status: StatusCode, error: JsonRpcError) -> HttpResponse<Body> {
+    let body = serde_json::to_string(&Response::error(Id::Null, error)).unwrap_or_default();
+    json_response(status, body)
+}
+
+fn payload_too_large(max_message_size: usize) -> HttpResponse<Body> {
+    rpc_error_response(
+        StatusCode::PAYLOAD_TOO_LARGE,
+        JsonRpcError::with_data(
+            ErrorCode::InvalidRequest,
+            "Invalid Request: Message exceeds maximum size",
+            serde_json::json!({ "max_message_size": max_message_size }),
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+
+    /// Minimal HTTP/1.1 exchange over a fresh connection.
+    async fn send(addr: SocketAddr, method: &str, headers: &[(&str, &str)], body: &str) -> (u16, String, String) {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let mut request = format!("{method} {MCP_ENDPOINT} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n", body.len());
+        for (name, value) in headers {
+            request.push_str(&format!("{name}: {value}\r\n"));
+        }
+        request.push_str("\r\n");
+        request.push_str(body);
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut raw = String::new();
+        stream.read_to_string(&mut raw).await.unwrap();
+        let (head, body) = raw.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (status, head.to_lowercase(), body.to_string())
+    }
+
+    fn session_id(head: &str) -> String {
+        head.lines()
+            .find_map(|line| line.strip_prefix("mcp-session-id: "))
+            .unwrap()
+            .trim()
+            .to_string()
+    }
+
+    async fn start(config: ServerConfig) -> SocketAddr {
+        start_with_limits(config, ConnectionLimits::default()).await
+    }
+
+    async fn start_with_limits(config: ServerConfig, limits: ConnectionLimits) -> SocketAddr {
+        let mut handler = JsonRpcHandler::new();
+        handler.register_method("initialize", |params, _ctx| async move {
+            match params {
+                Some(params) if params["fail"] == true => Err(JsonRpcError::invalid_params("failing on request")),
+                _ => Ok(json!({})),
+            }
+        });
+        handler.register_method("echo", |params, _ctx| async move { Ok(params.unwrap_or(Value::Null)) });
+        handler.register_method("notify", |params, ctx: MethodContext| async move {
+            let notification = crate::protocol::jsonrpc::types::Request::notification("notifications/message", params);
+            let sent = ctx.session.unwrap().send(serde_json::to_string(&notification).unwrap());
+            Ok(json!(sent))
+        });
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(StreamableHttpTransport::new(&config, &limits).serve(listener, handler, CancellationToken::new()));
+        addr
+    }
+
+    const JSON: (&str, &str) = ("Content-Type", "application/json");
+    const INIT: &str = r#"{"jsonrpc":"2.0","method":"initialize","id":1}"#;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_session_lifecycle() {
+        let addr = start(ServerConfig::default()).await;
+
+        let (status, head, _) = send(addr, "POST", &[JSON], INIT).await;
+        assert_eq!(status, 200);
+        let id = session_id(&head);
+
+        let echo = r#"{"jsonrpc":"2.0","method":"echo","params":[1],"id":2}"#;
+        let (status, _, body) = send(addr, "POST", &[JSON, ("Mcp-Session-Id", &id)], echo).await;
+        assert_eq!(status, 200);
+        let response: Response = serde_json::from_str(&body).unwrap();
+        assert_eq!(response.result, Some(json!([1])));
+
+        let notification = r#"{"jsonrpc":"2.0","method":"echo"}"#;
+        let (status, _, _) = send(addr, "POST", &[JSON, ("Mcp-Session-Id", &id)], notification).await;
+        assert_eq!(status, 202);
+
+        let (status, _, _) = send(addr, "DELETE", &[("Mcp-Session-Id", &id)], "").await;
+        assert_eq!(status, 204);
+        let (status, _, _) = send(addr, "POST", &[JSON, ("Mcp-Session-Id", &id)], echo).await;
+        assert_eq!(status, 404);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_failed_initialize_opens_no_session() {
+        let addr = start(ServerConfig::default()).await;
+        let failing = r#"{"jsonrpc":"2.0","method":"initialize","params":{"fail":true},"id":1}"#;
+        let (status, head, body) = send(addr, "POST", &[JSON], failing).await;
+        assert_eq!(status, 200);
+        assert!(!head.contains(SESSION_HEADER));
+        assert!(serde_json::from_str::<Response>(&body).unwrap().error.is_some());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_session_limit_and_idle_eviction() {
+        let limits = ConnectionLimits {
+            max_concurrent_connections: 1,
+            idle_timeout_ms: 100,
+            ..ConnectionLimits::default()
+        };
+        let addr = start_with_limits(ServerConfig::default(), limits).await;
+
+        let (status, head, _) = send(addr, "POST", &[JSON], INIT).await;
+        assert_eq!(status, 200);
+        let id = session_id(&head);
+        let (status, _, _) = send(addr, "POST", &[JSON], INIT).await;
+        assert_eq!(status, 503);
+
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+        let echo = r#"{"jsonrpc":"2.0","method":"echo","id":2}"#;
+        let (status, _, _) = send(addr, "POST", &[JSON, ("Mcp-Session-Id", &id)], echo).await;
+        assert_eq!(status, 404);
+        let (status, _, _) = send(addr, "POST", &[JSON], INIT).await;
+        assert_eq!(status, 200);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_disallowed_origin_is_forbidden() {
+        let config = ServerConfig {
+            allowed_origins: vec!["https://app.example.com".to_string()],
+            ..ServerConfig::default()
+        };
+        let addr = start(config).await;
+        for origin in ["http://localhost:3000", "http://127.0.0.1", "https://app.example.com"] {
+            let (status, _, _) = send(addr, "POST", &[JSON, ("Origin", origin)], INIT).await;
+            assert_eq!(status, 200, "{origin}");
+        }
+        for origin in ["http://evil.example", "null"] {
+            let (status, _, _) = send(addr, "POST", &[JSON, ("Origin", origin)], INIT).await;
+            assert_eq!(status, 403, "{origin}");
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_requests_without_session_are_rejected() {
+        let addr = start(ServerConfig::default()).await;
+        let echo = r#"{"jsonrpc":"2.0","method":"echo","id":2}"#;
+        let (status, _, _) = send(addr, "POST", &[JSON], echo).await;
+        assert_eq!(status, 400);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_unsupported_protocol_version_is_rejected() {
+        let addr = start(ServerConfig::default()).await;
+        let (_, head, _) = send(addr, "POST", &[JSON], INIT).await;
+        let id = session_id(&head);
+
+        let echo = r#"{"jsonrpc":"2.0","method":"echo","id":2}"#;
+        let session = ("Mcp-Session-Id", id.as_str());
+        let (status, _, _) = send(addr, "POST", &[JSON, session, ("MCP-Protocol-Version", "2025-06-18")], echo).await;
+        assert_eq!(status, 200);
+        let (status, _, _) = send(addr, "POST", &[JSON, session, ("MCP-Protocol-Version", "1999-01-01")], echo).await;
+        assert_eq!(status, 400);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_body_size_limit() {
+        let config = ServerConfig {
+            max_message_size: 32,
+            ..ServerConfig::default()
+        };
+        let addr = start(config).await;
+        let big = format!(r#"{{"jsonrpc":"2.0","method":"initialize","params":"{}","id":1}}"#, "x".repeat(64));
+        let (status, _, _) = send(addr, "POST", &[JSON], &big).await;
+        assert_eq!(status, 413);
+    }
+
+    #[test]
+    fn test_event_log_replays_after_last_event_id() {
+        let log = EventLog::new();
+        for i in 0..3 {
+            log.push(format!("message {i}"));
+        }
+
+        let (replay, _) = log.subscribe(Some(1));
+        let ids: Vec<u64> = replay.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![2, 3]);
+
+        let (replay, _) = log.subscribe(None);
+        assert!(replay.is_empty());
+
+        assert_eq!(format_sse_event(&StoredEvent { id: 7, data: "{}".to_string() }), "id: 7\ndata: {}\n\n");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_sse_stream_resumes_from_last_event_id() {
+        let addr = start(ServerConfig::default()).await;
+        let (_, head, _) = send(addr, "POST", &[JSON], INIT).await;
+        let id = session_id(&head);
+
+        // Each call makes the server push one notification to the session
+        for n in 1..=3 {
+            let notify = format!(r#"{{"jsonrpc":"2.0","method":"notify","params":{{"n":{n}}},"id":{n}}}"#);
+            let (status, _, _) = send(addr, "POST", &[JSON, ("Mcp-Session-Id", &id)], &notify).await;
+            assert_eq!(status, 200);
+        }
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let request = format!(
+            "GET {MCP_ENDPOINT} HTTP/1.1\r\nHost: localhost\r\nAccept: text/event-stream\r\nMcp-Session-Id: {id}\r\nLast-Event-ID: 1\r\n\r\n"
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut received = String::new();
+        let mut buf = vec![0u8; 4096];
+        while !received.contains("id: 3") {
+            let n = tokio::time::timeout(std::time::Duration::from_secs(5), stream.read(&mut buf))
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(n > 0, "stream closed early: {received}");
+            received.push_str(&String::from_utf8_lossy(&buf[..n]));
+        }
+
+        assert!(received.to_lowercase().contains("content-type: text/event-stream"));
+        assert!(!received.contains("id: 1\n"));
+        assert!(received.contains("id: 2\n"));
+        assert!(received.contains(r#""n":3"#));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_shutdown_closes_event_streams() {
+        let mut handler = JsonRpcHandler::new();
+        handler.register_method("initialize", |_params, _ctx| async move { Ok(json!({})) });
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let shutdown = CancellationToken::new();
+        let server = tokio::spawn(
+            StreamableHttpTransport::new(&ServerConfig::default(), &ConnectionLimits::default())
+                .serve(listener, handler, shutdown.clone()),
+        );
+
+        let (_, head, _) = send(addr, "POST", &[JSON], INIT).await;
+        let id = session_id(&head);
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let request = format!(
+            "GET {MCP_ENDPOINT} HTTP/1.1\r\nHost: localhost\r\nAccept: text/event-stream\r\nMcp-Session-Id: {id}\r\n\r\n"
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut buf = vec![0u8; 4096];
+        assert!(stream.read(&mut buf).await.unwrap() > 0);
+
+        shutdown.cancel();
+        let result = tokio::time::timeout(std::time::Duration::from_secs(5), server).await;
+        assert!(result.unwrap().unwrap().is_ok());
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Transport layer for the Mauka MCP Server.
+//!
+//! Transports move JSON-RPC messages between MCP clients and the
+//! [`JsonRpcHandler`](crate::protocol::JsonRpcHandler). Each transport owns the
+//! framing and I/O for its medium and hands complete messages to the handler,
+//! writing back whatever the handler produces.
+//!
+//! Several transports can serve the same handler at once; the
+//! [`TransportSupervisor`] runs them side by side and coordinates their shutdown.
+
+pub mod http;
+pub mod stdio;
+pub mod supervisor;
+pub mod websocket;
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use hyper::Uri;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use crate::error::transport::TransportError;
+use crate::protocol::JsonRpcHandler;
+
+// Re-exports
+pub use http::StreamableHttpTransport;
+pub use stdio::StdioTransport;
+pub use supervisor::TransportSupervisor;
+pub use websocket::WebSocketTransport;
+
+/// Result type for transport operations.
+pub type TransportResult<T> = Result<T, TransportError>;
+
+/// A transport that can be run by the [`TransportSupervisor`].
+#[async_trait]
+pub trait Transport: Send {
+    /// Short name of the transport, used in logs.
+    fn name(&self) -> &'static str;
+
+    /// Serves clients until the transport finishes on its own or `shutdown` is
+    /// cancelled.
+    ///
+    /// After cancellation the transport stops accepting new work, lets requests
+    /// already in flight complete and then returns.
+    async fn run(self: Box<Self>, handler: JsonRpcHandler, shutdown: CancellationToken) -> TransportResult<()>;
+}
+
+/// Pause before accepting again after the first failure to accept.
+const MIN_ACCEPT_BACKOFF: Duration = Duration::from_millis(10);
+
+/// Longest pause before accepting again after failures to accept.
+const MAX_ACCEPT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Accepts the next connection on `listener`, or returns `None` once
+/// `shutdown` is cancelled.
+///
+/// Failing to accept, as when the process runs out of file descriptors or a
+/// client aborts during the TCP handshake, is logged and retried after a pause
+/// that doubles up to [`MAX_ACCEPT_BACKOFF`], so it never stops the transport.
+pub(crate) async fn accept(
+    listener: &TcpListener,
+    transport: &'static str,
+    shutdown: &CancellationToken,
+) -> Option<(TcpStream, SocketAddr)> {
+    let mut backoff = MIN_ACCEPT_BACKOFF;
+    loop {
+        let error = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => return Some(accepted),
+                Err(e) => e,
+            },
+            _ = shutdown.cancelled() => return None,
+        };
+        warn!(transport, error = %error, retry_in = ?backoff, "Failed to accept connection");
+        tokio::select! {
+            _ = tokio::time::sleep(backoff) => {}
+            _ = shutdown.cancelled() => return None,
+        }
+        backoff = (backoff * 2).min(MAX_ACCEPT_BACKOFF);
+    }
+}
+
+/// Returns true if a request sent by a page from `origin` may be served: the
+/// origin is on the local machine or listed in `allowed`.
+pub(crate) fn is_allowed_origin(origin: &str, allowed: &[String]) -> bool {
+    if allowed.iter().any(|allowed| allowed.eq_ignore_ascii_case(origin)) {
+        return true;
+    }
+    let Ok(uri) = origin.parse::<Uri>() else {
+        return false;
+    };
+    matches!(uri.host(), Some("localhost" | "127.0.0.1" | "[::1]" | "::1"))
+}
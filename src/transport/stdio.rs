@@ -0,0 +1,351 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Standard I/O transport.
+//!
+//! Reads newline-delimited JSON-RPC messages from stdin, dispatches each one to the
+//! [`JsonRpcHandler`] and writes the responses to stdout, one message per line.
+//! Stdout is reserved for protocol traffic, so all diagnostics go through `tracing`,
+//! which must be configured to write to stderr.
+//!
+//! The whole exchange is a single client [`Session`], passed to the handler
+//! through the [`MethodContext`].
+//!
+//! Messages are handled concurrently, so responses may be written in a different
+//! order than the requests arrived. Reaching EOF on the input, or cancellation of
+//! the shutdown token, stops reading, waits for in-flight requests to finish and
+//! flushes their responses before returning.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, Stdin, Stdout};
+use tokio::sync::mpsc;
+use tokio_util::codec::{AnyDelimiterCodec, AnyDelimiterCodecError, FramedRead};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use super::{Transport, TransportResult};
+use crate::config::server::ServerConfig;
+use crate::error::transport::TransportError;
+use crate::protocol::jsonrpc::error::{ErrorCode, JsonRpcError};
+use crate::protocol::jsonrpc::types::{Id, Response};
+use crate::protocol::{JsonRpcHandler, MethodContext, Session};
+
+/// Name reported by sessions created by this transport.
+const TRANSPORT_NAME: &str = "stdio";
+
+/// Capacity of the queue between request tasks and the output writer.
+const OUTBOUND_QUEUE_CAPACITY: usize = 256;
+
+/// Transport that speaks newline-delimited JSON-RPC over a pair of byte streams.
+///
+/// In production the streams are the process's stdin and stdout (see
+/// [`StdioTransport::new`]); tests can substitute any async reader and writer.
+#[derive(Debug)]
+pub struct StdioTransport<R = Stdin, W = Stdout> {
+    /// Source of incoming messages
+    reader: R,
+
+    /// Sink for outgoing messages
+    writer: W,
+
+    /// Maximum size of a single incoming message in bytes
+    max_message_size: usize,
+}
+
+impl StdioTransport<Stdin, Stdout> {
+    /// Creates a transport bound to the process's stdin and stdout.
+    pub fn new(config: &ServerConfig) -> Self {
+        Self::with_io(tokio::io::stdin(), tokio::io::stdout(), config)
+    }
+}
+
+impl<R, W> StdioTransport<R, W>
+where
+    R: AsyncRead + Unpin + Send,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    /// Creates a transport over the given reader and writer.
+    pub fn with_io(reader: R, writer: W, config: &ServerConfig) -> Self {
+        Self {
+            reader,
+            writer,
+            max_message_size: config.max_message_size,
+        }
+    }
+
+    /// Runs the transport until the input reaches EOF or `shutdown` is cancelled.
+    ///
+    /// Returns `Ok(())` on a clean shutdown, [`TransportError::StdioReadError`] if
+    /// reading fails and [`TransportError::StdioWriteError`] if a response cannot
+    /// be written.
+    pub async fn run(self, handler: JsonRpcHandler, shutdown: CancellationToken) -> TransportResult<()> {
+        let Self {
+            reader,
+            writer,
+            max_message_size,
+        } = self;
+
+        let (tx, rx) = mpsc::channel::<Vec<u8>>(OUTBOUND_QUEUE_CAPACITY);
+        let mut writer_task = tokio::spawn(write_messages(writer, rx));
+        let codec = AnyDelimiterCodec::new_with_max_length(b"\n".to_vec(), b"\n".to_vec(), max_message_size);
+        let mut lines = FramedRead::new(reader, codec);
+
+        let session = Arc::new(Session::new(TRANSPORT_NAME));
+        info!(session_id = session.id(), "Stdio transport started");
+
+        // Forward messages the server initiates, such as notifications, to the writer
+        let (outbound, mut notifications) = mpsc::unbounded_channel::<String>();
+        session.attach_outbound(outbound);
+        let notification_tx = tx.clone();
+        tokio::spawn(async move {
+            while let Some(message) = notifications.recv().await {
+                if notification_tx.send(message.into_bytes()).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        loop {
+            let frame = tokio::select! {
+                frame = lines.next() => frame,
+                _ = shutdown.cancelled() => break,
+                // The writer only exits early if output failed
+                result = &mut writer_task => {
+                    session.detach_outbound();
+                    return flatten_writer_result(result);
+                }
+            };
+
+            let line = match frame {
+                Some(Ok(line)) => line,
+                Some(Err(AnyDelimiterCodecError::MaxChunkLengthExceeded)) => {
+                    warn!(max_message_size, "Discarding oversized stdio message");
+                    let _ = tx.send(oversized_message_response(max_message_size)).await;
+                    continue;
+                }
+                Some(Err(AnyDelimiterCodecError::Io(err))) => {
+                    session.detach_outbound();
+                    drop(tx);
+                    let _ = writer_task.await;
+                    return Err(TransportError::StdioReadError(err));
+                }
+                None => break,
+            };
+
+            if line.trim_ascii().is_empty() {
+                continue;
+            }
+
+            let handler = handler.clone();
+            let context = MethodContext::for_session(session.clone());
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                if let Some(output) = handler.handle_bytes(line.trim_ascii(), Some(context)).await {
+                    // A closed queue means the writer failed; the read loop reports it
+                    let _ = tx.send(output.to_vec()).await;
+                }
+            });
+        }
+
+        debug!("Stdio input finished, draining in-flight requests");
+
+        // The writer exits once every request task has dropped its sender
+        session.detach_outbound();
+        drop(tx);
+        let result = flatten_writer_result(writer_task.await);
+
+        info!("Stdio transport stopped");
+        result
+    }
+}
+
+#[async_trait]
+impl<R, W> Transport for StdioTransport<R, W>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    fn name(&self) -> &'static str {
+        TRANSPORT_NAME
+    }
+
+    async fn run(self: Box<Self>, handler: JsonRpcHandler, shutdown: CancellationToken) -> TransportResult<()> {
+        StdioTransport::run(*self, handler, shutdown).await
+    }
+}
+
+/// Writes queued messages to the output until every sender is dropped.
+async fn write_messages<W>(mut writer: W, mut rx: mpsc::Receiver<Vec<u8>>) -> TransportResult<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    while let Some(message) = rx.recv().await {
+        writer
+            .write_all(&message)
+            .await
+            .map_err(|e| TransportError::StdioWriteError(e.to_string()))?;
+        writer
+            .write_all(b"\n")
+            .await
+            .map_err(|e| TransportError::StdioWriteError(e.to_string()))?;
+        writer
+            .flush()
+            .await
+            .map_err(|e| TransportError::StdioWriteError(e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+/// Converts the writer task's join result into a transport result.
+fn flatten_writer_result(
+    result: Result<TransportResult<()>, tokio::task::JoinError>,
+) -> TransportResult<()> {
+    result.unwrap_or_else(|e| Err(TransportError::StdioWriteError(e.to_string())))
+}
+
+/// Builds the error response sent when a message exceeds the size limit.
+fn oversized_message_response(max_message_size: usize) -> Vec<u8> {
+    let response = Response::error(
+        Id::Null,
+        JsonRpcError::with_data(
+            ErrorCode::InvalidRequest,
+            "Invalid Request: Message exceeds maximum size",
+            serde_json::json!({ "max_message_size": max_message_size }),
+        ),
+    );
+
+    serde_json::to_vec(&response).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use tokio::io::{duplex, AsyncBufReadExt, AsyncReadExt, BufReader};
+
+    fn echo_handler() -> JsonRpcHandler {
+        let mut handler = JsonRpcHandler::new();
+        handler.register_method("echo", |params, _ctx| async move {
+            Ok(params.unwrap_or(Value::Null))
+        });
+        handler
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_request_produces_response_line() {
+        let (mut client_in, server_in) = duplex(4096);
+        let (server_out, client_out) = duplex(4096);
+        let transport = StdioTransport::with_io(server_in, server_out, &ServerConfig::default());
+        let task = tokio::spawn(transport.run(echo_handler(), CancellationToken::new()));
+
+        client_in
+            .write_all(b"{\"jsonrpc\":\"2.0\",\"method\":\"echo\",\"params\":{\"a\":1},\"id\":7}\n")
+            .await
+            .unwrap();
+
+        let mut lines = BufReader::new(client_out).lines();
+        let line = lines.next_line().await.unwrap().unwrap();
+        let response: Response = serde_json::from_str(&line).unwrap();
+        assert_eq!(response.id, Id::Number(7));
+        assert_eq!(response.result, Some(json!({"a": 1})));
+
+        drop(client_in);
+        assert!(task.await.unwrap().is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_notification_produces_no_output() {
+        let (mut client_in, server_in) = duplex(4096);
+        let (server_out, mut client_out) = duplex(4096);
+        let transport = StdioTransport::with_io(server_in, server_out, &ServerConfig::default());
+        let task = tokio::spawn(transport.run(echo_handler(), CancellationToken::new()));
+
+        client_in
+            .write_all(b"{\"jsonrpc\":\"2.0\",\"method\":\"echo\",\"params\":[1]}\n\n")
+            .await
+            .unwrap();
+        drop(client_in);
+        assert!(task.await.unwrap().is_ok());
+
+        let mut output = String::new();
+        client_out.read_to_string(&mut output).await.unwrap();
+        assert!(output.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_requests_share_one_session() {
+        let handler = crate::protocol::jsonrpc::create_handler();
+        let input = concat!(
+            r#"{"jsonrpc":"2.0","method":"tools/list","id":1}"#,
+            "\n",
+            r#"{"jsonrpc":"2.0","method":"initialize","id":2,"params":{"protocolVersion":"2025-06-18","clientInfo":{"name":"c","version":"1"}}}"#,
+            "\n",
+        );
+        let (server_out, mut client_out) = duplex(4096);
+        let transport = StdioTransport::with_io(input.as_bytes(), server_out, &ServerConfig::default());
+        assert!(transport.run(handler, CancellationToken::new()).await.is_ok());
+
+        let mut output = String::new();
+        client_out.read_to_string(&mut output).await.unwrap();
+        let mut responses: Vec<Response> = output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        responses.sort_by_key(|response| response.id.clone() == Id::Number(2));
+        assert_eq!(responses[0].error.as_ref().unwrap().code, ErrorCode::InvalidRequest.code());
+        assert_eq!(responses[1].result.as_ref().unwrap()["protocolVersion"], json!("2025-06-18"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_eof_drains_in_flight_requests() {
+        let mut handler = JsonRpcHandler::new();
+        handler.register_method("slow", |_params, _ctx| async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            Ok(json!("done"))
+        });
+
+        let input = b"{\"jsonrpc\":\"2.0\",\"method\":\"slow\",\"id\":1}\n".to_vec();
+        let (server_out, mut client_out) = duplex(4096);
+        let transport =
+            StdioTransport::with_io(&input[..], server_out, &ServerConfig::default());
+        assert!(transport.run(handler, CancellationToken::new()).await.is_ok());
+
+        let mut output = String::new();
+        client_out.read_to_string(&mut output).await.unwrap();
+        let response: Response = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(response.result, Some(json!("done")));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_oversized_message_is_rejected() {
+        let config = ServerConfig {
+            max_message_size: 16,
+            ..ServerConfig::default()
+        };
+        let input = b"{\"jsonrpc\":\"2.0\",\"method\":\"echo\",\"id\":1}\n".to_vec();
+        let (server_out, mut client_out) = duplex(4096);
+        let transport = StdioTransport::with_io(&input[..], server_out, &config);
+        assert!(transport.run(echo_handler(), CancellationToken::new()).await.is_ok());
+
+        let mut output = String::new();
+        client_out.read_to_string(&mut output).await.unwrap();
+        let response: Response = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(response.error.unwrap().code, ErrorCode::InvalidRequest.code());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_shutdown_stops_reading() {
+        let (_client_in, server_in) = duplex(4096);
+        let (server_out, _client_out) = duplex(4096);
+        let transport = StdioTransport::with_io(server_in, server_out, &ServerConfig::default());
+        let shutdown = CancellationToken::new();
+        let task = tokio::spawn(transport.run(echo_handler(), shutdown.clone()));
+
+        shutdown.cancel();
+        assert!(task.await.unwrap().is_ok());
+    }
+}
@@ -0,0 +1,326 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! Transport supervisor.
+//!
+//! Runs any number of transports concurrently against one shared
+//! [`JsonRpcHandler`], so every transport sees the same methods, caches and
+//! server state. Each transport runs in its own task: one that fails or panics
+//! is logged and the others keep serving.
+//!
+//! Shutdown is coordinated through a single [`CancellationToken`]. Once it is
+//! cancelled, every transport stops accepting new work and gets
+//! [`ServerConfig::shutdown_drain_ms`] to finish the requests it already has in
+//! flight; transports still running after that are aborted.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use super::{StdioTransport, StreamableHttpTransport, Transport, TransportResult, WebSocketTransport};
+use crate::config::server::{ServerConfig, TransportType};
+use crate::config::MaukaConfig;
+use crate::error::transport::TransportError;
+use crate::protocol::JsonRpcHandler;
+
+/// Runs several transports side by side and coordinates their shutdown.
+pub struct TransportSupervisor {
+    /// Transports to run
+    transports: Vec<Box<dyn Transport>>,
+
+    /// Time transports get to drain after shutdown is requested
+    drain_timeout: Duration,
+}
+
+impl TransportSupervisor {
+    /// Creates a supervisor with no transports.
+    pub fn new(drain_timeout: Duration) -> Self {
+        Self {
+            transports: Vec::new(),
+            drain_timeout,
+        }
+    }
+
+    /// Creates a supervisor running the transports selected by the configuration.
+    ///
+    /// [`TransportType::Both`] runs the stdio and WebSocket transports together.
+    pub fn from_config(config: &MaukaConfig) -> Self {
+        let server: &ServerConfig = &config.server;
+        let mut supervisor = Self::new(Duration::from_millis(server.shutdown_drain_ms));
+
+        let websocket = || WebSocketTransport::new(server, &config.limits.connection);
+        match server.transport {
+            TransportType::Stdio => supervisor.add(StdioTransport::new(server)),
+            TransportType::WebSocket => supervisor.add(websocket()),
+            TransportType::Http => supervisor.add(StreamableHttpTransport::new(server, &config.limits.connection)),
+            TransportType::Both => supervisor.add(StdioTransport::new(server)).add(websocket()),
+        };
+
+        supervisor
+    }
+
+    /// Adds a transport to be run.
+    pub fn add<T>(&mut self, transport: T) -> &mut Self
+    where
+        T: Transport + 'static,
+    {
+        self.transports.push(Box::new(transport));
+        self
+    }
+
+    /// Returns the names of the transports that will be run.
+    pub fn transport_names(&self) -> Vec<&'static str> {
+        self.transports.iter().map(|t| t.name()).collect()
+    }
+
+    /// Runs all transports until they finish or `shutdown` is cancelled.
+    ///
+    /// Returns an error only if every transport failed; a transport that fails
+    /// while others are still serving is logged and otherwise ignored.
+    pub async fn run(self, handler: JsonRpcHandler, shutdown: CancellationToken) -> TransportResult<()> {
+        if self.transports.is_empty() {
+            return Err(TransportError::Other("No transports configured".to_string()));
+        }
+
+        let total = self.transports.len();
+        let mut tasks = JoinSet::new();
+        let mut names = HashMap::new();
+        for transport in self.transports {
+            let name = transport.name();
+            let handler = handler.clone();
+            let token = shutdown.child_token();
+            let task = tasks.spawn(async move { transport.run(handler, token).await });
+            names.insert(task.id(), name);
+            info!(transport = name, "Transport started");
+        }
+
+        let mut failures = Vec::new();
+        loop {
+            tokio::select! {
+                joined = tasks.join_next_with_id() => match joined {
+                    Some(joined) => record_exit(&names, joined, &mut failures),
+                    None => break,
+                },
+                _ = shutdown.cancelled() => break,
+            }
+        }
+
+        if !tasks.is_empty() {
+            info!(
+                transports = tasks.len(),
+                drain_ms = self.drain_timeout.as_millis() as u64,
+                "Shutting down, draining transports"
+            );
+            let drained = tokio::time::timeout(self.drain_timeout, async {
+                while let Some(joined) = tasks.join_next_with_id().await {
+                    record_exit(&names, joined, &mut failures);
+                }
+            })
+            .await;
+
+            if drained.is_err() {
+                warn!(transports = tasks.len(), "Drain period elapsed, aborting remaining transports");
+                tasks.shutdown().await;
+            }
+        }
+
+        if failures.len() == total {
+            return Err(failures.pop().unwrap_or(TransportError::Closed));
+        }
+        Ok(())
+    }
+
+    /// Runs all transports until they finish or the process receives SIGINT or
+    /// SIGTERM, which triggers a coordinated shutdown.
+    pub async fn run_until_signal(self, handler: JsonRpcHandler) -> TransportResult<()> {
+        let shutdown = CancellationToken::new();
+        let signals = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move {
+                wait_for_signal().await;
+                info!("Shutdown signal received");
+                shutdown.cancel();
+            }
+        });
+
+        let result = self.run(handler, shutdown).await;
+        signals.abort();
+        result
+    }
+}
+
+impl std::fmt::Debug for TransportSupervisor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransportSupervisor")
+            .field("transports", &self.transport_names())
+            .field("drain_timeout", &self.drain_timeout)
+            .finish()
+    }
+}
+
+/// Logs how a transport task ended, recording failures.
+fn record_exit(
+    names: &HashMap<tokio::task::Id, &'static str>,
+    joined: Result<(tokio::task::Id, TransportResult<()>), tokio::task::JoinError>,
+    failures: &mut Vec<TransportError>,
+) {
+    match joined {
+        Ok((id, Ok(()))) => info!(transport = names[&id], "Transport stopped"),
+        Ok((id, Err(e))) => {
+            error!(transport = names[&id], error = %e, "Transport failed");
+            failures.push(e);
+        }
+        Err(e) => {
+            let name = names.get(&e.id()).copied().unwrap_or("unknown");
+            error!(transport = name, error = %e, "Transport task panicked");
+            failures.push(TransportError::Other(format!("Transport {name} panicked: {e}")));
+        }
+    }
+}
+
+/// Resolves when the process receives SIGINT or, on Unix, SIGTERM.
+async fn wait_for_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!(error = %e, "Failed to listen for SIGINT");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    /// Transport whose behaviour is scripted by the test.
+    struct FakeTransport {
+        name: &'static str,
+        behaviour: Behaviour,
+        drained: Arc<AtomicBool>,
+    }
+
+    enum Behaviour {
+        Fail,
+        Panic,
+        /// Waits for shutdown, then takes this long to drain
+        Serve(Duration),
+    }
+
+    impl FakeTransport {
+        fn new(name: &'static str, behaviour: Behaviour) -> (Self, Arc<AtomicBool>) {
+            let drained = Arc::new(AtomicBool::new(false));
+            let transport = Self {
+                name,
+                behaviour,
+                drained: drained.clone(),
+            };
+            (transport, drained)
+        }
+    }
+
+    #[async_trait]
+    impl Transport for FakeTransport {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        async fn run(self: Box<Self>, _handler: JsonRpcHandler, shutdown: CancellationToken) -> TransportResult<()> {
+            match self.behaviour {
+                Behaviour::Fail => Err(TransportError::Other("bind failed".to_string())),
+                Behaviour::Panic => panic!("transport crashed"),
+                Behaviour::Serve(drain) => {
+                    shutdown.cancelled().await;
+                    tokio::time::sleep(drain).await;
+                    self.drained.store(true, Ordering::SeqCst);
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failing_transport_does_not_stop_others() {
+        let (failing, _) = FakeTransport::new("failing", Behaviour::Fail);
+        let (panicking, _) = FakeTransport::new("panicking", Behaviour::Panic);
+        let (healthy, drained) = FakeTransport::new("healthy", Behaviour::Serve(Duration::ZERO));
+
+        let mut supervisor = TransportSupervisor::new(Duration::from_secs(5));
+        supervisor.add(failing).add(panicking).add(healthy);
+        let shutdown = CancellationToken::new();
+        let task = tokio::spawn(supervisor.run(JsonRpcHandler::new(), shutdown.clone()));
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!task.is_finished());
+
+        shutdown.cancel();
+        assert!(task.await.unwrap().is_ok());
+        assert!(drained.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_all_transports_failing_is_an_error() {
+        let (failing, _) = FakeTransport::new("failing", Behaviour::Fail);
+        let mut supervisor = TransportSupervisor::new(Duration::from_secs(5));
+        supervisor.add(failing);
+
+        let result = supervisor.run(JsonRpcHandler::new(), CancellationToken::new()).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_slow_transport_is_aborted_after_drain_period() {
+        let (slow, drained) = FakeTransport::new("slow", Behaviour::Serve(Duration::from_secs(60)));
+        let mut supervisor = TransportSupervisor::new(Duration::from_millis(50));
+        supervisor.add(slow);
+
+        let shutdown = CancellationToken::new();
+        shutdown.cancel();
+        let result = tokio::time::timeout(
+            Duration::from_secs(5),
+            supervisor.run(JsonRpcHandler::new(), shutdown),
+        )
+        .await
+        .unwrap();
+
+        assert!(result.is_ok());
+        assert!(!drained.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_both_runs_stdio_and_websocket() {
+        let mut config = MaukaConfig::default();
+        config.server.transport = TransportType::Both;
+        let supervisor = TransportSupervisor::from_config(&config);
+        assert_eq!(supervisor.transport_names(), vec!["stdio", "websocket"]);
+    }
+}
@@ -0,0 +1,542 @@
+// Copyright (c) 2025 Mauka MCP Authors
+//
+// Licensed under dual license:
+// - MIT License (LICENSE-MIT or https://opensource.org/licenses/MIT)
+// - Apache License, Version 2.0 (LICENSE-APACHE or https://www.apache.org/licenses/LICENSE-2.0)
+
+//! WebSocket transport.
+//!
+//! Listens on [`ServerConfig::address`] and accepts WebSocket connections, each of
+//! which carries JSON-RPC messages as text frames. Every connection gets its own
+//! [`Session`], passed to the handler through the [`MethodContext`], and may have
+//! any number of requests in flight at once. Once
+//! [`ConnectionLimits::max_concurrent_connections`] are open, further handshakes
+//! are answered with `503 Service Unavailable`.
+//!
+//! Connections are kept alive with ping frames sent every half
+//! [`ConnectionLimits::idle_timeout_ms`]. A connection that produces no frames at
+//! all, pongs included, for a full idle timeout is closed, as is one that does
+//! not complete its handshake within that time.
+//!
+//! As with the Streamable HTTP transport, a handshake carrying an `Origin`
+//! header is rejected with `403 Forbidden` unless the origin is on the local
+//! machine or listed in [`ServerConfig::allowed_origins`], so that web pages
+//! cannot open cross-site connections to the server.
+//!
+//! When the shutdown token is cancelled the transport stops accepting connections
+//! and stops reading from open ones, lets their in-flight requests finish and
+//! then closes each socket with a close frame.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, Semaphore};
+use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
+use tokio_tungstenite::tungstenite::handshake::server::{
+    ErrorResponse, Request as HandshakeRequest, Response as HandshakeResponse,
+};
+use tokio_tungstenite::tungstenite::http::header::{AUTHORIZATION, ORIGIN};
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+use tracing::{debug, info, warn};
+
+use super::{is_allowed_origin, Transport, TransportResult};
+use crate::config::limits::ConnectionLimits;
+use crate::config::server::ServerConfig;
+use crate::error::transport::TransportError;
+use crate::protocol::jsonrpc::error::{ErrorCode, JsonRpcError};
+use crate::protocol::jsonrpc::middleware::{AUTHORIZATION_METADATA, REMOTE_ADDR_METADATA};
+use crate::protocol::jsonrpc::types::{Id, Response};
+use crate::protocol::{JsonRpcHandler, MethodContext, Session};
+
+/// Name reported by sessions created by this transport.
+const TRANSPORT_NAME: &str = "websocket";
+
+/// Capacity of the per-connection queue between request tasks and the socket writer.
+const OUTBOUND_QUEUE_CAPACITY: usize = 256;
+
+/// Transport that serves JSON-RPC over WebSocket connections.
+#[derive(Debug, Clone)]
+pub struct WebSocketTransport {
+    /// Address to listen on
+    address: SocketAddr,
+
+    /// Maximum size of a single incoming message in bytes
+    max_message_size: usize,
+
+    /// Maximum number of simultaneously open connections
+    max_connections: usize,
+
+    /// Time without any frame from the client after which a connection is closed
+    idle_timeout: Duration,
+
+    /// Origins allowed to connect besides those of the local machine
+    allowed_origins: Arc<[String]>,
+}
+
+impl WebSocketTransport {
+    /// Creates a WebSocket transport from the server and connection configuration.
+    pub fn new(server: &ServerConfig, limits: &ConnectionLimits) -> Self {
+        Self {
+            address: server.address,
+            max_message_size: server.max_message_size,
+            max_connections: limits.max_concurrent_connections,
+            idle_timeout: Duration::from_millis(limits.idle_timeout_ms),
+            allowed_origins: server.allowed_origins.clone().into(),
+        }
+    }
+
+    /// Returns the address this transport listens on.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Binds the configured address and serves connections until `shutdown` is
+    /// cancelled.
+    pub async fn run(self, handler: JsonRpcHandler, shutdown: CancellationToken) -> TransportResult<()> {
+        let listener = TcpListener::bind(self.address).await.map_err(|e| {
+            TransportError::WebSocketConnectionError(format!(
+                "Failed to bind {}: {}",
+                self.address, e
+            ))
+        })?;
+
+        self.serve(listener, handler, shutdown).await
+    }
+
+    /// Serves connections accepted from an already bound listener.
+    ///
+    /// On cancellation of `shutdown`, returns once every open connection has
+    /// drained its in-flight requests and been closed.
+    pub async fn serve(
+        self,
+        listener: TcpListener,
+        handler: JsonRpcHandler,
+        shutdown: CancellationToken,
+    ) -> TransportResult<()> {
+        let local_addr = listener.local_addr().ok();
+        info!(address = ?local_addr, "WebSocket transport listening");
+
+        let connection_slots = Arc::new(Semaphore::new(self.max_connections));
+        let connections = TaskTracker::new();
+
+        loop {
+            let Some((stream, peer)) = super::accept(&listener, TRANSPORT_NAME, &shutdown).await else {
+                break;
+            };
+
+            let Ok(permit) = connection_slots.clone().try_acquire_owned() else {
+                warn!(%peer, "Connection limit reached, rejecting WebSocket connection");
+                connections.spawn(refuse_connection(stream, self.idle_timeout, shutdown.clone()));
+                continue;
+            };
+
+            let transport = self.clone();
+            let handler = handler.clone();
+            let shutdown = shutdown.clone();
+            connections.spawn(async move {
+                if let Err(e) = transport.handle_connection(stream, peer, handler, shutdown).await {
+                    debug!(%peer, error = %e, "WebSocket connection ended with error");
+                }
+                drop(permit);
+            });
+        }
+
+        info!(connections = connections.len(), "WebSocket transport draining connections");
+        connections.close();
+        connections.wait().await;
+
+        info!("WebSocket transport stopped");
+        Ok(())
+    }
+
+    /// Runs a single connection until the client disconnects, goes idle or the
+    /// transport shuts down.
+    async fn handle_connection(
+        &self,
+        stream: TcpStream,
+        peer: SocketAddr,
+        handler: JsonRpcHandler,
+        shutdown: CancellationToken,
+    ) -> TransportResult<()> {
+        let config = WebSocketConfig::default()
+            .max_message_size(Some(self.max_message_size))
+            .max_frame_size(Some(self.max_message_size));
+        // Calls on the connection are authenticated with the handshake's credentials
+        let mut authorization = None;
+        #[allow(clippy::result_large_err)] // the error type is tungstenite's
+        let remember_authorization = |request: &HandshakeRequest, response: HandshakeResponse| {
+            let origin = request.headers().get(ORIGIN).map(|v| v.to_str().unwrap_or_default());
+            if origin.is_some_and(|origin| !is_allowed_origin(origin, &self.allowed_origins)) {
+                warn!(%peer, origin, "Rejecting WebSocket connection from disallowed origin");
+                return Err(error_response(StatusCode::FORBIDDEN));
+            }
+            authorization = request
+                .headers()
+                .get(AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
+            Ok(response)
+        };
+        let handshake = tokio_tungstenite::accept_hdr_async_with_config(stream, remember_authorization, Some(config));
+        let socket = tokio::select! {
+            socket = tokio::time::timeout(self.idle_timeout, handshake) => socket
+                .map_err(|_| TransportError::WebSocketConnectionError("handshake timed out".to_string()))?
+                .map_err(|e| TransportError::WebSocketConnectionError(e.to_string()))?,
+            _ = shutdown.cancelled() => return Ok(()),
+        };
+        let mut base_context = MethodContext::default().with_metadata(REMOTE_ADDR_METADATA, peer.to_string());
+        if let Some(authorization) = authorization {
+            base_context = base_context.with_metadata(AUTHORIZATION_METADATA, authorization);
+        }
+
+        let session = Arc::new(Session::new(TRANSPORT_NAME));
+        info!(%peer, session_id = session.id(), "WebSocket session opened");
+
+        let (mut sink, mut stream) = socket.split();
+        let (tx, mut rx) = mpsc::channel::<Message>(OUTBOUND_QUEUE_CAPACITY);
+
+        let mut writer_task = tokio::spawn(async move {
+            while let Some(message) = rx.recv().await {
+                let closing = matches!(message, Message::Close(_));
+                sink.send(message)
+                    .await
+                    .map_err(|e| TransportError::WebSocketSendError(e.to_string()))?;
+                if closing {
+                    break;
+                }
+            }
+            Ok::<_, TransportError>(())
+        });
+
+        // Forward messages the server initiates, such as notifications, to the writer
+        let (outbound, mut notifications) = mpsc::unbounded_channel::<String>();
+        session.attach_outbound(outbound);
+        let notification_tx = tx.clone();
+        tokio::spawn(async move {
+            while let Some(message) = notifications.recv().await {
+                if notification_tx.send(Message::Text(message.into())).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut keepalive = tokio::time::interval(self.idle_timeout / 2);
+        keepalive.tick().await;
+        let mut last_seen = Instant::now();
+        let requests = TaskTracker::new();
+        let mut writer_finished = false;
+
+        let result = loop {
+            let frame = tokio::select! {
+                frame = stream.next() => frame,
+                _ = shutdown.cancelled() => {
+                    // Let in-flight requests deliver their responses before closing
+                    requests.close();
+                    requests.wait().await;
+                    let _ = tx.send(Message::Close(None)).await;
+                    break Ok(());
+                }
+                _ = keepalive.tick() => {
+                    if last_seen.elapsed() >= self.idle_timeout {
+                        let _ = tx.send(Message::Close(None)).await;
+                        break Err(TransportError::WebSocketConnectionClosed(
+                            "idle timeout".to_string(),
+                        ));
+                    }
+                    let _ = tx.send(Message::Ping(Default::default())).await;
+                    continue;
+                }
+                result = &mut writer_task => {
+                    writer_finished = true;
+                    break result.unwrap_or_else(|e| {
+                        Err(TransportError::WebSocketSendError(e.to_string()))
+                    });
+                }
+            };
+
+            let message = match frame {
+                Some(Ok(message)) => message,
+                Some(Err(e)) => break Err(TransportError::WebSocketReceiveError(e.to_string())),
+                None => break Ok(()),
+            };
+            last_seen = Instant::now();
+
+            match message {
+                Message::Text(text) => {
+                    let handler = handler.clone();
+                    let context = MethodContext {
+                        session: Some(session.clone()),
+                        ..base_context.clone()
+                    };
+                    let tx = tx.clone();
+                    requests.spawn(async move {
+                        if let Some(output) = handler.handle_bytes(text.as_bytes(), Some(context)).await {
+                            let _ = tx.send(Message::Text(output.to_json_string().into())).await;
+                        }
+                    });
+                }
+                Message::Binary(_) => {
+                    let response = Response::error(
+                        Id::Null,
+                        JsonRpcError::new(
+                            ErrorCode::InvalidRequest,
+                            "Invalid Request: Binary frames are not supported, send JSON-RPC as text",
+                        ),
+                    );
+                    if let Ok(output) = serde_json::to_string(&response) {
+                        let _ = tx.send(Message::Text(output.into())).await;
+                    }
+                }
+                Message::Close(_) => break Ok(()),
+                // Pings are answered by tungstenite; pongs only refresh `last_seen`
+                Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => {}
+            }
+        };
+
+        // The writer exits after a close frame or once every request task is done
+        session.detach_outbound();
+        drop(tx);
+        if !writer_finished {
+            let _ = writer_task.await;
+        }
+
+        info!(%peer, session_id = session.id(), "WebSocket session closed");
+        result
+    }
+}
+
+/// Answers a connection's handshake with `503 Service Unavailable`, giving up
+/// after `timeout` or once `shutdown` is cancelled.
+async fn refuse_connection(stream: TcpStream, timeout: Duration, shutdown: CancellationToken) {
+    #[allow(clippy::result_large_err)] // the error type is tungstenite's
+    let refuse = |_: &HandshakeRequest, _: HandshakeResponse| -> Result<HandshakeResponse, ErrorResponse> {
+        Err(error_response(StatusCode::SERVICE_UNAVAILABLE))
+    };
+    tokio::select! {
+        _ = tokio::time::timeout(timeout, tokio_tungstenite::accept_hdr_async(stream, refuse)) => {}
+        _ = shutdown.cancelled() => {}
+    }
+}
+
+/// Builds the response refusing a handshake with `status`.
+fn error_response(status: StatusCode) -> ErrorResponse {
+    let mut response = ErrorResponse::new(None);
+    *response.status_mut() = status;
+    response
+}
+
+#[async_trait]
+impl Transport for WebSocketTransport {
+    fn name(&self) -> &'static str {
+        TRANSPORT_NAME
+    }
+
+    async fn run(self: Box<Self>, handler: JsonRpcHandler, shutdown: CancellationToken) -> TransportResult<()> {
+        WebSocketTransport::run(*self, handler, shutdown).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use tokio_tungstenite::connect_async;
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+    use tokio_tungstenite::tungstenite::http::HeaderValue;
+
+    async fn start(handler: JsonRpcHandler, limits: ConnectionLimits) -> SocketAddr {
+        start_with_shutdown(handler, limits, CancellationToken::new()).await.0
+    }
+
+    async fn start_with_shutdown(
+        handler: JsonRpcHandler,
+        limits: ConnectionLimits,
+        shutdown: CancellationToken,
+    ) -> (SocketAddr, tokio::task::JoinHandle<TransportResult<()>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let transport = WebSocketTransport::new(&ServerConfig::default(), &limits);
+        (addr, tokio::spawn(transport.serve(listener, handler, shutdown)))
+    }
+
+    async fn next_response<S>(stream: &mut S) -> Response
+    where
+        S: futures::Stream<Item = tokio_tungstenite::tungstenite::Result<Message>> + Unpin,
+    {
+        loop {
+            match stream.next().await.unwrap().unwrap() {
+                Message::Text(text) => return serde_json::from_str(text.as_str()).unwrap(),
+                _ => continue,
+            }
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_sessions_are_per_connection() {
+        let mut handler = JsonRpcHandler::new();
+        handler.register_method("whoami", |_params, ctx: MethodContext| async move {
+            Ok(json!(ctx.session.map(|s| s.id().to_string())))
+        });
+        let addr = start(handler, ConnectionLimits::default()).await;
+
+        let request = r#"{"jsonrpc":"2.0","method":"whoami","id":1}"#;
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            let (mut socket, _) = connect_async(format!("ws://{addr}")).await.unwrap();
+            socket.send(Message::Text(request.into())).await.unwrap();
+            socket.send(Message::Text(request.into())).await.unwrap();
+            let first = next_response(&mut socket).await.result.unwrap();
+            let second = next_response(&mut socket).await.result.unwrap();
+            assert_eq!(first, second);
+            ids.push(first);
+        }
+
+        assert!(ids[0].is_string());
+        assert_ne!(ids[0], ids[1]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_requests_run_concurrently() {
+        let mut handler = JsonRpcHandler::new();
+        handler.register_method("sleep", |params: Option<Value>, _ctx| async move {
+            let ms = params.as_ref().and_then(|p| p["ms"].as_u64()).unwrap_or(0);
+            tokio::time::sleep(Duration::from_millis(ms)).await;
+            Ok(json!(ms))
+        });
+        let addr = start(handler, ConnectionLimits::default()).await;
+
+        let (mut socket, _) = connect_async(format!("ws://{addr}")).await.unwrap();
+        let slow = r#"{"jsonrpc":"2.0","method":"sleep","params":{"ms":300},"id":"slow"}"#;
+        let fast = r#"{"jsonrpc":"2.0","method":"sleep","params":{"ms":0},"id":"fast"}"#;
+        socket.send(Message::Text(slow.into())).await.unwrap();
+        socket.send(Message::Text(fast.into())).await.unwrap();
+
+        // The fast request overtakes the slow one on the same socket
+        assert_eq!(next_response(&mut socket).await.id, Id::String("fast".to_string()));
+        assert_eq!(next_response(&mut socket).await.id, Id::String("slow".to_string()));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_disallowed_origin_is_forbidden() {
+        let addr = start(JsonRpcHandler::new(), ConnectionLimits::default()).await;
+        let connect = |origin: &'static str| {
+            let mut request = format!("ws://{addr}").into_client_request().unwrap();
+            request.headers_mut().insert(ORIGIN, HeaderValue::from_static(origin));
+            connect_async(request)
+        };
+
+        let Err(tokio_tungstenite::tungstenite::Error::Http(response)) = connect("http://evil.example").await else {
+            panic!("expected the handshake to be refused");
+        };
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert!(connect("http://localhost:3000").await.is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_connections_over_limit_are_refused() {
+        let limits = ConnectionLimits {
+            max_concurrent_connections: 1,
+            ..ConnectionLimits::default()
+        };
+        let addr = start(JsonRpcHandler::new(), limits).await;
+
+        let (first, _) = connect_async(format!("ws://{addr}")).await.unwrap();
+        let Err(tokio_tungstenite::tungstenite::Error::Http(response)) = connect_async(format!("ws://{addr}")).await
+        else {
+            panic!("expected the handshake to be refused");
+        };
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        // Closing the first connection frees its slot
+        drop(first);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(connect_async(format!("ws://{addr}")).await.is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_stalled_handshake_frees_its_slot() {
+        let limits = ConnectionLimits {
+            idle_timeout_ms: 200,
+            max_concurrent_connections: 1,
+            ..ConnectionLimits::default()
+        };
+        let addr = start(JsonRpcHandler::new(), limits).await;
+
+        // A client that never sends its handshake only holds the slot until the idle timeout
+        let _stalled = TcpStream::connect(addr).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert!(connect_async(format!("ws://{addr}")).await.is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_idle_connection_is_pinged_then_closed() {
+        let limits = ConnectionLimits {
+            idle_timeout_ms: 200,
+            ..ConnectionLimits::default()
+        };
+        let addr = start(JsonRpcHandler::new(), limits).await;
+
+        let (mut socket, _) = connect_async(format!("ws://{addr}")).await.unwrap();
+
+        // A client that stops reading never answers pings, so the server gives up
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let mut saw_ping = false;
+        let closed = tokio::time::timeout(Duration::from_secs(5), async {
+            while let Some(Ok(message)) = socket.next().await {
+                match message {
+                    Message::Ping(_) => saw_ping = true,
+                    Message::Close(_) => return true,
+                    _ => {}
+                }
+            }
+            true
+        })
+        .await
+        .unwrap();
+
+        assert!(saw_ping);
+        assert!(closed);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_shutdown_drains_in_flight_requests() {
+        let mut handler = JsonRpcHandler::new();
+        handler.register_method("slow", |_params, _ctx| async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            Ok(json!("done"))
+        });
+        let shutdown = CancellationToken::new();
+        let (addr, server) =
+            start_with_shutdown(handler, ConnectionLimits::default(), shutdown.clone()).await;
+
+        let (mut socket, _) = connect_async(format!("ws://{addr}")).await.unwrap();
+        let request = r#"{"jsonrpc":"2.0","method":"slow","id":1}"#;
+        socket.send(Message::Text(request.into())).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        shutdown.cancel();
+
+        assert_eq!(next_response(&mut socket).await.result, Some(json!("done")));
+        let closed = tokio::time::timeout(Duration::from_secs(5), async {
+            while let Some(Ok(message)) = socket.next().await {
+                if matches!(message, Message::Close(_)) {
+                    return true;
+                }
+            }
+            false
+        })
+        .await
+        .unwrap();
+        assert!(closed);
+
+        assert!(server.await.unwrap().is_ok());
+        assert!(TcpStream::connect(addr).await.is_err());
+    }
+}